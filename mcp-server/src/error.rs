@@ -0,0 +1,108 @@
+use std::fmt;
+
+use serde_json::Value;
+
+/// Everything that can go wrong calling out to the ClawProof HTTP API from a
+/// tool handler, typed so callers (ultimately the MCP client) can branch on
+/// `kind()`/`is_retryable()` instead of pattern-matching message text.
+#[derive(Debug)]
+pub enum ClawError {
+    /// The request never got a response — connection refused, DNS failure,
+    /// timeout, etc. Always safe to retry for idempotent (GET) calls.
+    HttpTransport(String),
+    /// The server responded, but with a non-2xx status.
+    BadStatus { code: u16, body: String },
+    /// The response body wasn't the JSON we expected.
+    Decode(String),
+    /// The receipt reached a terminal `"failed"` status.
+    ProofFailed { receipt: Value },
+    /// Polling exhausted its budget before the receipt left `"proving"`.
+    ProofTimeout { receipt_id: String, polls: u32 },
+    /// A required tool argument was missing or the wrong type.
+    MissingArg(&'static str),
+    /// A local filesystem operation (reading a model file, writing a
+    /// downloaded artifact) failed.
+    FileIo(String),
+    /// `tools/call` named a tool this server doesn't define.
+    UnknownTool(String),
+}
+
+impl ClawError {
+    /// A short, stable machine-readable tag for this error variant, so an
+    /// MCP client can branch on `kind` without parsing `message`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ClawError::HttpTransport(_) => "http_transport",
+            ClawError::BadStatus { .. } => "bad_status",
+            ClawError::Decode(_) => "decode",
+            ClawError::ProofFailed { .. } => "proof_failed",
+            ClawError::ProofTimeout { .. } => "proof_timeout",
+            ClawError::MissingArg(_) => "missing_arg",
+            ClawError::FileIo(_) => "file_io",
+            ClawError::UnknownTool(_) => "unknown_tool",
+        }
+    }
+
+    /// Whether the same call is worth retrying as-is: transport-level
+    /// failures and 5xx/429 responses are, 4xx responses and local
+    /// validation/IO problems aren't.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClawError::HttpTransport(_) => true,
+            ClawError::BadStatus { code, .. } => *code >= 500 || *code == 429,
+            ClawError::Decode(_) => false,
+            ClawError::ProofFailed { .. } => false,
+            ClawError::ProofTimeout { .. } => true,
+            ClawError::MissingArg(_) => false,
+            ClawError::FileIo(_) => false,
+            ClawError::UnknownTool(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for ClawError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClawError::HttpTransport(msg) => write!(f, "HTTP transport error: {}", msg),
+            ClawError::BadStatus { code, body } => write!(f, "server returned {}: {}", code, body),
+            ClawError::Decode(msg) => write!(f, "failed to decode response: {}", msg),
+            ClawError::ProofFailed { receipt } => write!(f, "proof failed: {}", receipt),
+            ClawError::ProofTimeout { receipt_id, polls } => {
+                write!(f, "proof generation timed out after {} polls for receipt {}", polls, receipt_id)
+            }
+            ClawError::MissingArg(name) => write!(f, "missing required argument: {}", name),
+            ClawError::FileIo(msg) => write!(f, "file I/O error: {}", msg),
+            ClawError::UnknownTool(name) => write!(f, "unknown tool: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ClawError {}
+
+impl From<reqwest::Error> for ClawError {
+    fn from(e: reqwest::Error) -> Self {
+        ClawError::HttpTransport(e.to_string())
+    }
+}
+
+/// Renders an error as the JSON payload returned to the MCP client, carrying
+/// enough structure (`kind`, `retryable`) to drive automatic retry/backoff
+/// decisions without string-matching `message`.
+pub fn to_mcp_error_value(err: &ClawError) -> Value {
+    let mut obj = serde_json::json!({
+        "error": err.to_string(),
+        "kind": err.kind(),
+        "retryable": err.is_retryable(),
+    });
+    if let ClawError::BadStatus { code, .. } = err {
+        obj["status"] = Value::from(*code);
+    }
+    if let ClawError::ProofTimeout { receipt_id, polls } = err {
+        obj["receipt_id"] = Value::from(receipt_id.clone());
+        obj["polls"] = Value::from(*polls);
+    }
+    if let ClawError::ProofFailed { receipt } = err {
+        obj["receipt"] = receipt.clone();
+    }
+    obj
+}
@@ -1,3 +1,6 @@
+mod error;
+mod metrics;
+mod retry;
 mod tools;
 
 use serde::{Deserialize, Serialize};
@@ -138,48 +141,52 @@ async fn handle_tools_call(
                 }),
             )
         }
-        Err(err_msg) => success_response(
-            id,
-            json!({
-                "content": [
-                    {
-                        "type": "text",
-                        "text": err_msg
-                    }
-                ],
-                "isError": true
-            }),
-        ),
+        Err(err) => {
+            let err_value = error::to_mcp_error_value(&err);
+            let text = match serde_json::to_string_pretty(&err_value) {
+                Ok(s) => s,
+                Err(_) => err_value.to_string(),
+            };
+            success_response(
+                id,
+                json!({
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": text
+                        }
+                    ],
+                    "isError": true
+                }),
+            )
+        }
     }
 }
 
-/// Process a single JSON-RPC request line.
+/// Process a single JSON-RPC request, returning the response to send back or
+/// `None` for a notification (no `id`), which the spec says must not be
+/// replied to. Shared by both the stdio and WebSocket transports below.
 async fn process_request(
     line: &str,
     client: &reqwest::Client,
     base_url: &str,
-) {
+) -> Option<JsonRpcResponse> {
     let request: JsonRpcRequest = match serde_json::from_str(line) {
         Ok(r) => r,
         Err(e) => {
-            let resp = error_response(
+            return Some(error_response(
                 Value::Null,
                 -32700,
                 format!("Parse error: {}", e),
-            );
-            write_response(&resp);
-            return;
+            ));
         }
     };
 
-    let id = request.id.clone().unwrap_or(Value::Null);
-
-    // Notifications (no id) for methods like "notifications/initialized" --
-    // the spec says we must not reply to notifications.
     if request.id.is_none() {
         // Silently accept notifications
-        return;
+        return None;
     }
+    let id = request.id.clone().unwrap_or(Value::Null);
 
     let response = match request.method.as_str() {
         "initialize" => handle_initialize(id),
@@ -193,7 +200,131 @@ async fn process_request(
         ),
     };
 
-    write_response(&response);
+    Some(response)
+}
+
+/// Default transport: line-delimited JSON-RPC over stdin/stdout, one
+/// request per line, driven by a spawned subprocess.
+async fn run_stdio(client: reqwest::Client, base_url: String) {
+    let stdin = io::stdin();
+    let reader = stdin.lock();
+
+    for line_result in reader.lines() {
+        let line = match line_result {
+            Ok(l) => l,
+            Err(_) => break, // stdin closed
+        };
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(response) = process_request(trimmed, &client, &base_url).await {
+            write_response(&response);
+        }
+    }
+}
+
+/// WebSocket transport: the same `initialize`/`tools/list`/`tools/call`/
+/// `ping` dispatch, but over a long-lived socket instead of a spawned
+/// subprocess's stdio, so a browser or persistent agent client can drive
+/// the proving backend directly. Each inbound text message is framed as
+/// one JSON-RPC request; each response is sent back as one text message.
+async fn run_ws(bind_addr: String, client: reqwest::Client, base_url: String) {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = match TcpListener::bind(&bind_addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("[clawproof-mcp] failed to bind {}: {:?}", bind_addr, e);
+            return;
+        }
+    };
+    eprintln!("[clawproof-mcp] WebSocket transport listening on {}", bind_addr);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                eprintln!("[clawproof-mcp] accept failed: {:?}", e);
+                continue;
+            }
+        };
+        let client = client.clone();
+        let base_url = base_url.clone();
+        tokio::spawn(async move {
+            let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+                Ok(ws) => ws,
+                Err(e) => {
+                    eprintln!("[clawproof-mcp] WS handshake with {} failed: {:?}", peer, e);
+                    return;
+                }
+            };
+            let (mut write, mut read) = ws_stream.split();
+            while let Some(msg) = read.next().await {
+                let msg = match msg {
+                    Ok(m) => m,
+                    Err(_) => break,
+                };
+                if !msg.is_text() {
+                    continue;
+                }
+                let text = msg.into_text().unwrap_or_default();
+                if let Some(response) = process_request(&text, &client, &base_url).await {
+                    let out = serde_json::to_string(&response).unwrap_or_default();
+                    if write.send(Message::Text(out)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Optional Prometheus scrape endpoint: every GET gets the same
+/// `metrics::render()` body regardless of path, since this process only
+/// ever exposes the one thing. Gated behind `CLAWPROOF_MCP_METRICS_ADDR`
+/// rather than always-on, since most deployments run this server as a
+/// spawned stdio subprocess with no business binding a port.
+async fn run_metrics_server(bind_addr: String) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    let listener = match TcpListener::bind(&bind_addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("[clawproof-mcp] failed to bind metrics endpoint {}: {:?}", bind_addr, e);
+            return;
+        }
+    };
+    eprintln!("[clawproof-mcp] metrics endpoint listening on {}", bind_addr);
+
+    loop {
+        let (mut stream, _) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                eprintln!("[clawproof-mcp] metrics accept failed: {:?}", e);
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            // Drain (and discard) the request; we don't care about the path.
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+
+            let body = metrics::metrics().render();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+        });
+    }
 }
 
 #[tokio::main]
@@ -209,21 +340,16 @@ async fn main() {
         .build()
         .expect("Failed to build HTTP client");
 
-    // Read JSON-RPC messages from stdin, one per line
-    let stdin = io::stdin();
-    let reader = stdin.lock();
-
-    for line_result in reader.lines() {
-        let line = match line_result {
-            Ok(l) => l,
-            Err(_) => break, // stdin closed
-        };
+    if let Ok(metrics_addr) = std::env::var("CLAWPROOF_MCP_METRICS_ADDR") {
+        tokio::spawn(run_metrics_server(metrics_addr));
+    }
 
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
+    match std::env::var("CLAWPROOF_MCP_TRANSPORT").as_deref() {
+        Ok("ws") => {
+            let bind_addr = std::env::var("CLAWPROOF_MCP_WS_ADDR")
+                .unwrap_or_else(|_| "127.0.0.1:8765".to_string());
+            run_ws(bind_addr, client, base_url).await;
         }
-
-        process_request(trimmed, &client, &base_url).await;
+        _ => run_stdio(client, base_url).await,
     }
 }
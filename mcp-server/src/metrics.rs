@@ -0,0 +1,193 @@
+//! Process-lifetime Prometheus counters/histograms for `tools/call`, so an
+//! operator running this server standalone can see per-tool latency and
+//! error rates instead of only what the backend's own `/metrics` reports.
+//! Mirrors the main crate's `metrics.rs` (hand-rolled text exposition, no
+//! external Prometheus client crate) since this is a separate binary with
+//! no path dependency on it.
+
+use dashmap::DashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+
+const DURATION_BUCKETS_MS: &[f64] = &[50.0, 200.0, 1000.0, 5000.0, 15000.0, 60000.0];
+const PROVE_WALL_TIME_BUCKETS_MS: &[f64] = &[1000.0, 5000.0, 15000.0, 30000.0, 60000.0, 300000.0];
+const PROVE_POLL_BUCKETS: &[f64] = &[1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+struct Histogram {
+    buckets: &'static [f64],
+    /// One counter per bucket bound plus a trailing +Inf counter; each
+    /// counter is already cumulative (an observation increments every
+    /// bucket whose bound is >= the value), matching Prometheus semantics.
+    counts: Vec<AtomicU64>,
+    sum: AtomicU64,
+    total: AtomicU64,
+}
+
+impl Histogram {
+    fn new(buckets: &'static [f64]) -> Self {
+        Self {
+            buckets,
+            counts: (0..=buckets.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value: f64) {
+        self.total.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value.round() as u64, Ordering::Relaxed);
+        for (i, bound) in self.buckets.iter().enumerate() {
+            if value <= *bound {
+                self.counts[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.counts[self.buckets.len()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, labels: &str, out: &mut String) {
+        let label_prefix = if labels.is_empty() { String::new() } else { format!("{},", labels) };
+        for (i, bound) in self.buckets.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{label_prefix}le=\"{bound}\"}} {}",
+                self.counts[i].load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{label_prefix}le=\"+Inf\"}} {}",
+            self.total.load(Ordering::Relaxed)
+        );
+        let label_suffix = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{name}_sum{label_suffix} {}", self.sum.load(Ordering::Relaxed));
+        let _ = writeln!(out, "{name}_count{label_suffix} {}", self.total.load(Ordering::Relaxed));
+    }
+}
+
+/// Per-tool-call instrumentation, recorded once by `tools::call_tool` around
+/// every dispatch.
+pub struct Metrics {
+    requests_total: DashMap<String, AtomicU64>,
+    success_total: DashMap<String, AtomicU64>,
+    /// Keyed by `(tool_name, error_kind)` — `ClawError::kind()` — so an
+    /// operator can tell a `bad_status` failure from a `proof_timeout`.
+    failure_total: DashMap<(String, String), AtomicU64>,
+    duration_ms: DashMap<String, Histogram>,
+    prove_poll_iterations: Histogram,
+    prove_wall_time_ms: Histogram,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Self {
+            requests_total: DashMap::new(),
+            success_total: DashMap::new(),
+            failure_total: DashMap::new(),
+            duration_ms: DashMap::new(),
+            prove_poll_iterations: Histogram::new(PROVE_POLL_BUCKETS),
+            prove_wall_time_ms: Histogram::new(PROVE_WALL_TIME_BUCKETS_MS),
+        }
+    }
+
+    /// Records one `tools/call` dispatch: a request counter, a success or
+    /// failure counter (the latter labeled by `ClawError::kind()`), and a
+    /// per-tool duration observation.
+    pub fn record_call(&self, tool_name: &str, duration_ms: f64, outcome: Result<(), &'static str>) {
+        self.requests_total
+            .entry(tool_name.to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+
+        match outcome {
+            Ok(()) => {
+                self.success_total
+                    .entry(tool_name.to_string())
+                    .or_insert_with(|| AtomicU64::new(0))
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            Err(kind) => {
+                self.failure_total
+                    .entry((tool_name.to_string(), kind.to_string()))
+                    .or_insert_with(|| AtomicU64::new(0))
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        self.duration_ms
+            .entry(tool_name.to_string())
+            .or_insert_with(|| Histogram::new(DURATION_BUCKETS_MS))
+            .observe(duration_ms);
+    }
+
+    /// Records one completed `prove` tool call's poll count and total
+    /// submit-to-terminal wall time, on top of the generic `record_call`
+    /// duration observation for the `prove` tool.
+    pub fn record_prove_completion(&self, poll_iterations: u32, wall_time_ms: f64) {
+        self.prove_poll_iterations.observe(poll_iterations as f64);
+        self.prove_wall_time_ms.observe(wall_time_ms);
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_requests_total Tool calls received, by tool name.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_requests_total counter");
+        for entry in self.requests_total.iter() {
+            let _ = writeln!(
+                out,
+                "clawproof_mcp_requests_total{{tool=\"{}\"}} {}",
+                entry.key(),
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_success_total Tool calls that returned successfully, by tool name.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_success_total counter");
+        for entry in self.success_total.iter() {
+            let _ = writeln!(
+                out,
+                "clawproof_mcp_success_total{{tool=\"{}\"}} {}",
+                entry.key(),
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_failure_total Tool calls that errored, by tool name and ClawError kind.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_failure_total counter");
+        for entry in self.failure_total.iter() {
+            let (tool, kind) = entry.key();
+            let _ = writeln!(
+                out,
+                "clawproof_mcp_failure_total{{tool=\"{}\",kind=\"{}\"}} {}",
+                tool,
+                kind,
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_call_duration_ms End-to-end tool call latency, by tool name.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_call_duration_ms histogram");
+        for entry in self.duration_ms.iter() {
+            entry
+                .value()
+                .render("clawproof_mcp_call_duration_ms", &format!("tool=\"{}\"", entry.key()), &mut out);
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_prove_poll_iterations Number of polls the blocking 'prove' tool took per call.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_prove_poll_iterations histogram");
+        self.prove_poll_iterations.render("clawproof_mcp_prove_poll_iterations", "", &mut out);
+
+        let _ = writeln!(out, "# HELP clawproof_mcp_prove_wall_time_ms Total submit-to-terminal wall time for the blocking 'prove' tool.");
+        let _ = writeln!(out, "# TYPE clawproof_mcp_prove_wall_time_ms histogram");
+        self.prove_wall_time_ms.render("clawproof_mcp_prove_wall_time_ms", "", &mut out);
+
+        out
+    }
+}
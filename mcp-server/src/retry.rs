@@ -0,0 +1,121 @@
+//! Retry helper for the HTTP calls this server makes to the ClawProof
+//! backend. Mirrors the backoff shape of the main crate's `retry.rs`, but
+//! implemented locally since `mcp-server` is a separate crate with no path
+//! dependency on it, and using full jitter (`[0, delay]`) rather than
+//! half-plus-jitter since every call here is already wrapped in the caller's
+//! own timeout budget.
+
+use std::time::Duration;
+
+use crate::error::ClawError;
+
+/// Default retry budget for a single outbound call, used whenever a tool
+/// argument doesn't override it.
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+
+const BASE_BACKOFF_MS: u64 = 250;
+const MAX_BACKOFF_MS: u64 = 8_000;
+
+/// Whether a response's status code is worth retrying: 5xx or 429. Only
+/// meaningful for idempotent calls — a caller making a non-idempotent POST
+/// must not retry once a response has been received, to avoid duplicate
+/// submissions.
+fn is_retriable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status.as_u16() == 429
+}
+
+/// Connection-level failures — dropped connection, DNS failure, timeout —
+/// are always safe to retry, whether or not the call is idempotent.
+pub(crate) fn is_retriable_transport_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+/// Exponential backoff (base 250ms, doubling, capped at 8s) with full
+/// jitter, so concurrent retries of the same endpoint don't wake up in
+/// lockstep. `retry_after` overrides the computed delay when the server
+/// sent a `Retry-After: <seconds>` header.
+pub(crate) fn backoff_with_jitter(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    if let Some(d) = retry_after {
+        return d;
+    }
+    let cap_ms = BASE_BACKOFF_MS
+        .saturating_mul(1u64 << attempt.min(10))
+        .min(MAX_BACKOFF_MS);
+    let jitter_ns = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    Duration::from_millis(jitter_ns % (cap_ms + 1))
+}
+
+fn parse_retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Sends a request via `send`, retrying up to `max_retries` times with
+/// exponential backoff and full jitter. `send` is called again on each
+/// retry so the caller can rebuild the request (reqwest's `RequestBuilder`/
+/// `Form` aren't reusable across attempts).
+///
+/// Connection-level errors are always retried. A non-2xx response is only
+/// retried when `retry_on_status` is true — callers making a non-idempotent
+/// request (e.g. `POST /prove`) must pass `false` so a response that was
+/// actually received is never retried, avoiding duplicate submissions.
+pub async fn retry_send<F, Fut>(
+    max_retries: u32,
+    retry_on_status: bool,
+    mut send: F,
+) -> Result<reqwest::Response, ClawError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
+{
+    for attempt in 0..=max_retries {
+        match send().await {
+            Ok(resp) if retry_on_status && is_retriable_status(resp.status()) => {
+                if attempt == max_retries {
+                    return Ok(resp);
+                }
+                let retry_after = parse_retry_after(&resp);
+                eprintln!(
+                    "[clawproof-mcp] retriable {} response, attempt {}/{}",
+                    resp.status(),
+                    attempt + 1,
+                    max_retries + 1
+                );
+                tokio::time::sleep(backoff_with_jitter(attempt, retry_after)).await;
+            }
+            Ok(resp) => return Ok(resp),
+            Err(e) if is_retriable_transport_error(&e) => {
+                if attempt == max_retries {
+                    return Err(ClawError::from(e));
+                }
+                eprintln!(
+                    "[clawproof-mcp] retriable transport error, attempt {}/{}: {}",
+                    attempt + 1,
+                    max_retries + 1,
+                    e
+                );
+                tokio::time::sleep(backoff_with_jitter(attempt, None)).await;
+            }
+            Err(e) => return Err(ClawError::from(e)),
+        }
+    }
+    unreachable!("loop always returns by the final iteration")
+}
+
+/// Reads an optional `max_retries` tool argument, falling back to
+/// `DEFAULT_MAX_RETRIES` when absent or not a valid integer.
+pub fn read_max_retries(arguments: &serde_json::Value) -> u32 {
+    arguments
+        .get("max_retries")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as u32)
+        .unwrap_or(DEFAULT_MAX_RETRIES)
+}
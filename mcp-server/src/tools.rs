@@ -1,6 +1,18 @@
+use futures_util::stream::{self, StreamExt};
 use serde_json::{json, Value};
+use std::io::SeekFrom;
 use std::path::Path;
 use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use crate::error::ClawError;
+use crate::retry;
+
+/// Default size of one `upload_part` chunk when the caller doesn't override
+/// it — small enough to keep memory use low, large enough that a multi-GB
+/// model doesn't need thousands of round trips.
+const DEFAULT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
 
 /// Returns the JSON array of MCP tool definitions with name, description, and inputSchema.
 pub fn tool_definitions() -> Value {
@@ -27,11 +39,55 @@ pub fn tool_definitions() -> Value {
                     "input_json": {
                         "type": "string",
                         "description": "JSON string representing the input object. For structured_fields models: {\"fields\": {\"field\": value}}. For text models: {\"text\": \"...\"}. For raw models: {\"raw\": [1, 2, 3, ...]}"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures (default applies to both the submission and every poll)."
                     }
                 },
                 "required": ["model_id", "input_json"]
             }
         },
+        {
+            "name": "submit_proof",
+            "description": "Submit a zkML proof generation request and return immediately with the receipt ID and status 'proving', without waiting for the proof to complete. Use poll_proof to check on it later. Prefer this over the blocking 'prove' tool when running several proofs concurrently.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "model_id": {
+                        "type": "string",
+                        "description": "The model ID to run inference and proof on (e.g. 'authorization')"
+                    },
+                    "input_json": {
+                        "type": "string",
+                        "description": "JSON string representing the input object. For structured_fields models: {\"fields\": {\"field\": value}}. For text models: {\"text\": \"...\"}. For raw models: {\"raw\": [1, 2, 3, ...]}"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures submitting this proof."
+                    }
+                },
+                "required": ["model_id", "input_json"]
+            }
+        },
+        {
+            "name": "poll_proof",
+            "description": "Check the current status of a proof in progress with a single, non-blocking request. Returns the receipt (with status 'proving', 'verified', or 'failed') plus an estimated completion hint while still proving.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "receipt_id": {
+                        "type": "string",
+                        "description": "The UUID of the receipt to check, as returned by submit_proof"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this poll."
+                    }
+                },
+                "required": ["receipt_id"]
+            }
+        },
         {
             "name": "verify",
             "description": "Verify a previously generated zkML proof receipt. Returns whether the proof is valid, along with the receipt ID and current status.",
@@ -41,6 +97,10 @@ pub fn tool_definitions() -> Value {
                     "receipt_id": {
                         "type": "string",
                         "description": "The UUID of the receipt to verify"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this call."
                     }
                 },
                 "required": ["receipt_id"]
@@ -55,11 +115,37 @@ pub fn tool_definitions() -> Value {
                     "receipt_id": {
                         "type": "string",
                         "description": "The UUID of the receipt to retrieve"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this call."
                     }
                 },
                 "required": ["receipt_id"]
             }
         },
+        {
+            "name": "download_proof",
+            "description": "Download the raw proof artifact for a verified receipt to a local file, streaming it straight to disk so memory stays flat regardless of proof size. If output_path already has a partial download from a previous interrupted call, resumes from where it left off via an HTTP Range request instead of starting over.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "receipt_id": {
+                        "type": "string",
+                        "description": "The UUID of the receipt whose proof artifact to download"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "Absolute path to write the proof artifact to. If it already exists and is partially written, the download resumes from its current size."
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this call."
+                    }
+                },
+                "required": ["receipt_id", "output_path"]
+            }
+        },
         {
             "name": "upload_model",
             "description": "Upload a custom ONNX model to ClawProof. The model will be registered and preprocessed for proof generation. Maximum file size is 5MB. The model must accept raw integer input vectors.",
@@ -86,243 +172,604 @@ pub fn tool_definitions() -> Value {
                     "trace_length": {
                         "type": "integer",
                         "description": "Jolt trace length as a power of 2 (default: 16384 = 2^14). Larger models need larger traces."
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient connection failures (a response that was actually received is never retried, to avoid duplicate uploads)."
+                    }
+                },
+                "required": ["file_path", "name", "labels", "input_dim"]
+            }
+        },
+        {
+            "name": "begin_upload",
+            "description": "Start a resumable, chunked upload for an ONNX model of any size (unlike 'upload_model', which is capped at 5MB and buffers the whole file in memory). Registers the model's metadata and returns an upload_id; follow with one or more upload_part calls, then complete_upload.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "file_path": {
+                        "type": "string",
+                        "description": "Absolute path to the ONNX model file on the local filesystem, used to determine the total upload size"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Human-readable name for the model"
+                    },
+                    "labels": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Output class labels (e.g. [\"cat\", \"dog\"])"
+                    },
+                    "input_dim": {
+                        "type": "integer",
+                        "description": "Number of input dimensions (length of the input vector)"
+                    },
+                    "trace_length": {
+                        "type": "integer",
+                        "description": "Jolt trace length as a power of 2 (default: 16384 = 2^14). Larger models need larger traces."
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient connection failures."
                     }
                 },
                 "required": ["file_path", "name", "labels", "input_dim"]
             }
+        },
+        {
+            "name": "upload_part",
+            "description": "Upload one chunk of a file previously registered with begin_upload. Parts are numbered from 1 and must be sent in order; resending an already-received part number is safe and just reports current progress, so an interrupted upload can always resume from the next_part reported by the last successful call.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "upload_id": {
+                        "type": "string",
+                        "description": "The upload_id returned by begin_upload"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "Absolute path to the same local file passed to begin_upload"
+                    },
+                    "part_number": {
+                        "type": "integer",
+                        "description": "1-based index of the chunk being sent, matching chunk_size-sized slices of the file"
+                    },
+                    "chunk_size": {
+                        "type": "integer",
+                        "description": "Bytes per part (default: 4194304 = 4MB). Must be the same value for every part of a given upload."
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this part."
+                    }
+                },
+                "required": ["upload_id", "file_path", "part_number"]
+            }
+        },
+        {
+            "name": "complete_upload",
+            "description": "Finalize a resumable upload once all parts have been sent, triggering the same validation and preprocessing as upload_model.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "upload_id": {
+                        "type": "string",
+                        "description": "The upload_id returned by begin_upload"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient connection failures."
+                    }
+                },
+                "required": ["upload_id"]
+            }
+        },
+        {
+            "name": "batch_prove",
+            "description": "Run zkML proof generation for many inputs at once, dispatching them concurrently and polling each to completion. Returns a JSON array in the same order as 'items', where each element is either the completed receipt or a structured error object for that item — one failed item never aborts the rest of the batch.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "items": {
+                        "type": "array",
+                        "description": "Items to prove, each shaped like the 'prove' tool's arguments",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "model_id": { "type": "string" },
+                                "input_json": { "type": "string" }
+                            },
+                            "required": ["model_id", "input_json"]
+                        }
+                    },
+                    "max_concurrency": {
+                        "type": "integer",
+                        "description": "Maximum number of proofs to run concurrently (default: 4)"
+                    }
+                },
+                "required": ["items"]
+            }
+        },
+        {
+            "name": "verify_attestation",
+            "description": "Independently confirm that a verified receipt's ECDSA attestation was signed by the expected prover. Re-derives the attestation message from the receipt's own hashes and recovers the signer, rather than trusting the 'signer' field as reported.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "receipt_id": {
+                        "type": "string",
+                        "description": "The UUID of the receipt to check"
+                    },
+                    "max_retries": {
+                        "type": "integer",
+                        "description": "Override the default retry budget (5) for transient HTTP failures on this call."
+                    }
+                },
+                "required": ["receipt_id"]
+            }
+        },
+        {
+            "name": "get_metrics",
+            "description": "Return this MCP server process's accumulated tool-call metrics (request counts, success/failure counts by error kind, latency histograms, and prove-specific poll-count/wall-time histograms) in Prometheus text format. Useful when the optional CLAWPROOF_MCP_METRICS_ADDR HTTP endpoint isn't reachable from the caller.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {},
+                "required": []
+            }
         }
     ])
 }
 
-/// Dispatch a tool call by name to the appropriate handler.
+/// Dispatch a tool call by name to the appropriate handler, recording a
+/// request/success-or-failure/duration observation for every call — see
+/// `crate::metrics`.
 pub async fn call_tool(
     client: &reqwest::Client,
     base_url: &str,
     tool_name: &str,
     arguments: Value,
-) -> Result<Value, String> {
-    match tool_name {
+) -> Result<Value, ClawError> {
+    let start = std::time::Instant::now();
+
+    let result = match tool_name {
         "list_models" => handle_list_models(client, base_url).await,
+        "submit_proof" => handle_submit_proof(client, base_url, &arguments).await,
+        "poll_proof" => handle_poll_proof(client, base_url, &arguments).await,
         "prove" => handle_prove(client, base_url, &arguments).await,
+        "batch_prove" => handle_batch_prove(client, base_url, &arguments).await,
         "verify" => handle_verify(client, base_url, &arguments).await,
         "get_receipt" => handle_get_receipt(client, base_url, &arguments).await,
+        "download_proof" => handle_download_proof(client, base_url, &arguments).await,
         "upload_model" => handle_upload_model(client, base_url, &arguments).await,
-        _ => Err(format!("Unknown tool: {}", tool_name)),
-    }
+        "begin_upload" => handle_begin_upload(client, base_url, &arguments).await,
+        "upload_part" => handle_upload_part(client, base_url, &arguments).await,
+        "complete_upload" => handle_complete_upload(client, base_url, &arguments).await,
+        "verify_attestation" => handle_verify_attestation(client, base_url, &arguments).await,
+        "get_metrics" => handle_get_metrics(),
+        _ => Err(ClawError::UnknownTool(tool_name.to_string())),
+    };
+
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    crate::metrics::metrics().record_call(tool_name, duration_ms, result.as_ref().map(|_| ()).map_err(|e| e.kind()));
+    result
+}
+
+/// Returns this process's accumulated tool-call metrics in Prometheus text
+/// format, for agents/operators that can't reach the optional
+/// `CLAWPROOF_MCP_METRICS_ADDR` HTTP endpoint directly.
+fn handle_get_metrics() -> Result<Value, ClawError> {
+    Ok(json!({ "prometheus_text": crate::metrics::metrics().render() }))
 }
 
 // ---------------------------------------------------------------------------
 // Tool handlers
 // ---------------------------------------------------------------------------
 
+/// Decodes a response body as JSON, wrapping a decode failure with
+/// `ClawError::Decode`.
+async fn decode_json(resp: reqwest::Response) -> Result<Value, ClawError> {
+    resp.json()
+        .await
+        .map_err(|e| ClawError::Decode(e.to_string()))
+}
+
+/// Turns a non-2xx response into `ClawError::BadStatus`, otherwise passes
+/// the decoded body through.
+fn check_status(status: reqwest::StatusCode, body: Value) -> Result<Value, ClawError> {
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(ClawError::BadStatus {
+            code: status.as_u16(),
+            body: body.to_string(),
+        })
+    }
+}
+
 async fn handle_list_models(
     client: &reqwest::Client,
     base_url: &str,
-) -> Result<Value, String> {
+) -> Result<Value, ClawError> {
     let url = format!("{}/models", base_url);
-    let resp = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request to {} failed: {}", url, e))?;
-
+    let resp = retry::retry_send(retry::DEFAULT_MAX_RETRIES, true, || client.get(&url).send()).await?;
     let status = resp.status();
-    let body: Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response JSON: {}", e))?;
-
-    if !status.is_success() {
-        return Err(format!("GET /models returned {}: {}", status, body));
-    }
-
-    Ok(body)
+    let body = decode_json(resp).await?;
+    check_status(status, body)
 }
 
-async fn handle_prove(
+/// Builds and POSTs the `/prove` request body shared by `submit_proof` and
+/// the blocking `prove` wrapper.
+async fn post_prove(
     client: &reqwest::Client,
     base_url: &str,
     arguments: &Value,
-) -> Result<Value, String> {
+) -> Result<Value, ClawError> {
     let model_id = arguments
         .get("model_id")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: model_id".to_string())?;
+        .ok_or(ClawError::MissingArg("model_id"))?;
 
     let input_json_str = arguments
         .get("input_json")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: input_json".to_string())?;
+        .ok_or(ClawError::MissingArg("input_json"))?;
 
     let input_value: Value = serde_json::from_str(input_json_str)
-        .map_err(|e| format!("Invalid input_json: {}", e))?;
+        .map_err(|e| ClawError::Decode(format!("invalid input_json: {}", e)))?;
 
-    // Build the prove request body
     let prove_body = json!({
         "model_id": model_id,
         "input": input_value
     });
 
-    // POST /prove
     let url = format!("{}/prove", base_url);
-    let resp = client
-        .post(&url)
-        .json(&prove_body)
-        .send()
-        .await
-        .map_err(|e| format!("POST /prove failed: {}", e))?;
+    let max_retries = retry::read_max_retries(arguments);
+    // Non-idempotent: only retry connection-level failures, never a response
+    // that was actually received, to avoid duplicate proof submissions.
+    let resp = retry::retry_send(max_retries, false, || {
+        client.post(&url).json(&prove_body).send()
+    })
+    .await?;
+    let status = resp.status();
+    let body = decode_json(resp).await?;
+    check_status(status, body)
+}
 
+/// Single, non-blocking `GET /receipt/{id}`, annotated with a completion hint
+/// while the proof is still in progress. Shared by `poll_proof` and the
+/// blocking `prove` wrapper's poll loop.
+async fn get_receipt_with_hint(
+    client: &reqwest::Client,
+    base_url: &str,
+    receipt_id: &str,
+    max_retries: u32,
+) -> Result<Value, ClawError> {
+    let receipt_url = format!("{}/receipt/{}", base_url, receipt_id);
+    let resp = retry::retry_send(max_retries, true, || {
+        client
+            .get(&receipt_url)
+            .header("Accept", "application/json")
+            .send()
+    })
+    .await?;
     let status = resp.status();
-    let body: Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse /prove response: {}", e))?;
+    let body = decode_json(resp).await?;
+    let mut body = check_status(status, body)?;
+
+    if body.get("status").and_then(|v| v.as_str()) == Some("proving") {
+        if let Value::Object(map) = &mut body {
+            map.insert(
+                "estimated_completion_hint".to_string(),
+                json!("Proving typically takes 30s-10min depending on model size; poll again in a few seconds."),
+            );
+        }
+    }
 
-    if !status.is_success() {
-        return Err(format!("POST /prove returned {}: {}", status, body));
+    Ok(body)
+}
+
+async fn handle_submit_proof(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let body = post_prove(client, base_url, arguments).await?;
+
+    if body.get("receipt_id").and_then(|v| v.as_str()).is_none() {
+        return Err(ClawError::Decode(format!("no receipt_id in /prove response: {}", body)));
     }
 
+    Ok(body)
+}
+
+async fn handle_poll_proof(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let receipt_id = arguments
+        .get("receipt_id")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("receipt_id"))?;
+
+    let max_retries = retry::read_max_retries(arguments);
+    get_receipt_with_hint(client, base_url, receipt_id, max_retries).await
+}
+
+/// Convenience wrapper around `submit_proof` + repeated `poll_proof` for
+/// callers that would rather block than manage polling themselves. Unlike
+/// `poll_proof`, a `"failed"` terminal status is surfaced as an error since
+/// this tool promises either a completed proof or a definitive failure.
+async fn handle_prove(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let wall_time_start = std::time::Instant::now();
+    let body = post_prove(client, base_url, arguments).await?;
+
     let receipt_id = body
         .get("receipt_id")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "No receipt_id in /prove response".to_string())?
+        .ok_or_else(|| ClawError::Decode(format!("no receipt_id in /prove response: {}", body)))?
         .to_string();
 
-    // Poll GET /receipt/{id} until status is no longer "proving"
-    let receipt_url = format!("{}/receipt/{}", base_url, receipt_id);
-    let max_polls = 120; // up to ~10 minutes at 5s intervals
+    let max_polls: u32 = 120; // up to ~10 minutes at 5s intervals
     let poll_interval = Duration::from_secs(5);
+    let max_retries = retry::read_max_retries(arguments);
 
     for attempt in 0..max_polls {
         if attempt > 0 {
             tokio::time::sleep(poll_interval).await;
         }
 
-        let poll_resp = client
-            .get(&receipt_url)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| format!("GET /receipt/{} failed: {}", receipt_id, e))?;
-
-        let poll_body: Value = poll_resp
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse receipt JSON: {}", e))?;
-
+        let poll_body = get_receipt_with_hint(client, base_url, &receipt_id, max_retries).await?;
         let status_str = poll_body
             .get("status")
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
 
         match status_str {
-            "verified" | "failed" => return Ok(poll_body),
+            "verified" => {
+                crate::metrics::metrics().record_prove_completion(
+                    attempt + 1,
+                    wall_time_start.elapsed().as_secs_f64() * 1000.0,
+                );
+                return Ok(poll_body);
+            }
+            "failed" => {
+                crate::metrics::metrics().record_prove_completion(
+                    attempt + 1,
+                    wall_time_start.elapsed().as_secs_f64() * 1000.0,
+                );
+                return Err(ClawError::ProofFailed { receipt: poll_body });
+            }
             "proving" => continue,
             other => {
-                return Err(format!(
-                    "Unexpected receipt status '{}': {}",
+                return Err(ClawError::Decode(format!(
+                    "unexpected receipt status '{}': {}",
                     other, poll_body
-                ))
+                )))
             }
         }
     }
 
-    Err(format!(
-        "Proof generation timed out after {} polls for receipt {}",
-        max_polls, receipt_id
-    ))
+    crate::metrics::metrics()
+        .record_prove_completion(max_polls, wall_time_start.elapsed().as_secs_f64() * 1000.0);
+    Err(ClawError::ProofTimeout {
+        receipt_id,
+        polls: max_polls,
+    })
+}
+
+/// Proves many items concurrently (bounded by `max_concurrency`) and collects
+/// per-item results in input order, so one failed item doesn't abort the rest.
+async fn handle_batch_prove(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let items = arguments
+        .get("items")
+        .and_then(|v| v.as_array())
+        .ok_or(ClawError::MissingArg("items"))?
+        .clone();
+
+    let max_concurrency = arguments
+        .get("max_concurrency")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(4)
+        .max(1) as usize;
+
+    let results = stream::iter(items.into_iter().enumerate())
+        .map(|(idx, item)| {
+            let client = client.clone();
+            let base_url = base_url.to_string();
+            async move {
+                let result = handle_prove(&client, &base_url, &item).await;
+                (idx, result)
+            }
+        })
+        .buffer_unordered(max_concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut ordered: Vec<Value> = vec![Value::Null; results.len()];
+    for (idx, result) in results {
+        ordered[idx] = match result {
+            Ok(receipt) => receipt,
+            Err(e) => crate::error::to_mcp_error_value(&e),
+        };
+    }
+
+    Ok(Value::Array(ordered))
 }
 
 async fn handle_verify(
     client: &reqwest::Client,
     base_url: &str,
     arguments: &Value,
-) -> Result<Value, String> {
+) -> Result<Value, ClawError> {
     let receipt_id = arguments
         .get("receipt_id")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: receipt_id".to_string())?;
+        .ok_or(ClawError::MissingArg("receipt_id"))?;
 
     let url = format!("{}/verify", base_url);
     let body = json!({ "receipt_id": receipt_id });
+    let max_retries = retry::read_max_retries(arguments);
 
-    let resp = client
-        .post(&url)
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| format!("POST /verify failed: {}", e))?;
-
+    // Read-only check, no side effects, so it's safe to retry on 5xx/429 too.
+    let resp = retry::retry_send(max_retries, true, || client.post(&url).json(&body).send()).await?;
     let status = resp.status();
-    let resp_body: Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse /verify response: {}", e))?;
-
-    if !status.is_success() {
-        return Err(format!("POST /verify returned {}: {}", status, resp_body));
-    }
-
-    Ok(resp_body)
+    let resp_body = decode_json(resp).await?;
+    check_status(status, resp_body)
 }
 
 async fn handle_get_receipt(
     client: &reqwest::Client,
     base_url: &str,
     arguments: &Value,
-) -> Result<Value, String> {
+) -> Result<Value, ClawError> {
     let receipt_id = arguments
         .get("receipt_id")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: receipt_id".to_string())?;
+        .ok_or(ClawError::MissingArg("receipt_id"))?;
 
     let url = format!("{}/receipt/{}", base_url, receipt_id);
-    let resp = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("GET /receipt/{} failed: {}", receipt_id, e))?;
-
+    let max_retries = retry::read_max_retries(arguments);
+    let resp = retry::retry_send(max_retries, true, || {
+        client
+            .get(&url)
+            .header("Accept", "application/json")
+            .send()
+    })
+    .await?;
     let status = resp.status();
-    let body: Value = resp
-        .json()
+    let body = decode_json(resp).await?;
+    check_status(status, body)
+}
+
+/// Downloads a receipt's raw proof artifact to `output_path`, streaming the
+/// response body straight to disk so memory stays flat no matter the proof
+/// size. If `output_path` already holds a partial download from a previous
+/// interrupted call, resumes with `Range: bytes=<current_size>-`; if the
+/// server ignores the range and sends the whole artifact back (status 200
+/// instead of 206), falls back to a full download but still streams it.
+async fn handle_download_proof(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let receipt_id = arguments
+        .get("receipt_id")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("receipt_id"))?;
+
+    let output_path = arguments
+        .get("output_path")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("output_path"))?;
+
+    let existing_len = tokio::fs::metadata(output_path)
         .await
-        .map_err(|e| format!("Failed to parse receipt JSON: {}", e))?;
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let url = format!("{}/receipt/{}/proof", base_url, receipt_id);
+    let max_retries = retry::read_max_retries(arguments);
+    let resp = retry::retry_send(max_retries, true, || {
+        let mut req = client.get(&url);
+        if existing_len > 0 {
+            req = req.header(reqwest::header::RANGE, format!("bytes={}-", existing_len));
+        }
+        req.send()
+    })
+    .await?;
 
+    let status = resp.status();
     if !status.is_success() {
-        return Err(format!(
-            "GET /receipt/{} returned {}: {}",
-            receipt_id, status, body
-        ));
+        let body = decode_json(resp).await?;
+        return check_status(status, body);
     }
 
-    Ok(body)
+    // A 200 (rather than the requested 206) means the server ignored our
+    // Range header, so the body is the whole artifact from byte 0 — start
+    // the file over rather than appending a second copy after our partial.
+    let resumed = existing_len > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = if resumed {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(output_path)
+            .await
+    } else {
+        tokio::fs::File::create(output_path).await
+    }
+    .map_err(|e| ClawError::FileIo(format!("failed to open {}: {}", output_path, e)))?;
+
+    let mut bytes_written: u64 = 0;
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| ClawError::FileIo(format!("failed to write {}: {}", output_path, e)))?;
+        bytes_written += chunk.len() as u64;
+    }
+
+    Ok(json!({
+        "receipt_id": receipt_id,
+        "output_path": output_path,
+        "resumed": resumed,
+        "bytes_written": bytes_written,
+        "total_bytes_on_disk": if resumed { existing_len + bytes_written } else { bytes_written },
+    }))
+}
+
+async fn handle_verify_attestation(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let receipt_id = arguments
+        .get("receipt_id")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("receipt_id"))?;
+
+    let url = format!("{}/attestation/verify", base_url);
+    let body = json!({ "receipt_id": receipt_id });
+    let max_retries = retry::read_max_retries(arguments);
+
+    // Read-only check, no side effects, so it's safe to retry on 5xx/429 too.
+    let resp = retry::retry_send(max_retries, true, || client.post(&url).json(&body).send()).await?;
+    let status = resp.status();
+    let resp_body = decode_json(resp).await?;
+    check_status(status, resp_body)
 }
 
 async fn handle_upload_model(
     client: &reqwest::Client,
     base_url: &str,
     arguments: &Value,
-) -> Result<Value, String> {
+) -> Result<Value, ClawError> {
     let file_path = arguments
         .get("file_path")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: file_path".to_string())?;
+        .ok_or(ClawError::MissingArg("file_path"))?;
 
     let name = arguments
         .get("name")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: name".to_string())?;
+        .ok_or(ClawError::MissingArg("name"))?;
 
     let labels = arguments
         .get("labels")
-        .ok_or_else(|| "Missing required argument: labels".to_string())?;
+        .ok_or(ClawError::MissingArg("labels"))?;
 
     let input_dim = arguments
         .get("input_dim")
         .and_then(|v| v.as_u64())
-        .ok_or_else(|| "Missing required argument: input_dim (must be a positive integer)".to_string())?;
+        .ok_or(ClawError::MissingArg("input_dim"))?;
 
     let trace_length = arguments
         .get("trace_length")
@@ -332,12 +779,12 @@ async fn handle_upload_model(
     // Read the ONNX file from disk
     let path = Path::new(file_path);
     if !path.exists() {
-        return Err(format!("File not found: {}", file_path));
+        return Err(ClawError::FileIo(format!("file not found: {}", file_path)));
     }
 
     let file_bytes = tokio::fs::read(path)
         .await
-        .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+        .map_err(|e| ClawError::FileIo(format!("failed to read {}: {}", file_path, e)))?;
 
     let file_name = path
         .file_name()
@@ -346,41 +793,220 @@ async fn handle_upload_model(
 
     // Serialize labels to JSON string
     let labels_json = serde_json::to_string(labels)
-        .map_err(|e| format!("Failed to serialize labels: {}", e))?;
+        .map_err(|e| ClawError::Decode(format!("failed to serialize labels: {}", e)))?;
 
-    // Build multipart form
-    let onnx_part = reqwest::multipart::Part::bytes(file_bytes)
-        .file_name(file_name)
-        .mime_str("application/octet-stream")
-        .map_err(|e| format!("Failed to create multipart part: {}", e))?;
+    let url = format!("{}/models/upload", base_url);
+    let max_retries = retry::read_max_retries(arguments);
+
+    // Non-idempotent (creates a model): only retry connection-level
+    // failures, never a response that was actually received. `multipart::
+    // Form` is consumed by `send`, so it's rebuilt fresh on every attempt.
+    let resp = retry::retry_send(max_retries, false, || {
+        let onnx_part = reqwest::multipart::Part::bytes(file_bytes.clone())
+            .file_name(file_name.clone())
+            .mime_str("application/octet-stream")
+            .expect("static mime type is always valid");
+        let form = reqwest::multipart::Form::new()
+            .part("onnx_file", onnx_part)
+            .text("name", name.to_string())
+            .text("input_dim", input_dim.to_string())
+            .text("labels", labels_json.clone())
+            .text("trace_length", trace_length.to_string());
+        client.post(&url).multipart(form).send()
+    })
+    .await?;
+    let status = resp.status();
+    let body = decode_json(resp).await?;
+    check_status(status, body)
+}
 
-    let form = reqwest::multipart::Form::new()
-        .part("onnx_file", onnx_part)
-        .text("name", name.to_string())
-        .text("input_dim", input_dim.to_string())
-        .text("labels", labels_json)
-        .text("trace_length", trace_length.to_string());
+/// Registers metadata for a resumable, chunked upload and returns the
+/// `upload_id` that `upload_part`/`complete_upload` key off of. Unlike
+/// `upload_model`, this never reads the file into memory — only its size on
+/// disk, which the server needs to know when the upload is complete.
+async fn handle_begin_upload(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let file_path = arguments
+        .get("file_path")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("file_path"))?;
 
-    let url = format!("{}/models/upload", base_url);
-    let resp = client
-        .post(&url)
-        .multipart(form)
-        .send()
+    let name = arguments
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("name"))?;
+
+    let labels = arguments
+        .get("labels")
+        .ok_or(ClawError::MissingArg("labels"))?;
+
+    let input_dim = arguments
+        .get("input_dim")
+        .and_then(|v| v.as_u64())
+        .ok_or(ClawError::MissingArg("input_dim"))?;
+
+    let trace_length = arguments
+        .get("trace_length")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(16384); // default 2^14
+
+    let path = Path::new(file_path);
+    let total_size = tokio::fs::metadata(path)
         .await
-        .map_err(|e| format!("POST /models/upload failed: {}", e))?;
+        .map_err(|e| ClawError::FileIo(format!("failed to stat {}: {}", file_path, e)))?
+        .len();
+
+    let begin_body = json!({
+        "name": name,
+        "labels": labels,
+        "input_dim": input_dim,
+        "trace_length": trace_length,
+        "total_size": total_size,
+    });
 
+    let url = format!("{}/models/upload/begin", base_url);
+    let max_retries = retry::read_max_retries(arguments);
+    // Non-idempotent (creates a model dir server-side): only retry
+    // connection-level failures, never a response that was actually received.
+    let resp = retry::retry_send(max_retries, false, || {
+        client.post(&url).json(&begin_body).send()
+    })
+    .await?;
     let status = resp.status();
-    let body: Value = resp
-        .json()
+    let body = decode_json(resp).await?;
+    check_status(status, body)
+}
+
+/// Streams one chunk of `file_path` straight from disk into a multipart
+/// request, without ever buffering the whole file — so a single `upload_id`
+/// can carry a model far larger than `upload_model`'s 5MB cap. Retries a
+/// dropped connection or retriable status by reopening and reseeking the
+/// file, since a `reqwest::Body` stream can't be replayed once consumed.
+async fn handle_upload_part(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let upload_id = arguments
+        .get("upload_id")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("upload_id"))?;
+
+    let file_path = arguments
+        .get("file_path")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("file_path"))?;
+
+    let part_number = arguments
+        .get("part_number")
+        .and_then(|v| v.as_u64())
+        .ok_or(ClawError::MissingArg("part_number"))?;
+
+    let chunk_size = arguments
+        .get("chunk_size")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_CHUNK_SIZE);
+
+    let path = Path::new(file_path);
+    let total_size = tokio::fs::metadata(path)
         .await
-        .map_err(|e| format!("Failed to parse /models/upload response: {}", e))?;
+        .map_err(|e| ClawError::FileIo(format!("failed to stat {}: {}", file_path, e)))?
+        .len();
+
+    let offset = part_number.saturating_sub(1) * chunk_size;
+    if offset >= total_size {
+        return Err(ClawError::FileIo(format!(
+            "part_number {} starts at offset {}, but {} is only {} bytes",
+            part_number, offset, file_path, total_size
+        )));
+    }
+    let part_len = chunk_size.min(total_size - offset);
 
-    if !status.is_success() {
-        return Err(format!(
-            "POST /models/upload returned {}: {}",
-            status, body
-        ));
+    let url = format!("{}/models/upload/part", base_url);
+    let max_retries = retry::read_max_retries(arguments);
+
+    for attempt in 0..=max_retries {
+        let mut file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| ClawError::FileIo(format!("failed to open {}: {}", file_path, e)))?;
+        file.seek(SeekFrom::Start(offset))
+            .await
+            .map_err(|e| ClawError::FileIo(format!("failed to seek {}: {}", file_path, e)))?;
+        let chunk_stream = ReaderStream::new(file.take(part_len));
+        let chunk_part = reqwest::multipart::Part::stream_with_length(
+            reqwest::Body::wrap_stream(chunk_stream),
+            part_len,
+        );
+        let form = reqwest::multipart::Form::new()
+            .text("upload_id", upload_id.to_string())
+            .text("part_number", part_number.to_string())
+            .part("chunk", chunk_part);
+
+        match client.post(&url).multipart(form).send().await {
+            Ok(resp) if resp.status().is_server_error() || resp.status().as_u16() == 429 => {
+                if attempt == max_retries {
+                    let status = resp.status();
+                    let body = decode_json(resp).await?;
+                    return check_status(status, body);
+                }
+                eprintln!(
+                    "[clawproof-mcp] retriable {} response uploading part {}, attempt {}/{}",
+                    resp.status(),
+                    part_number,
+                    attempt + 1,
+                    max_retries + 1
+                );
+                tokio::time::sleep(retry::backoff_with_jitter(attempt, None)).await;
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                let body = decode_json(resp).await?;
+                return check_status(status, body);
+            }
+            Err(e) if retry::is_retriable_transport_error(&e) => {
+                if attempt == max_retries {
+                    return Err(ClawError::from(e));
+                }
+                eprintln!(
+                    "[clawproof-mcp] retriable transport error uploading part {}, attempt {}/{}: {}",
+                    part_number,
+                    attempt + 1,
+                    max_retries + 1,
+                    e
+                );
+                tokio::time::sleep(retry::backoff_with_jitter(attempt, None)).await;
+            }
+            Err(e) => return Err(ClawError::from(e)),
+        }
     }
+    unreachable!("loop always returns by the final iteration")
+}
 
-    Ok(body)
+/// Finalizes a resumable upload once every part has landed, triggering the
+/// same validation/preprocessing as `upload_model`.
+async fn handle_complete_upload(
+    client: &reqwest::Client,
+    base_url: &str,
+    arguments: &Value,
+) -> Result<Value, ClawError> {
+    let upload_id = arguments
+        .get("upload_id")
+        .and_then(|v| v.as_str())
+        .ok_or(ClawError::MissingArg("upload_id"))?;
+
+    let complete_body = json!({ "upload_id": upload_id });
+    let url = format!("{}/models/upload/complete", base_url);
+    let max_retries = retry::read_max_retries(arguments);
+    // Non-idempotent (kicks off preprocessing): only retry connection-level
+    // failures, never a response that was actually received.
+    let resp = retry::retry_send(max_retries, false, || {
+        client.post(&url).json(&complete_body).send()
+    })
+    .await?;
+    let status = resp.status();
+    let body = decode_json(resp).await?;
+    check_status(status, body)
 }
@@ -0,0 +1,147 @@
+//! Signed-ticket auth for the `/admin/*` surface.
+//!
+//! Stateless bearer-ticket-plus-CSRF-token scheme, replacing the ad hoc
+//! `ADMIN_SECRET` bearer check `static_update`/`admin_tokens` used to do
+//! independently. An operator logs in once via `POST /admin/login` with
+//! `Config::admin_password`; the response carries an HMAC-signed,
+//! time-limited ticket of the form `admin:<timestamp>:<base64(hmac)>` plus a
+//! CSRF token bound to the same timestamp, so both can be verified without
+//! any server-side session state. [`require_admin`] — layered on every
+//! `/admin/*` route in `main.rs` — checks the ticket's signature and TTL on
+//! every request, and the CSRF token too on mutating verbs.
+//!
+//! Entirely optional, same as `auth`/`proof_archive`: with `admin_password`
+//! unset, every `/admin/*` route 404s rather than accepting any ticket.
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto;
+use crate::state::AppState;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn sign(secret: &str, message: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(message.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn make_ticket(secret: &str, timestamp: i64) -> String {
+    format!("admin:{}:{}", timestamp, sign(secret, &format!("admin{}", timestamp)))
+}
+
+fn make_csrf_token(secret: &str, timestamp: i64) -> String {
+    sign(secret, &format!("csrf{}", timestamp))
+}
+
+fn parse_ticket(value: &str) -> Option<(i64, String)> {
+    let mut parts = value.splitn(3, ':');
+    if parts.next()? != "admin" {
+        return None;
+    }
+    let timestamp: i64 = parts.next()?.parse().ok()?;
+    let mac = parts.next()?.to_string();
+    Some((timestamp, mac))
+}
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct LoginResponse {
+    pub ticket: String,
+    pub csrf_token: String,
+    pub expires_in_secs: u64,
+}
+
+/// POST /admin/login — not itself behind `require_admin` (a ticket can't
+/// exist before this runs), but 404s under the same condition as the rest
+/// of `/admin/*` when the feature is unconfigured.
+pub async fn login(
+    State(state): State<AppState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, StatusCode> {
+    let Some(password) = state.config.admin_password.as_deref() else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let Some(ticket_secret) = state.config.admin_ticket_secret.as_deref() else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    if !crypto::constant_time_eq(&request.password, password) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    Ok(Json(LoginResponse {
+        ticket: make_ticket(ticket_secret, now),
+        csrf_token: make_csrf_token(ticket_secret, now),
+        expires_in_secs: state.config.admin_ticket_ttl_secs,
+    }))
+}
+
+fn unauthorized(hint: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({"error": "Unauthorized", "hint": hint})),
+    )
+        .into_response()
+}
+
+/// Middleware layered onto every `/admin/*` route (except `/admin/login`
+/// itself): verifies the ticket's HMAC and TTL, and — for mutating verbs —
+/// the `X-CSRF-Token` header too. 404s the whole surface when
+/// `admin_password` or `admin_ticket_secret` is unset, same as the bespoke
+/// `ADMIN_SECRET` checks this replaces.
+pub async fn require_admin(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if state.config.admin_password.is_none() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let Some(ticket_secret) = state.config.admin_ticket_secret.as_deref() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let ticket = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .and_then(parse_ticket);
+
+    let Some((timestamp, mac)) = ticket else {
+        return unauthorized("Provide an Authorization: Bearer admin:<timestamp>:<mac> ticket from POST /admin/login");
+    };
+
+    if !crypto::constant_time_eq(&sign(ticket_secret, &format!("admin{}", timestamp)), &mac) {
+        return unauthorized("Ticket signature is invalid");
+    }
+
+    let age_secs = chrono::Utc::now().timestamp() - timestamp;
+    if age_secs < 0 || age_secs as u64 > state.config.admin_ticket_ttl_secs {
+        return unauthorized("Ticket has expired, log in again via POST /admin/login");
+    }
+
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        let csrf_header = req
+            .headers()
+            .get("x-csrf-token")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        if !crypto::constant_time_eq(csrf_header, &make_csrf_token(ticket_secret, timestamp)) {
+            return unauthorized("Missing or invalid X-CSRF-Token header");
+        }
+    }
+
+    next.run(req).await
+}
@@ -0,0 +1,56 @@
+//! Pluggable receipt-announcement backends.
+//!
+//! The posting/heartbeat task used to be hardcoded to Moltbook, including
+//! bespoke verification-challenge solving. Each platform now implements
+//! `Announcer`, and `AnnouncerSet` broadcasts a receipt to every backend a
+//! deployer has enabled via `ANNOUNCE_BACKENDS` — unlike `TrustSourceRegistry`
+//! (which dispatches to exactly one source by scheme), an operator may well
+//! want the same receipt to go out to Moltbook *and* the fediverse at once,
+//! so this holds a flat list rather than a keyed map.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::receipt::Receipt;
+
+pub mod activitypub;
+pub mod moltbook;
+
+/// A platform a verified proof receipt can be announced to.
+#[async_trait]
+pub trait Announcer: Send + Sync {
+    /// Short identifier used only in logs (e.g. "moltbook").
+    fn name(&self) -> &'static str;
+
+    async fn announce(&self, receipt: &Receipt) -> anyhow::Result<()>;
+}
+
+/// The announcers a deployer has enabled, built once at startup and held on
+/// `AppState`.
+#[derive(Clone, Default)]
+pub struct AnnouncerSet {
+    announcers: Arc<Vec<Arc<dyn Announcer>>>,
+}
+
+impl AnnouncerSet {
+    pub fn new(announcers: Vec<Arc<dyn Announcer>>) -> Self {
+        Self {
+            announcers: Arc::new(announcers),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.announcers.is_empty()
+    }
+
+    /// Announce `receipt` on every enabled backend. A failing backend is
+    /// logged and skipped rather than stopping the others.
+    pub async fn announce_all(&self, receipt: &Receipt) {
+        for announcer in self.announcers.iter() {
+            if let Err(e) = announcer.announce(receipt).await {
+                tracing::warn!("[announce] {} failed: {:?}", announcer.name(), e);
+            }
+        }
+    }
+}
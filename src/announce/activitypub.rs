@@ -0,0 +1,167 @@
+//! ActivityPub `Announcer` — federates proof receipts to the fediverse
+//! instead of one proprietary API. Serves an actor document + inbox (wired
+//! up in `handlers::activitypub`) so remote accounts can follow ClawProof,
+//! and on `announce` delivers a `Create{Note}` summarizing the receipt to
+//! every follower's inbox, signed per the same draft-cavage HTTP Signatures
+//! subset `trust_source::fediverse` uses for outbound "authorized fetch"
+//! GETs — just for a POST, with a `Digest` header binding the signature to
+//! the body (see `crypto::sign_http_post`).
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::crypto::{self, HttpSignatureKey};
+use crate::receipt::Receipt;
+use crate::state::ProverBackendKind;
+
+use super::Announcer;
+
+pub const ACTOR_PATH: &str = "/actors/clawproof";
+pub const INBOX_PATH: &str = "/actors/clawproof/inbox";
+
+pub struct ActivityPubAnnouncer {
+    base_url: String,
+    signing_key: Arc<HttpSignatureKey>,
+    public_key_pem: String,
+    client: reqwest::Client,
+    /// Inbox URLs of accounts that have `Follow`ed the actor, populated by
+    /// `handlers::activitypub::inbox` as `Follow` activities arrive.
+    followers: RwLock<Vec<String>>,
+}
+
+impl ActivityPubAnnouncer {
+    pub fn new(base_url: String, signing_key: Arc<HttpSignatureKey>) -> anyhow::Result<Self> {
+        let public_key_pem = crypto::http_signature_public_key_pem(&signing_key)?;
+        Ok(Self {
+            base_url,
+            signing_key,
+            public_key_pem,
+            client: reqwest::Client::new(),
+            followers: RwLock::new(Vec::new()),
+        })
+    }
+
+    pub fn actor_id(&self) -> String {
+        format!("{}{}", self.base_url, ACTOR_PATH)
+    }
+
+    pub fn inbox_url(&self) -> String {
+        format!("{}{}", self.base_url, INBOX_PATH)
+    }
+
+    pub fn webfinger_subject(&self) -> String {
+        let host = self.base_url.split("://").nth(1).unwrap_or(&self.base_url);
+        format!("acct:clawproof@{}", host.trim_end_matches('/'))
+    }
+
+    /// The `Person`/`Service` actor document served at `ACTOR_PATH`.
+    pub fn actor_document(&self) -> Value {
+        let actor_id = self.actor_id();
+        json!({
+            "@context": ["https://www.w3.org/ns/activitystreams", "https://w3id.org/security/v1"],
+            "id": actor_id,
+            "type": "Service",
+            "preferredUsername": "clawproof",
+            "name": "ClawProof",
+            "summary": "Cryptographic proofs of ML inference, announced as they're generated.",
+            "inbox": self.inbox_url(),
+            "publicKey": {
+                "id": format!("{}#main-key", actor_id),
+                "owner": actor_id,
+                "publicKeyPem": self.public_key_pem,
+            },
+        })
+    }
+
+    pub async fn add_follower(&self, inbox: String) {
+        let mut followers = self.followers.write().await;
+        if !followers.contains(&inbox) {
+            info!("[announce:activitypub] new follower inbox: {}", inbox);
+            followers.push(inbox);
+        }
+    }
+
+    fn note_for(&self, receipt: &Receipt) -> Value {
+        let backend_name = ProverBackendKind::from_str(&receipt.backend)
+            .unwrap_or_default()
+            .display_name();
+        let receipt_url = format!("{}/receipt/{}", self.base_url, receipt.id);
+        let content = format!(
+            "Proved ML inference with {}.\n\nModel: {}\nResult: {} ({:.1}% confidence)\n\nReceipt: {}",
+            backend_name, receipt.model_name, receipt.output.label, receipt.output.confidence * 100.0, receipt_url,
+        );
+        let published = chrono::Utc::now().to_rfc3339();
+        let actor_id = self.actor_id();
+        json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "id": format!("{}#proof-{}", actor_id, receipt.id),
+            "type": "Create",
+            "actor": actor_id,
+            "published": published,
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+            "object": {
+                "id": receipt_url,
+                "type": "Note",
+                "attributedTo": actor_id,
+                "content": content,
+                "published": published,
+            },
+        })
+    }
+
+    async fn deliver(&self, inbox: &str, activity: &Value) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(activity)?;
+        let digest = format!("SHA-256={}", crypto::sha256_digest_base64(&body));
+
+        let url = reqwest::Url::parse(inbox)?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let path_and_query = match url.query() {
+            Some(q) => format!("{}?{}", url.path(), q),
+            None => url.path().to_string(),
+        };
+        let date = chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let signature = crypto::sign_http_post(&self.signing_key, &path_and_query, &host, &date, &digest)?;
+
+        self.client
+            .post(inbox)
+            .header("Content-Type", "application/activity+json")
+            .header("Host", host)
+            .header("Date", date)
+            .header("Digest", digest)
+            .header("Signature", signature)
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Announcer for ActivityPubAnnouncer {
+    fn name(&self) -> &'static str {
+        "activitypub"
+    }
+
+    async fn announce(&self, receipt: &Receipt) -> anyhow::Result<()> {
+        let followers = self.followers.read().await.clone();
+        if followers.is_empty() {
+            return Ok(());
+        }
+
+        let activity = self.note_for(receipt);
+        for inbox in &followers {
+            if let Err(e) = self.deliver(inbox, &activity).await {
+                warn!("[announce:activitypub] delivery to {} failed: {:?}", inbox, e);
+            }
+        }
+        Ok(())
+    }
+}
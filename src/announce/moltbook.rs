@@ -0,0 +1,329 @@
+//! Moltbook `Announcer` — the original (and until now, only) posting
+//! backend. Owns the "engagement" pings (home/notifications/feed) Moltbook
+//! expects before a post counts as coming from an active account, and the
+//! verification-challenge solving a post triggers before it goes live.
+//!
+//! `announce` used to rotate through five templates driven by aggregate
+//! stats across *all* receipts (a platform stats roundup, a generic skill
+//! drop, a usage guide, ...). `Announcer::announce` is handed one receipt at
+//! a time, so only the two templates that describe a single receipt
+//! (showcase + technical deep-dive) survive the refactor; they alternate by
+//! an internal cycle counter, same as the submolt rotation did before.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::receipt::Receipt;
+use crate::state::ProverBackendKind;
+
+use super::Announcer;
+
+const BASE: &str = "https://www.moltbook.com/api/v1";
+const SUBMOLTS: [&str; 5] = ["tools", "ai", "programming", "crypto", "openclaw"];
+
+static RE_DUP: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(.)\1{2,}").unwrap());
+static RE_WS: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\s+").unwrap());
+
+pub struct MoltbookAnnouncer {
+    api_key: String,
+    base_url: String,
+    client: reqwest::Client,
+    cycle: AtomicU64,
+}
+
+impl MoltbookAnnouncer {
+    pub fn new(api_key: String, base_url: String) -> Self {
+        Self {
+            api_key,
+            base_url,
+            client: reqwest::Client::new(),
+            cycle: AtomicU64::new(0),
+        }
+    }
+
+    fn auth(&self) -> String {
+        format!("Bearer {}", self.api_key)
+    }
+
+    /// Hit `/home`, read-all `/notifications`, and `/feed` — Moltbook only
+    /// keeps an account's posts from being throttled if it's also browsing,
+    /// not just posting.
+    async fn engage(&self) {
+        let _ = self
+            .client
+            .get(format!("{}/home", BASE))
+            .header("Authorization", self.auth())
+            .send()
+            .await
+            .map(|r| info!("[announce:moltbook] home: {}", r.status()))
+            .map_err(|e| warn!("[announce:moltbook] home failed: {:?}", e));
+
+        if let Ok(resp) = self
+            .client
+            .get(format!("{}/notifications", BASE))
+            .header("Authorization", self.auth())
+            .send()
+            .await
+        {
+            if resp.status().is_success() {
+                let _ = self
+                    .client
+                    .post(format!("{}/notifications/read-all", BASE))
+                    .header("Authorization", self.auth())
+                    .send()
+                    .await;
+            }
+        }
+
+        let _ = self
+            .client
+            .get(format!("{}/feed", BASE))
+            .header("Authorization", self.auth())
+            .send()
+            .await;
+    }
+
+    fn post_for(&self, receipt: &Receipt, cycle: u64) -> (&'static str, String, String) {
+        let submolt = SUBMOLTS[(cycle as usize) % SUBMOLTS.len()];
+        let backend_name = ProverBackendKind::from_str(&receipt.backend)
+            .unwrap_or_default()
+            .display_name();
+        let base = &self.base_url;
+
+        let (title, content) = if cycle % 2 == 0 {
+            (
+                format!(
+                    "Live proof: {} classified as {} ({:.1}% confidence)",
+                    receipt.model_name, receipt.output.label, receipt.output.confidence * 100.0
+                ),
+                format!(
+                    "Just proved ML inference with {}.\n\n\
+                    **Model:** {}\n\
+                    **Result:** {} ({:.1}% confidence)\n\
+                    **Prove time:** {} ms\n\
+                    **Verify time:** {} ms\n\n\
+                    View the receipt: {base}/receipt/{id}\n\
+                    Badge: ![proof]({base}/badge/{id})\n\n\
+                    The proof cryptographically guarantees this model produced this output for this input. Anyone can verify without re-running inference.\n\n\
+                    Open source (MIT): https://github.com/hshadab/clawproof",
+                    backend_name,
+                    receipt.model_name, receipt.output.label, receipt.output.confidence * 100.0,
+                    receipt.prove_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
+                    receipt.verify_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
+                    base = base, id = receipt.id,
+                ),
+            )
+        } else {
+            (
+                format!("Live proof of ML inference — {}", backend_name),
+                format!(
+                    "Generated a real proof of neural network inference with {backend}. The default proof system uses a JOLT lookup-based SNARK with Dory polynomial commitment on BN254.\n\n\
+                    **Cryptographic receipt contains:**\n\
+                    - `model_hash` — Keccak256 commitment to the exact ONNX weights\n\
+                    - `input_hash` — Keccak256 of the input tensor\n\
+                    - `output_hash` — Keccak256 of the inference output\n\
+                    - `proof_hash` — Keccak256 of the serialized SNARK proof\n\n\
+                    View this receipt: {base}/receipt/{id}\n\n\
+                    No API keys. Open source (MIT): https://github.com/hshadab/clawproof",
+                    backend = backend_name, base = base, id = receipt.id,
+                ),
+            )
+        };
+
+        (submolt, title, content)
+    }
+
+    async fn solve_verification(&self, code: &str, challenge: &str) {
+        info!("[announce:moltbook] verification challenge: {}", challenge);
+        let Some(answer) = solve_moltbook_challenge(challenge) else {
+            warn!("[announce:moltbook] could not solve challenge: {}", challenge);
+            return;
+        };
+        info!("[announce:moltbook] solving with answer: {}", answer);
+        let verify_body = serde_json::json!({ "verification_code": code, "answer": answer });
+        match self
+            .client
+            .post(format!("{}/verify", BASE))
+            .header("Authorization", self.auth())
+            .header("Content-Type", "application/json")
+            .body(verify_body.to_string())
+            .send()
+            .await
+        {
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                if status.is_success() {
+                    info!("[announce:moltbook] verification solved, post is live");
+                } else {
+                    warn!("[announce:moltbook] verification failed {}: {}", status, body);
+                }
+            }
+            Err(e) => warn!("[announce:moltbook] verify request failed: {:?}", e),
+        }
+    }
+}
+
+#[async_trait]
+impl Announcer for MoltbookAnnouncer {
+    fn name(&self) -> &'static str {
+        "moltbook"
+    }
+
+    async fn announce(&self, receipt: &Receipt) -> anyhow::Result<()> {
+        self.engage().await;
+
+        let cycle = self.cycle.fetch_add(1, Ordering::Relaxed);
+        let (submolt, title, content) = self.post_for(receipt, cycle);
+
+        let post_body = serde_json::json!({
+            "title": title,
+            "content": content,
+            "submolt": submolt,
+            "type": "text",
+        });
+
+        let resp = self
+            .client
+            .post(format!("{}/posts", BASE))
+            .header("Authorization", self.auth())
+            .header("Content-Type", "application/json")
+            .body(post_body.to_string())
+            .send()
+            .await?;
+
+        let status = resp.status();
+        info!("[announce:moltbook] posted to m/{}: {} — {}", submolt, status, title);
+
+        if let Ok(body) = resp.text().await {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
+                let verification = json
+                    .get("post")
+                    .and_then(|p| p.get("verification"))
+                    .or_else(|| json.get("verification"));
+
+                if let Some(v) = verification {
+                    let code = v.get("verification_code").and_then(|c| c.as_str());
+                    let challenge = v.get("challenge_text").and_then(|c| c.as_str());
+                    if let (Some(code), Some(challenge)) = (code, challenge) {
+                        self.solve_verification(code, challenge).await;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Solve Moltbook verification challenges (lobster-themed arithmetic).
+/// Strips junk chars, extracts number words, determines operation, computes answer.
+fn solve_moltbook_challenge(challenge: &str) -> Option<String> {
+    // Strip non-alpha/space chars, normalize to lowercase
+    let clean: String = challenge
+        .chars()
+        .map(|c| if c.is_alphabetic() || c.is_whitespace() { c.to_ascii_lowercase() } else { ' ' })
+        .collect();
+    // Collapse repeated letters (e.g., "looobster" -> "lobster", "thhree" -> "three")
+    let clean = RE_DUP.replace_all(&clean, "$1$1");
+    // Collapse whitespace
+    let clean = RE_WS.replace_all(&clean, " ");
+
+    let word_to_num: Vec<(&str, f64)> = vec![
+        ("zero", 0.0), ("one", 1.0), ("two", 2.0), ("three", 3.0), ("four", 4.0),
+        ("five", 5.0), ("six", 6.0), ("seven", 7.0), ("eight", 8.0), ("nine", 9.0),
+        ("ten", 10.0), ("eleven", 11.0), ("twelve", 12.0), ("thirteen", 13.0),
+        ("fourteen", 14.0), ("fifteen", 15.0), ("sixteen", 16.0), ("seventeen", 17.0),
+        ("eighteen", 18.0), ("nineteen", 19.0), ("twenty", 20.0), ("thirty", 30.0),
+        ("forty", 40.0), ("fifty", 50.0), ("sixty", 60.0), ("seventy", 70.0),
+        ("eighty", 80.0), ("ninety", 90.0), ("hundred", 100.0),
+    ];
+
+    // Extract all number words in order and build compound numbers
+    let words: Vec<&str> = clean.split_whitespace().collect();
+    let mut numbers: Vec<f64> = Vec::new();
+    let mut current: Option<f64> = None;
+
+    for w in &words {
+        if let Some(&(_, val)) = word_to_num.iter().find(|&&(name, _)| name == *w) {
+            if val == 100.0 {
+                // "hundred" multiplies the current accumulator
+                current = Some(current.unwrap_or(1.0) * 100.0);
+            } else if val >= 20.0 && val < 100.0 {
+                // Tens place — start or extend a compound
+                if let Some(c) = current {
+                    if c < 20.0 {
+                        // previous was a single digit that's part of a different number
+                        numbers.push(c);
+                        current = Some(val);
+                    } else {
+                        numbers.push(c);
+                        current = Some(val);
+                    }
+                } else {
+                    current = Some(val);
+                }
+            } else {
+                // Units (0-19)
+                if let Some(c) = current {
+                    if c >= 20.0 && c % 10.0 == 0.0 && c < 100.0 {
+                        // Compound: twenty + three = 23
+                        current = Some(c + val);
+                    } else {
+                        numbers.push(c);
+                        current = Some(val);
+                    }
+                } else {
+                    current = Some(val);
+                }
+            }
+        } else if current.is_some() {
+            // Non-number word breaks the current compound
+            if let Some(c) = current.take() {
+                numbers.push(c);
+            }
+        }
+    }
+    if let Some(c) = current {
+        numbers.push(c);
+    }
+
+    if numbers.len() < 2 {
+        return None;
+    }
+
+    // Determine operation from cleaned text
+    let is_subtract = clean.contains("slow") || clean.contains("lose")
+        || clean.contains("less") || clean.contains("subtract")
+        || clean.contains("minus") || clean.contains("decreas")
+        || clean.contains("reduc") || clean.contains("drop")
+        || clean.contains("fell") || clean.contains("lost");
+
+    let is_multiply = clean.contains("times") || clean.contains("multipl")
+        || clean.contains("product");
+
+    let is_divide = clean.contains("divid") || clean.contains("split")
+        || clean.contains("per each") || clean.contains("shared equal");
+
+    let a = numbers[0];
+    let b = numbers[1];
+
+    let result = if is_subtract {
+        a - b
+    } else if is_multiply {
+        a * b
+    } else if is_divide && b != 0.0 {
+        a / b
+    } else {
+        a + b // default: addition (total, combined, adds, etc.)
+    };
+
+    if result.fract() == 0.0 {
+        Some(format!("{}", result as i64))
+    } else {
+        Some(format!("{:.2}", result))
+    }
+}
@@ -0,0 +1,148 @@
+//! Typed error taxonomy for `/prove/model` and `/verify`, replacing the
+//! ad-hoc `(StatusCode, Json<ErrorResponse>)` tuples those handlers used to
+//! build by hand at every failure point. Each variant carries a stable
+//! `code` in its JSON body (`{"error", "code", "hint"}`) so a caller can
+//! branch on `code` instead of matching on `error`'s prose — the same role
+//! `ClawError::kind` plays in mcp-server's client-side error handling.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+#[derive(Debug)]
+pub enum ApiError {
+    /// A required multipart field was missing entirely.
+    MissingField(&'static str),
+    /// A multipart field's text couldn't be parsed as the JSON it was
+    /// supposed to contain.
+    InvalidJson { field: &'static str, detail: String },
+    /// A multipart field's bytes couldn't be read off the wire.
+    InvalidMultipart(String),
+    /// Uploaded model file exceeded the 5MB limit.
+    ModelTooLarge,
+    /// Uploaded `input_tensor` file exceeded the 5MB limit.
+    InputTensorTooLarge,
+    /// `source_format` isn't `"onnx"` and no converter sidecar is configured.
+    UnsupportedSourceFormat(String),
+    /// The converter sidecar is unreachable, errored, or returned something
+    /// that couldn't be used.
+    ConverterUnavailable(String),
+    /// The model bytes don't parse as ONNX, before or after conversion.
+    InvalidOnnx(String),
+    /// Preprocessing the model panicked instead of returning an error —
+    /// almost always an operator the backend doesn't support.
+    PreprocessingCrashed,
+    /// Preprocessing returned an error naming the unsupported operation(s).
+    UnsupportedOps(String),
+    /// Caller-supplied input failed validation in a way none of the above
+    /// variants name precisely enough to be worth their own variant.
+    InvalidInput(String),
+    /// Something named by the caller doesn't exist.
+    NotFound(&'static str),
+    /// A local operation (disk IO, background task join) failed in a way
+    /// that isn't the caller's fault.
+    Internal(String),
+}
+
+impl ApiError {
+    /// Stable machine-readable tag serialized as `code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::MissingField(_) => "missing_field",
+            ApiError::InvalidJson { .. } => "invalid_json",
+            ApiError::InvalidMultipart(_) => "invalid_multipart",
+            ApiError::ModelTooLarge => "model_too_large",
+            ApiError::InputTensorTooLarge => "input_tensor_too_large",
+            ApiError::UnsupportedSourceFormat(_) => "unsupported_source_format",
+            ApiError::ConverterUnavailable(_) => "converter_unavailable",
+            ApiError::InvalidOnnx(_) => "invalid_onnx",
+            ApiError::PreprocessingCrashed => "preprocessing_crashed",
+            ApiError::UnsupportedOps(_) => "unsupported_ops",
+            ApiError::InvalidInput(_) => "invalid_input",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::MissingField(_) | ApiError::InvalidJson { .. } | ApiError::InvalidMultipart(_) | ApiError::InvalidInput(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            ApiError::ModelTooLarge | ApiError::InputTensorTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::UnsupportedSourceFormat(_) => StatusCode::NOT_IMPLEMENTED,
+            ApiError::ConverterUnavailable(_) => StatusCode::BAD_GATEWAY,
+            ApiError::InvalidOnnx(_) | ApiError::PreprocessingCrashed | ApiError::UnsupportedOps(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::MissingField(field) => format!("Missing {} field", field),
+            ApiError::InvalidJson { field, detail } => format!("Invalid {} JSON: {}", field, detail),
+            ApiError::InvalidMultipart(detail) => format!("Failed to read model file: {}", detail),
+            ApiError::ModelTooLarge => "Model file exceeds 5MB limit".to_string(),
+            ApiError::InputTensorTooLarge => "input_tensor file exceeds 5MB limit".to_string(),
+            ApiError::UnsupportedSourceFormat(format) => format!("Conversion from '{}' requires the converter sidecar", format),
+            ApiError::ConverterUnavailable(detail) => detail.clone(),
+            ApiError::InvalidOnnx(detail) => detail.clone(),
+            ApiError::PreprocessingCrashed => "Model preprocessing crashed — likely uses unsupported operations".to_string(),
+            ApiError::UnsupportedOps(detail) => format!("Model preprocessing failed: {}", detail),
+            ApiError::InvalidInput(detail) => detail.clone(),
+            ApiError::NotFound(what) => format!("{} not found", what),
+            ApiError::Internal(detail) => detail.clone(),
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            ApiError::MissingField(field) if *field == "onnx_file or model_file" => {
+                Some("Upload an ONNX model as multipart field 'onnx_file'".to_string())
+            }
+            ApiError::MissingField(field) if *field == "input_raw" => {
+                Some("Provide input as JSON array: input_raw=[0, 1, 2, ...]".to_string())
+            }
+            ApiError::MissingField(field) if *field == "input_raw or input_tensor" => {
+                Some("Provide input_raw as a JSON array, or upload input_tensor as a .npy/.npz file".to_string())
+            }
+            ApiError::MissingField(_) => None,
+            ApiError::InvalidJson { field, .. } if *field == "input_raw" => {
+                Some("Provide a JSON array of integers, e.g. [0, 1, 2, ...]".to_string())
+            }
+            ApiError::InvalidJson { field, .. } if *field == "input_shape" => {
+                Some("Provide a JSON array of dimensions, e.g. [1, 1, 28, 28]".to_string())
+            }
+            ApiError::InvalidJson { .. } => None,
+            ApiError::UnsupportedSourceFormat(_) => Some("Upload an ONNX file directly, or wait for the converter service".to_string()),
+            ApiError::NotFound("Receipt") => Some("Check the receipt_id and try GET /receipt/{id}".to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Wire shape for `IntoResponse` — a strict superset of `handlers::prove::
+/// ErrorResponse` (adds `code`), kept as a private struct here rather than
+/// widening `ErrorResponse` itself since most handlers sharing that type
+/// still build ad-hoc tuples and don't have a stable code to report.
+#[derive(Serialize)]
+struct ApiErrorBody {
+    error: String,
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = ApiErrorBody {
+            error: self.message(),
+            code: self.code(),
+            hint: self.hint(),
+        };
+        (status, Json(body)).into_response()
+    }
+}
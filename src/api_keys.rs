@@ -0,0 +1,196 @@
+//! Opaque bearer-token auth for the proving routes — distinct from
+//! `auth::require_auth`'s signed JWTs: a key here is just a random token
+//! looked up in a map, carrying a label (stamped onto
+//! `Receipt::api_key_label` so `recent` can filter by caller) and an
+//! optional per-minute quota enforced with the same token-bucket approach
+//! as `rate_limit::RateLimiter`, just scoped to the key itself rather than
+//! a shared tier ceiling.
+//!
+//! Entirely optional, same as `auth`/`admin_auth`: with no keys configured
+//! (`API_KEYS` unset and none issued via `POST /admin/api-keys`),
+//! [`require_api_key`] passes every request through unauthenticated.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use dashmap::DashMap;
+
+use crate::config::ApiKeySeed;
+use crate::handlers::prove::ErrorResponse;
+use crate::state::AppState;
+
+#[derive(Clone)]
+struct KeyRecord {
+    label: String,
+    quota_per_min: Option<f64>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Identity stashed in request extensions by a successful `require_api_key`
+/// check, read back via `Extension<Option<ApiKeyIdentity>>` by handlers
+/// that stamp or filter on it.
+#[derive(Clone, Debug)]
+pub struct ApiKeyIdentity {
+    pub label: String,
+}
+
+/// Token store backing `require_api_key`. Cloning shares the underlying
+/// maps (cheap `Arc` clone), same as `rate_limit::RateLimiter`.
+#[derive(Clone)]
+pub struct ApiKeyStore {
+    keys: Arc<DashMap<String, KeyRecord>>,
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl ApiKeyStore {
+    pub fn new() -> Self {
+        Self {
+            keys: Arc::new(DashMap::new()),
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Load `Config::api_keys`, parsed from `API_KEYS` at startup.
+    pub fn seed(seeds: &[ApiKeySeed]) -> Self {
+        let store = Self::new();
+        for seed in seeds {
+            store.keys.insert(
+                seed.token.clone(),
+                KeyRecord {
+                    label: seed.label.clone(),
+                    quota_per_min: seed.quota_per_min,
+                },
+            );
+        }
+        store
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Mint a new opaque token for `label`, with an optional per-minute quota.
+    pub fn issue(&self, label: &str, quota_per_min: Option<f64>) -> String {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        self.keys.insert(
+            token.clone(),
+            KeyRecord {
+                label: label.to_string(),
+                quota_per_min,
+            },
+        );
+        token
+    }
+
+    /// Returns `true` if `token` was known (and is now removed).
+    pub fn revoke(&self, token: &str) -> bool {
+        self.buckets.remove(token);
+        self.keys.remove(token).is_some()
+    }
+
+    fn lookup(&self, token: &str) -> Option<KeyRecord> {
+        self.keys.get(token).map(|r| r.clone())
+    }
+
+    /// Token-bucket check against `token`'s own quota rather than a shared
+    /// tier ceiling. `None` quota means unlimited — no bucket is consulted.
+    fn check_quota(&self, token: &str, quota_per_min: Option<f64>) -> Result<(), f64> {
+        let Some(capacity) = quota_per_min else {
+            return Ok(());
+        };
+        let refill_per_sec = capacity / 60.0;
+        let now = Instant::now();
+        let mut bucket = self.buckets.entry(token.to_string()).or_insert_with(|| Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err((1.0 - bucket.tokens) / refill_per_sec)
+        }
+    }
+}
+
+impl Default for ApiKeyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn unauthorized(hint: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            error: "Unauthorized".to_string(),
+            hint: Some(hint.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+fn too_many_requests(retry_after_secs: f64) -> Response {
+    let retry_after = retry_after_secs.ceil().max(1.0) as u64;
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ErrorResponse {
+            error: "Too Many Requests".to_string(),
+            hint: Some(format!("Retry after {} second(s)", retry_after)),
+        }),
+    )
+        .into_response();
+    if let Ok(value) = axum::http::HeaderValue::from_str(&retry_after.to_string()) {
+        response
+            .headers_mut()
+            .insert(axum::http::header::RETRY_AFTER, value);
+    }
+    response
+}
+
+/// Middleware for the proving routes. A no-op passthrough when no API keys
+/// are configured at all (`Config::api_keys` empty and none issued since);
+/// otherwise requires a known `Authorization: Bearer <token>`, rejecting
+/// missing/unknown tokens with `401`, and enforces that token's own quota
+/// (if any) with a `429` + `Retry-After` hint when exceeded. Stashes
+/// [`ApiKeyIdentity`] in request extensions on success.
+pub async fn require_api_key(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    if state.api_keys.is_empty() {
+        return next.run(req).await;
+    }
+
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    let Some(token) = token else {
+        return unauthorized("Provide an Authorization: Bearer <api-key> header");
+    };
+
+    let Some(record) = state.api_keys.lookup(token) else {
+        return unauthorized("Unknown API key");
+    };
+
+    if let Err(retry_after_secs) = state.api_keys.check_quota(token, record.quota_per_min) {
+        return too_many_requests(retry_after_secs);
+    }
+
+    req.extensions_mut().insert(ApiKeyIdentity { label: record.label });
+    next.run(req).await
+}
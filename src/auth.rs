@@ -0,0 +1,89 @@
+//! HS256 JSON Web Tokens gating the heavier proving/upload routes.
+//!
+//! Entirely optional: with `Config::jwt_secret` unset, [`require_auth`]
+//! passes every request through unauthenticated, so the public playground
+//! keeps working exactly as before this existed. Set `JWT_SECRET` to require
+//! a valid `Authorization: Bearer <token>` on whatever routes the middleware
+//! is layered onto, and mint tokens with `POST /admin/tokens`
+//! (`handlers::admin_tokens`).
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::prove::ErrorResponse;
+use crate::state::AppState;
+
+/// Claims embedded in every issued token. `tier` is the one custom field
+/// handlers key quota/feature decisions off of — `"free"` or `"pro"` today,
+/// but any string the token was minted with round-trips unchanged.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub iat: usize,
+    pub exp: usize,
+    pub tier: String,
+}
+
+/// Mints a token for `sub` at the given `tier`, expiring `ttl_secs` from now.
+pub fn issue_token(secret: &str, sub: &str, tier: &str, ttl_secs: i64) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = chrono::Utc::now();
+    let claims = Claims {
+        sub: sub.to_string(),
+        iat: now.timestamp() as usize,
+        exp: (now + chrono::Duration::seconds(ttl_secs)).timestamp() as usize,
+        tier: tier.to_string(),
+    };
+    encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(secret.as_bytes()))
+}
+
+fn validate_token(secret: &str, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode::<Claims>(token, &DecodingKey::from_secret(secret.as_bytes()), &Validation::new(Algorithm::HS256))
+        .map(|data| data.claims)
+}
+
+fn unauthorized(hint: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            error: "Unauthorized".to_string(),
+            hint: Some(hint.to_string()),
+        }),
+    )
+        .into_response()
+}
+
+/// Tower middleware for the routes that should require a bearer token. A
+/// no-op passthrough when `Config::jwt_secret` is unset; otherwise requires
+/// a valid, unexpired token and stashes its [`Claims`] in request extensions
+/// so handlers can read the caller's `tier` via `Extension<Claims>`.
+pub async fn require_auth(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    let Some(secret) = state.config.jwt_secret.as_deref() else {
+        return next.run(req).await;
+    };
+
+    let token = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    let Some(token) = token else {
+        return unauthorized("Provide an Authorization: Bearer <token> header");
+    };
+
+    match validate_token(secret, token) {
+        Ok(claims) => {
+            req.extensions_mut().insert(claims);
+            next.run(req).await
+        }
+        Err(e) => {
+            tracing::warn!("[clawproof] Rejected request with invalid bearer token: {:?}", e);
+            unauthorized("Token is invalid or expired")
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Capacity pre-flight: estimates how many JOLT execution steps a model's
+//! ONNX graph needs before committing to a `trace_length` for proving
+//! preprocessing, instead of trusting a hand-tuned constant from
+//! `model.toml`. A model's step count depends on its graph structure and op
+//! count, not the input values, so a synthetic all-zero input is
+//! representative for this estimate.
+
+use dashmap::DashMap;
+use onnx_tracer::{model, tensor::Tensor};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Smallest trace length worth preprocessing — anything below this still
+/// rounds up to it rather than degenerating to near-zero capacity.
+const MIN_TRACE_LENGTH: usize = 1 << 8;
+
+/// Per-model dry-run estimates, keyed by model id, so tracing only ever
+/// runs once per model rather than once per preprocessing attempt.
+static ESTIMATE_CACHE: LazyLock<DashMap<String, usize>> = LazyLock::new(DashMap::new);
+
+/// Runs the model's execution tracer against a zero-valued probe input and
+/// rounds the observed step count up to the next power of two. Cached per
+/// `model_id` after the first call.
+pub fn estimate_trace_length(model_id: &str, model_path: &Path, input_shape: &[usize]) -> anyhow::Result<usize> {
+    if let Some(cached) = ESTIMATE_CACHE.get(model_id) {
+        return Ok(*cached);
+    }
+
+    let element_count: usize = input_shape.iter().product();
+    let probe_input = Tensor::new(Some(&vec![0i32; element_count]), input_shape)?;
+    let step_count = dry_run_step_count(model_path, &probe_input)?;
+    let trace_length = step_count.max(MIN_TRACE_LENGTH).next_power_of_two();
+
+    ESTIMATE_CACHE.insert(model_id.to_string(), trace_length);
+    Ok(trace_length)
+}
+
+/// Traces `model_path` against `input` and returns the number of JOLT steps
+/// the interpreter actually executed — the same lightweight pass `/prove`
+/// re-runs on the concrete request input to check it still fits the
+/// preprocessed trace.
+pub fn dry_run_step_count(model_path: &Path, input: &Tensor<i32>) -> anyhow::Result<usize> {
+    let model_instance = model(model_path);
+    let trace = model_instance
+        .trace(&[input.clone()])
+        .map_err(|e| anyhow::anyhow!("onnx_tracer dry run failed: {}", e))?;
+    Ok(trace.step_count())
+}
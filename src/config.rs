@@ -9,7 +9,259 @@ pub struct Config {
     pub database_path: PathBuf,
     pub cors_origins: Option<String>,
     pub uploaded_models_dir: PathBuf,
+    pub proofs_dir: PathBuf,
     pub converter_url: Option<String>,
+    pub scrub_interval_secs: u64,
+    pub max_cache_entries: usize,
+    /// Upper bound on the auto-estimated `trace_length` a model is allowed
+    /// to preprocess with. A model whose dry-run step count rounds up past
+    /// this is marked `unsupported` in the registry instead of being handed
+    /// to `Snark::prover_preprocess`, which would otherwise eat the full
+    /// preprocessing cost before failing (or worse, succeed against a trace
+    /// too large to prove in practice).
+    pub max_trace_length: usize,
+    pub attestation_signing_key: Option<String>,
+    pub default_locale: String,
+    pub brand: BrandConfig,
+    /// Actor key-id (e.g. `https://this-host/actors/clawproof#main-key`) used
+    /// to sign outbound Fediverse "authorized fetch" requests. Paired with
+    /// `http_signature_private_key_pem`; both must be set to enable signing.
+    pub http_signature_key_id: Option<String>,
+    /// PKCS#8 PEM-encoded RSA private key matching `http_signature_key_id`.
+    pub http_signature_private_key_pem: Option<String>,
+    /// How long a cached `agent_lookup` profile is served before it's
+    /// considered due for a refetch.
+    pub profile_cache_ttl_secs: u64,
+    /// "memory" (default) or "s3" — selects the `ProfileCache` backend.
+    pub profile_cache_backend: String,
+    pub profile_cache_s3_bucket: Option<String>,
+    pub profile_cache_s3_endpoint: Option<String>,
+    pub profile_cache_s3_region: Option<String>,
+    /// Presence of this bucket gates the whole proof-archive feature — with
+    /// it unset, proofs are never uploaded and `proofs_dir` stays the only
+    /// copy, same as before this existed.
+    pub proof_archive_s3_bucket: Option<String>,
+    pub proof_archive_s3_endpoint: Option<String>,
+    pub proof_archive_s3_region: Option<String>,
+    /// Presence of this secret gates the whole `auth` module — unset, the
+    /// `require_auth` middleware passes every request through
+    /// unauthenticated so the public playground keeps working.
+    pub jwt_secret: Option<String>,
+    /// Per-subject token-bucket ceilings for `POST /prove`, keyed on the
+    /// caller's `tier` claim (or the `free` tier for anonymous callers).
+    /// Replaces what used to be one `RateLimitLayer` shared by every client.
+    pub prove_rate_limit: RateLimitConfig,
+    pub batch_rate_limit: RateLimitConfig,
+    pub upload_rate_limit: RateLimitConfig,
+    pub prove_model_rate_limit: RateLimitConfig,
+    /// Login password for `POST /admin/login`. Presence gates the whole
+    /// `/admin/*` surface — unset, every admin route 404s rather than
+    /// accepting any ticket, same as the `ADMIN_SECRET` check it replaces.
+    pub admin_password: Option<String>,
+    /// HMAC key signing/verifying admin tickets and CSRF tokens. Kept
+    /// separate from `admin_password` so a leaked ticket secret alone can't
+    /// be used to log in fresh.
+    pub admin_ticket_secret: Option<String>,
+    /// How long an admin ticket from `POST /admin/login` stays valid.
+    pub admin_ticket_ttl_secs: u64,
+    /// Signs outbound webhook/callback deliveries with an
+    /// `X-Clawproof-Signature: sha256=<hex(HMAC)>` header so receivers can
+    /// verify a payload actually came from this server. Optional — with it
+    /// unset, webhooks are delivered unsigned, same as before this existed.
+    pub webhook_signing_secret: Option<String>,
+    /// API key for the `moltbook` `trust_source` and `announce` backends.
+    /// Unset disables both.
+    pub moltbook_api_key: Option<String>,
+    /// Which `announce::Announcer` backends are active (e.g. `["moltbook",
+    /// "activitypub"]`), read from a comma-separated `ANNOUNCE_BACKENDS`.
+    /// Empty by default — a deployer opts in explicitly, same as the old
+    /// Moltbook heartbeat only ran when `MOLTBOOK_API_KEY` was set.
+    pub announce_backends: Vec<String>,
+    /// How often the announce heartbeat picks up the most recently verified
+    /// receipt and broadcasts it to every enabled `announce_backends` entry.
+    pub announce_interval_secs: u64,
+    /// Where the Ed25519 signing key for `?format=vc` Verifiable Credentials
+    /// is stored (its raw 32-byte seed). Generated on first run if missing,
+    /// so the server's `did:key` identity stays stable across restarts.
+    pub signing_key_path: PathBuf,
+    /// How many proving runs `queue::spawn_dispatcher` lets run concurrently
+    /// — the permit count of its `tokio::sync::Semaphore`. Bounds CPU/memory
+    /// use under a burst of `/prove`/`/batch_prove` calls instead of
+    /// spawning one SNARK proving task per request.
+    pub prove_concurrency: usize,
+    /// Opaque bearer tokens for `api_keys::require_api_key`, seeded at
+    /// startup from `API_KEYS` (also growable at runtime via
+    /// `POST /admin/api-keys`). Empty by default — with no keys configured,
+    /// the proving routes stay open, same as `jwt_secret` unset disables
+    /// `auth::require_auth`.
+    pub api_keys: Vec<ApiKeySeed>,
+    /// HMAC key verifying a signed `policy` grant's `x-amz-signature` on
+    /// `POST /models/upload` (see `models::UploadPolicy`). A `policy` field is
+    /// only ever checked if it's submitted — unset secret or not, an upload
+    /// with no `policy` field goes through the old unauthenticated path
+    /// unchanged. With the secret unset, a submitted policy can never
+    /// verify, so it's rejected with 403 rather than silently accepted.
+    pub upload_policy_secret: Option<String>,
+    /// "local" (default) or "s3" — selects the `model_store::ModelStore`
+    /// backend `upload_model` and its preprocessing step read/write
+    /// through, same selection shape as `profile_cache_backend`.
+    pub model_store_backend: String,
+    pub model_store_s3_bucket: Option<String>,
+    pub model_store_s3_endpoint: Option<String>,
+    pub model_store_s3_region: Option<String>,
+    /// Upper bound on an `onnx_file` upload, enforced while the multipart
+    /// field is still streaming in rather than after the whole thing has
+    /// been buffered. Defaults to the 5MB limit `upload_model` used to hard-code.
+    pub max_upload_bytes: usize,
+    /// How many `Snark::prover_preprocess` runs `preprocess_queue::spawn_dispatcher`
+    /// ever allows in flight at once, same shape as `prove_concurrency`.
+    pub preprocess_concurrency: usize,
+    /// How many runs a preprocessing job gets in all (the first attempt plus
+    /// retries, with exponential backoff between them) before
+    /// `preprocess_queue::PreprocessJobStatus::Failed` becomes permanent.
+    pub preprocess_max_attempts: u32,
+    /// Base delay before a preprocessing job's first retry; each
+    /// subsequent retry doubles it.
+    pub preprocess_backoff_base_secs: u64,
+    /// Upper bound on `begin_upload`'s declared `total_size` and the
+    /// cumulative bytes a session's `upload_part` calls may write — the
+    /// resumable path's counterpart to `max_upload_bytes`, but deliberately
+    /// its own (much larger) setting since resumable upload exists
+    /// specifically for files past the single-shot cap.
+    pub max_resumable_upload_bytes: u64,
+    /// Upper bound on how many `begin_upload` sessions can be in flight at
+    /// once — each one holds an on-disk staging file until `complete_upload`
+    /// or the reaper below cleans it up, so this is the resumable upload
+    /// path's disk-exhaustion backstop, same role `max_upload_bytes` plays
+    /// for the single-shot path.
+    pub max_pending_uploads: usize,
+    /// How long an unfinished `begin_upload` session is kept before the
+    /// background reaper deletes its staging file and forgets it — guards
+    /// against a client that starts an upload and never finishes it.
+    pub pending_upload_ttl_secs: u64,
+    /// How long a rate-limit bucket can sit idle before the background
+    /// reaper evicts it — `RateLimiter::buckets` is keyed by client IP for
+    /// unauthenticated callers and is otherwise never cleaned up, so without
+    /// this a large number of distinct source IPs (trivial with IPv6) grows
+    /// it forever.
+    pub rate_limit_bucket_ttl_secs: u64,
+}
+
+/// One `API_KEYS` entry — `token:label` or `token:label:quota_per_min`.
+#[derive(Clone, Debug)]
+pub struct ApiKeySeed {
+    pub token: String,
+    pub label: String,
+    /// `None` means unlimited — no token-bucket is applied for this key.
+    pub quota_per_min: Option<f64>,
+}
+
+impl ApiKeySeed {
+    fn parse(entry: &str) -> Option<Self> {
+        let mut parts = entry.splitn(3, ':');
+        let token = parts.next()?.trim();
+        let label = parts.next()?.trim();
+        if token.is_empty() || label.is_empty() {
+            return None;
+        }
+        let quota_per_min = parts.next().and_then(|v| v.trim().parse().ok());
+        Some(Self {
+            token: token.to_string(),
+            label: label.to_string(),
+            quota_per_min,
+        })
+    }
+}
+
+/// Token-bucket capacity and refill rate for one rate-limited route, split
+/// by the caller's `tier` claim. A `pro` caller gets a higher ceiling than
+/// `free`; anyone with no token at all (or a server with `jwt_secret`
+/// unset) is always bucketed at `free`.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitConfig {
+    pub free_capacity: f64,
+    pub free_refill_per_sec: f64,
+    pub pro_capacity: f64,
+    pub pro_refill_per_sec: f64,
+}
+
+impl RateLimitConfig {
+    /// `default_capacity`/`default_window_secs` describe the `free` tier
+    /// (and match the limits the old global `RateLimitLayer`s used);
+    /// `pro` defaults to `pro_multiplier` times as generous.
+    fn from_env(env_prefix: &str, default_capacity: f64, default_window_secs: f64, pro_multiplier: f64) -> Self {
+        let default_refill_per_sec = default_capacity / default_window_secs;
+
+        let env_f64 = |suffix: &str, default: f64| -> f64 {
+            std::env::var(format!("{}_{}", env_prefix, suffix))
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            free_capacity: env_f64("FREE_CAPACITY", default_capacity),
+            free_refill_per_sec: env_f64("FREE_REFILL_PER_SEC", default_refill_per_sec),
+            pro_capacity: env_f64("PRO_CAPACITY", default_capacity * pro_multiplier),
+            pro_refill_per_sec: env_f64("PRO_REFILL_PER_SEC", default_refill_per_sec * pro_multiplier),
+        }
+    }
+
+    /// Returns `(capacity, refill_per_sec)` for the given `tier` string,
+    /// falling back to `free` for anything other than `"pro"`.
+    pub fn for_tier(&self, tier: &str) -> (f64, f64) {
+        match tier {
+            "pro" => (self.pro_capacity, self.pro_refill_per_sec),
+            _ => (self.free_capacity, self.free_refill_per_sec),
+        }
+    }
+}
+
+/// Per-instance white-label customization for the receipt page, following
+/// Firefox Send's deployer-branding model. Every field is optional except
+/// `wordmark` and `default_theme`, which fall back to the stock ClawProof
+/// look when unset.
+#[derive(Clone, Debug)]
+pub struct BrandConfig {
+    pub wordmark: String,
+    pub logo_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub accent: Option<String>,
+    pub green: Option<String>,
+    pub amber: Option<String>,
+    pub red: Option<String>,
+    /// Raw HTML rendered in place of the default footer links. Deployers
+    /// own this string end-to-end; it is not escaped.
+    pub footer_html: Option<String>,
+    /// One of "auto", "light", "dark" — the theme a fresh visitor (no
+    /// `cp-theme` in localStorage yet) sees. "auto" follows
+    /// `prefers-color-scheme` until the visitor toggles explicitly.
+    pub default_theme: String,
+}
+
+impl BrandConfig {
+    fn from_env() -> Self {
+        let default_theme = std::env::var("BRAND_DEFAULT_THEME").unwrap_or_else(|_| "dark".to_string());
+        let default_theme = match default_theme.as_str() {
+            "auto" | "light" | "dark" => default_theme,
+            _ => {
+                warn!("[clawproof] Invalid BRAND_DEFAULT_THEME value, defaulting to dark");
+                "dark".to_string()
+            }
+        };
+
+        Self {
+            wordmark: std::env::var("BRAND_WORDMARK").unwrap_or_else(|_| "ClawProof".to_string()),
+            logo_url: std::env::var("BRAND_LOGO_URL").ok(),
+            favicon_url: std::env::var("BRAND_FAVICON_URL").ok(),
+            accent: std::env::var("BRAND_ACCENT_COLOR").ok(),
+            green: std::env::var("BRAND_GREEN_COLOR").ok(),
+            amber: std::env::var("BRAND_AMBER_COLOR").ok(),
+            red: std::env::var("BRAND_RED_COLOR").ok(),
+            footer_html: std::env::var("BRAND_FOOTER_HTML").ok(),
+            default_theme,
+        }
+    }
 }
 
 impl Config {
@@ -47,8 +299,137 @@ impl Config {
                 .unwrap_or_else(|_| "./data/models".to_string()),
         );
 
+        let proofs_dir = PathBuf::from(
+            std::env::var("PROOFS_DIR")
+                .unwrap_or_else(|_| "./data/proofs".to_string()),
+        );
+
         let converter_url = std::env::var("CONVERTER_URL").ok();
 
+        let scrub_interval_secs: u64 = std::env::var("SCRUB_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+
+        let max_cache_entries: usize = std::env::var("MAX_CACHE_ENTRIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10_000);
+
+        let max_trace_length: usize = std::env::var("MAX_TRACE_LENGTH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1 << 20);
+
+        let attestation_signing_key = std::env::var("ATTESTATION_SIGNING_KEY").ok();
+
+        let default_locale = std::env::var("DEFAULT_LOCALE").unwrap_or_else(|_| "en".to_string());
+
+        let brand = BrandConfig::from_env();
+
+        let http_signature_key_id = std::env::var("HTTP_SIGNATURE_KEY_ID").ok();
+        let http_signature_private_key_pem = std::env::var("HTTP_SIGNATURE_PRIVATE_KEY_PEM").ok();
+
+        let profile_cache_ttl_secs: u64 = std::env::var("PROFILE_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(900);
+
+        let profile_cache_backend = std::env::var("PROFILE_CACHE_BACKEND").unwrap_or_else(|_| "memory".to_string());
+        let profile_cache_s3_bucket = std::env::var("PROFILE_CACHE_S3_BUCKET").ok();
+        let profile_cache_s3_endpoint = std::env::var("PROFILE_CACHE_S3_ENDPOINT").ok();
+        let profile_cache_s3_region = std::env::var("PROFILE_CACHE_S3_REGION").ok();
+
+        let proof_archive_s3_bucket = std::env::var("PROOF_ARCHIVE_S3_BUCKET").ok();
+        let proof_archive_s3_endpoint = std::env::var("PROOF_ARCHIVE_S3_ENDPOINT").ok();
+        let proof_archive_s3_region = std::env::var("PROOF_ARCHIVE_S3_REGION").ok();
+
+        let jwt_secret = std::env::var("JWT_SECRET").ok();
+
+        let prove_rate_limit = RateLimitConfig::from_env("PROVE_RATE_LIMIT", 10.0, 60.0, 3.0);
+        let batch_rate_limit = RateLimitConfig::from_env("BATCH_RATE_LIMIT", 2.0, 60.0, 3.0);
+        let upload_rate_limit = RateLimitConfig::from_env("UPLOAD_RATE_LIMIT", 1.0, 300.0, 3.0);
+        let prove_model_rate_limit = RateLimitConfig::from_env("PROVE_MODEL_RATE_LIMIT", 1.0, 300.0, 3.0);
+
+        let admin_password = std::env::var("ADMIN_PASSWORD").ok();
+        // Falls back to the password itself so a deployer only has to set
+        // one variable in the common case; set ADMIN_TICKET_SECRET
+        // separately for a stronger setup.
+        let admin_ticket_secret = std::env::var("ADMIN_TICKET_SECRET").ok().or_else(|| admin_password.clone());
+        let admin_ticket_ttl_secs: u64 = std::env::var("ADMIN_TICKET_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(7200);
+
+        let webhook_signing_secret = std::env::var("WEBHOOK_SIGNING_SECRET").ok();
+
+        let moltbook_api_key = std::env::var("MOLTBOOK_API_KEY").ok();
+        let announce_backends = std::env::var("ANNOUNCE_BACKENDS")
+            .ok()
+            .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let announce_interval_secs: u64 = std::env::var("ANNOUNCE_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1800);
+
+        let signing_key_path = PathBuf::from(
+            std::env::var("SIGNING_KEY_PATH")
+                .unwrap_or_else(|_| "./data/credential_signing_key".to_string()),
+        );
+
+        let prove_concurrency: usize = std::env::var("PROVE_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
+
+        let api_keys = std::env::var("API_KEYS")
+            .ok()
+            .map(|v| v.split(',').filter_map(ApiKeySeed::parse).collect())
+            .unwrap_or_default();
+
+        let upload_policy_secret = std::env::var("UPLOAD_POLICY_SECRET").ok();
+
+        let model_store_backend = std::env::var("MODEL_STORE_BACKEND").unwrap_or_else(|_| "local".to_string());
+        let model_store_s3_bucket = std::env::var("MODEL_STORE_S3_BUCKET").ok();
+        let model_store_s3_endpoint = std::env::var("MODEL_STORE_S3_ENDPOINT").ok();
+        let model_store_s3_region = std::env::var("MODEL_STORE_S3_REGION").ok();
+
+        let max_upload_bytes = std::env::var("MAX_UPLOAD_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5 * 1024 * 1024);
+
+        let preprocess_concurrency: usize = std::env::var("PREPROCESS_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
+        let preprocess_max_attempts: u32 = std::env::var("PREPROCESS_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3);
+        let preprocess_backoff_base_secs: u64 = std::env::var("PREPROCESS_BACKOFF_BASE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+
+        let max_resumable_upload_bytes: u64 = std::env::var("MAX_RESUMABLE_UPLOAD_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(500 * 1024 * 1024);
+        let max_pending_uploads: usize = std::env::var("MAX_PENDING_UPLOADS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(100);
+        let pending_upload_ttl_secs: u64 = std::env::var("PENDING_UPLOAD_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+        let rate_limit_bucket_ttl_secs: u64 = std::env::var("RATE_LIMIT_BUCKET_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+
         Self {
             port,
             models_dir,
@@ -56,7 +437,52 @@ impl Config {
             database_path,
             cors_origins,
             uploaded_models_dir,
+            proofs_dir,
             converter_url,
+            scrub_interval_secs,
+            max_cache_entries,
+            max_trace_length,
+            attestation_signing_key,
+            default_locale,
+            brand,
+            http_signature_key_id,
+            http_signature_private_key_pem,
+            profile_cache_ttl_secs,
+            profile_cache_backend,
+            profile_cache_s3_bucket,
+            profile_cache_s3_endpoint,
+            profile_cache_s3_region,
+            proof_archive_s3_bucket,
+            proof_archive_s3_endpoint,
+            proof_archive_s3_region,
+            jwt_secret,
+            prove_rate_limit,
+            batch_rate_limit,
+            upload_rate_limit,
+            prove_model_rate_limit,
+            admin_password,
+            admin_ticket_secret,
+            admin_ticket_ttl_secs,
+            webhook_signing_secret,
+            moltbook_api_key,
+            announce_backends,
+            announce_interval_secs,
+            signing_key_path,
+            prove_concurrency,
+            api_keys,
+            upload_policy_secret,
+            model_store_backend,
+            model_store_s3_bucket,
+            model_store_s3_endpoint,
+            model_store_s3_region,
+            max_upload_bytes,
+            preprocess_concurrency,
+            preprocess_max_attempts,
+            preprocess_backoff_base_secs,
+            max_resumable_upload_bytes,
+            max_pending_uploads,
+            pending_upload_ttl_secs,
+            rate_limit_bucket_ttl_secs,
         }
     }
 }
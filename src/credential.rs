@@ -0,0 +1,108 @@
+//! W3C Verifiable Credentials for proof receipts — the `?format=vc` branch
+//! of `handlers::receipt::get_receipt`.
+//!
+//! Unlike the `?format=jsonld` branch (an unsigned schema.org document) or
+//! `proof_string` (an ad-hoc colon-delimited tag), a VC is cryptographically
+//! self-contained: its `proof.jws` is checked against the `did:key` named in
+//! its own `proof.verificationMethod` (at issuance, the same DID as its
+//! `issuer` field), so `POST /verify-credential` (and any other verifier)
+//! can confirm it offline without trusting this server or looking the
+//! receipt up again.
+
+use serde_json::{json, Value};
+
+use crate::crypto::{self, CredentialSigningKey};
+use crate::receipt::Receipt;
+
+/// Serialize `doc` with object keys in sorted order. This repo's
+/// `serde_json::Map` is a `BTreeMap` (no `preserve_order` feature), so plain
+/// `serde_json::to_vec` already produces this — naming it here documents
+/// that the choice is load-bearing for `sign_credential_jws`/
+/// `verify_credential`, which both need the exact same bytes for the same
+/// document.
+fn canonicalize(doc: &Value) -> Vec<u8> {
+    serde_json::to_vec(doc).expect("serde_json::Value always serializes")
+}
+
+/// Build the unsigned credential document for `receipt`. `attach_proof`
+/// signs and completes it.
+fn unsigned_credential(receipt: &Receipt, issuer_did: &str, base_url: &str) -> Value {
+    json!({
+        "@context": [
+            "https://www.w3.org/2018/credentials/v1",
+            format!("{}/credentials/v1", base_url),
+        ],
+        "id": format!("{}/receipt/{}", base_url, receipt.id),
+        "type": ["VerifiableCredential", "ZkmlInferenceCredential"],
+        "issuer": issuer_did,
+        "issuanceDate": receipt.created_at.to_rfc3339(),
+        "credentialSubject": {
+            "id": format!("{}/receipt/{}", base_url, receipt.id),
+            "modelId": receipt.model_id,
+            "modelName": receipt.model_name,
+            "modelHash": receipt.model_hash,
+            "inputHash": receipt.input_hash,
+            "outputHash": receipt.output_hash,
+            "proofHash": receipt.proof_hash,
+            "proofSize": receipt.proof_size,
+            "proveTimeMs": receipt.prove_time_ms.map(|t| t as u64),
+            "verifyTimeMs": receipt.verify_time_ms.map(|t| t as u64),
+            "prediction": {
+                "label": receipt.output.label,
+                "confidence": receipt.output.confidence,
+                "predictedClass": receipt.output.predicted_class,
+            },
+            "status": receipt.status.as_str(),
+        },
+    })
+}
+
+/// Build and sign the Verifiable Credential for `receipt`.
+pub fn issue(receipt: &Receipt, key: &CredentialSigningKey, base_url: &str) -> Value {
+    let mut doc = unsigned_credential(receipt, &key.did, base_url);
+    let payload = canonicalize(&doc);
+    let jws = crypto::sign_credential_jws(key, &payload);
+    let proof = json!({
+        "type": "Ed25519Signature2020",
+        "created": chrono::Utc::now().to_rfc3339(),
+        "verificationMethod": format!("{}#key-1", key.did),
+        "proofPurpose": "assertionMethod",
+        "jws": jws,
+    });
+    doc.as_object_mut()
+        .expect("unsigned_credential always builds an object")
+        .insert("proof".to_string(), proof);
+    doc
+}
+
+/// Re-canonicalize `doc` with its `proof` removed, resolve the signer from
+/// `proof.verificationMethod`'s `did:key`, and check `proof.jws` against it.
+/// Returns `Ok(false)` for a well-formed but wrongly-signed document —
+/// malformed input (missing fields, a `did:key` this server can't parse)
+/// is the only thing that errors.
+pub fn verify(doc: &Value) -> anyhow::Result<bool> {
+    let mut without_proof = doc
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("credential must be a JSON object"))?
+        .clone();
+    let proof = without_proof
+        .remove("proof")
+        .ok_or_else(|| anyhow::anyhow!("credential has no proof block"))?;
+
+    let jws = proof
+        .get("jws")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("proof has no jws"))?;
+    let verification_method = proof
+        .get("verificationMethod")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("proof has no verificationMethod"))?;
+    let did = verification_method
+        .split('#')
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("malformed verificationMethod"))?;
+
+    let verifying_key = crypto::verifying_key_from_did_key(did)?;
+    let payload = canonicalize(&Value::Object(without_proof));
+    crypto::verify_credential_jws(&verifying_key, &payload, jws)
+}
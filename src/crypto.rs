@@ -1,3 +1,15 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use base64::Engine;
+use ed25519_dalek::{Signer, Verifier};
+use hmac::{Hmac, Mac};
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as Sha2Digest, Sha256};
 use sha3::{Digest, Keccak256};
 use std::path::Path;
 
@@ -7,6 +19,32 @@ pub fn keccak256(data: &[u8]) -> String {
     format!("0x{}", hex::encode(hasher.finalize()))
 }
 
+/// Hex-encoded HMAC-SHA256 of `data` under `secret` — signs outbound
+/// webhook/callback deliveries so receivers can verify a payload actually
+/// came from this server, as `X-Clawproof-Signature: sha256=<this>`.
+pub fn hmac_sha256_hex(secret: &str, data: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(data);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// HMAC-SHA256 (hex) of the raw base64 `policy` string under `secret` — the
+/// `x-amz-signature` a `models::UploadPolicy` grant must match, the same
+/// S3 PostObject-style derivation `sign_upload_policy`'s callers check.
+pub fn sign_upload_policy(secret: &str, policy_b64: &str) -> String {
+    hmac_sha256_hex(secret, policy_b64.as_bytes())
+}
+
+/// Constant-time string comparison — an upload policy's `x-amz-signature` is
+/// checked against this instead of `==` so a timing side-channel can't be
+/// used to guess a valid signature one byte at a time.
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 pub fn hash_tensor(data: &[i32]) -> String {
     let bytes: Vec<u8> = data.iter().flat_map(|v| v.to_le_bytes()).collect();
     keccak256(&bytes)
@@ -16,3 +54,356 @@ pub fn compute_model_commitment(path: &Path) -> anyhow::Result<String> {
     let bytes = std::fs::read(path)?;
     Ok(keccak256(&bytes))
 }
+
+fn decode_hash32(hash: &str) -> anyhow::Result<[u8; 32]> {
+    let stripped = hash.strip_prefix("0x").unwrap_or(hash);
+    let bytes = hex::decode(stripped)?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("hash must decode to 32 bytes, got {} bytes", stripped.len() / 2))
+}
+
+/// An Ethereum-style secp256k1 signature over an attestation message, plus
+/// the address it recovers to and the program-IO hash that went into the
+/// message — bundling the latter lets a third party re-derive the exact
+/// message from the receipt's own `model_hash`/`input_hash`/`proof_hash`
+/// without needing access to the original ONNX program trace.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Attestation {
+    pub r: String,
+    pub s: String,
+    pub v: u8,
+    pub signer: String,
+    pub program_io_hash: String,
+}
+
+/// Parse a hex-encoded secp256k1 private key (as loaded from config/env)
+/// into a signing key.
+pub fn load_signing_key(hex_key: &str) -> anyhow::Result<SigningKey> {
+    let bytes = decode_hash32(hex_key)?;
+    Ok(SigningKey::from_bytes((&bytes).into())?)
+}
+
+/// The canonical message a receipt's attestation signs: keccak256 over the
+/// concatenation of the model commitment, input-tensor hash, proof hash,
+/// and program-IO hash — the same preimages an on-chain verifier would
+/// independently recompute from the receipt.
+pub fn attestation_message(
+    model_commitment: &str,
+    input_hash: &str,
+    proof_hash: &str,
+    program_io_hash: &str,
+) -> anyhow::Result<[u8; 32]> {
+    let mut buf = Vec::with_capacity(128);
+    buf.extend_from_slice(&decode_hash32(model_commitment)?);
+    buf.extend_from_slice(&decode_hash32(input_hash)?);
+    buf.extend_from_slice(&decode_hash32(proof_hash)?);
+    buf.extend_from_slice(&decode_hash32(program_io_hash)?);
+    let mut hasher = Keccak256::new();
+    hasher.update(&buf);
+    Ok(hasher.finalize().into())
+}
+
+fn eth_address(verifying_key: &VerifyingKey) -> String {
+    let encoded = verifying_key.to_encoded_point(false);
+    // Drop the leading 0x04 uncompressed-point tag before hashing.
+    let mut hasher = Keccak256::new();
+    hasher.update(&encoded.as_bytes()[1..]);
+    let hash = hasher.finalize();
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+/// Sign the attestation message binding `model_commitment`, `input_hash`,
+/// `proof_hash` and `program_io_hash` together, producing an Ethereum-style
+/// `(r, s, v)` signature and the address it recovers to.
+pub fn sign_attestation(
+    key: &SigningKey,
+    model_commitment: &str,
+    input_hash: &str,
+    proof_hash: &str,
+    program_io_hash: &str,
+) -> anyhow::Result<Attestation> {
+    let message_hash = attestation_message(model_commitment, input_hash, proof_hash, program_io_hash)?;
+    let (signature, recovery_id): (Signature, RecoveryId) = key.sign_prehash_recoverable(&message_hash)?;
+    let (r, s) = signature.split_bytes();
+    Ok(Attestation {
+        r: format!("0x{}", hex::encode(r)),
+        s: format!("0x{}", hex::encode(s)),
+        v: recovery_id.to_byte() + 27,
+        signer: eth_address(key.verifying_key()),
+        program_io_hash: program_io_hash.to_string(),
+    })
+}
+
+/// Recompute the attestation message from the receipt's hashes plus the
+/// `program_io_hash` bundled in `attestation`, recover the signer, and
+/// confirm it matches `attestation.signer`.
+pub fn verify_attestation(
+    model_commitment: &str,
+    input_hash: &str,
+    proof_hash: &str,
+    attestation: &Attestation,
+) -> anyhow::Result<bool> {
+    let message_hash = attestation_message(model_commitment, input_hash, proof_hash, &attestation.program_io_hash)?;
+    let r = decode_hash32(&attestation.r)?;
+    let s = decode_hash32(&attestation.s)?;
+    let signature = Signature::from_scalars(r, s)?;
+    let recovery_id = RecoveryId::from_byte(attestation.v.saturating_sub(27))
+        .ok_or_else(|| anyhow::anyhow!("invalid recovery id {}", attestation.v))?;
+    let recovered = VerifyingKey::recover_from_prehash(&message_hash, &signature, recovery_id)?;
+    Ok(eth_address(&recovered).eq_ignore_ascii_case(&attestation.signer))
+}
+
+/// Hash a receipt-sharing passphrase for storage. Never store the
+/// plaintext — only this Argon2 hash, which `verify_passphrase` checks
+/// candidate passphrases against.
+pub fn hash_passphrase(passphrase: &str) -> anyhow::Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default()
+        .hash_password(passphrase.as_bytes(), &salt)
+        .map_err(|e| anyhow::anyhow!("failed to hash passphrase: {}", e))?;
+    Ok(hash.to_string())
+}
+
+/// Check a candidate passphrase against a hash produced by
+/// `hash_passphrase`. Returns `false` (rather than erroring) on a malformed
+/// stored hash, since that should be treated the same as a wrong guess.
+pub fn verify_passphrase(passphrase: &str, hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return false;
+    };
+    Argon2::default()
+        .verify_password(passphrase.as_bytes(), &parsed)
+        .is_ok()
+}
+
+/// Key material for signing outbound "authorized fetch" requests under the
+/// draft-cavage HTTP Signatures scheme ActivityPub servers expect (the same
+/// approach Plume's `sign` module uses).
+pub struct HttpSignatureKey {
+    pub key_id: String,
+    private_key: RsaPrivateKey,
+}
+
+/// Parse a PKCS#8 PEM-encoded RSA private key (as loaded from config/env)
+/// alongside the actor key-id it's paired with.
+pub fn load_http_signature_key(key_id: &str, pem: &str) -> anyhow::Result<HttpSignatureKey> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(pem)
+        .map_err(|e| anyhow::anyhow!("invalid HTTP signature private key: {}", e))?;
+    Ok(HttpSignatureKey {
+        key_id: key_id.to_string(),
+        private_key,
+    })
+}
+
+/// Build the `Date` and `Signature` header values for a signed GET request to
+/// `path_and_query` on `host`. The signing string covers the pseudo-header
+/// `(request-target): get {path}` plus `host` and `date`, per the subset of
+/// the scheme ActivityPub's "authorized fetch" convention relies on.
+pub fn sign_http_get(
+    key: &HttpSignatureKey,
+    path_and_query: &str,
+    host: &str,
+    date: &str,
+) -> anyhow::Result<String> {
+    let signing_string = format!(
+        "(request-target): get {path_and_query}\nhost: {host}\ndate: {date}"
+    );
+    let digest = Sha256::digest(signing_string.as_bytes());
+    let signature = key
+        .private_key
+        .sign(Pkcs1v15Sign::new::<Sha256>(), &digest)
+        .map_err(|e| anyhow::anyhow!("RSA-SHA256 signing failed: {}", e))?;
+    let signature_b64 = base64::engine::general_purpose::STANDARD.encode(signature);
+    Ok(format!(
+        r#"keyId="{}",algorithm="rsa-sha256",headers="(request-target) host date",signature="{}""#,
+        key.key_id, signature_b64
+    ))
+}
+
+/// Base64-encoded SHA-256 digest of `body`, as embedded in an outbound
+/// `Digest: SHA-256=<this>` header — binds a signed POST to the exact bytes
+/// delivered, the way `sign_http_get` binds a GET to its path/host/date.
+pub fn sha256_digest_base64(body: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(Sha256::digest(body))
+}
+
+/// Build the `Signature` header value for a signed POST delivery of a body
+/// whose digest is `digest` (see `sha256_digest_base64`), e.g. an
+/// ActivityPub inbox delivery. Same draft-cavage subset as `sign_http_get`
+/// with a `digest` pseudo-header added so the signature also covers the body.
+pub fn sign_http_post(
+    key: &HttpSignatureKey,
+    path_and_query: &str,
+    host: &str,
+    date: &str,
+    digest: &str,
+) -> anyhow::Result<String> {
+    let signing_string = format!(
+        "(request-target): post {path_and_query}\nhost: {host}\ndate: {date}\ndigest: {digest}"
+    );
+    let hash = Sha256::digest(signing_string.as_bytes());
+    let signature = key
+        .private_key
+        .sign(Pkcs1v15Sign::new::<Sha256>(), &hash)
+        .map_err(|e| anyhow::anyhow!("RSA-SHA256 signing failed: {}", e))?;
+    let signature_b64 = base64::engine::general_purpose::STANDARD.encode(signature);
+    Ok(format!(
+        r#"keyId="{}",algorithm="rsa-sha256",headers="(request-target) host date digest",signature="{}""#,
+        key.key_id, signature_b64
+    ))
+}
+
+/// PEM-encoded RSA public key matching `key`, embedded in the ActivityPub
+/// actor document so remote servers can verify signatures `sign_http_get`
+/// and `sign_http_post` produce with it.
+pub fn http_signature_public_key_pem(key: &HttpSignatureKey) -> anyhow::Result<String> {
+    use rsa::pkcs8::EncodePublicKey;
+    key.private_key
+        .to_public_key()
+        .to_public_key_pem(rsa::pkcs8::LineEnding::LF)
+        .map_err(|e| anyhow::anyhow!("failed to encode HTTP signature public key: {}", e))
+}
+
+/// Multicodec prefix for an Ed25519 public key (`ed25519-pub`, code `0xed`
+/// as a two-byte unsigned varint), prepended before multibase-encoding a
+/// `did:key`.
+const MULTICODEC_ED25519_PUB: [u8; 2] = [0xed, 0x01];
+
+/// Alphabet multibase's `z` prefix (base58btc) encodes with — the same one
+/// Bitcoin and IPFS use.
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = vec![0];
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut out: String = std::iter::repeat('1').take(leading_zeros).collect();
+    out.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    out
+}
+
+fn base58_decode(s: &str) -> anyhow::Result<Vec<u8>> {
+    let mut digits: Vec<u8> = vec![0];
+    for c in s.chars() {
+        let value = BASE58_ALPHABET
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or_else(|| anyhow::anyhow!("invalid base58 character: {}", c))? as u32;
+        let mut carry = value;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) * 58;
+            *digit = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let leading_ones = s.chars().take_while(|&c| c == '1').count();
+    let mut out = vec![0u8; leading_ones];
+    out.extend(digits.iter().rev().copied());
+    Ok(out)
+}
+
+fn did_key_from_verifying_key(vk: &ed25519_dalek::VerifyingKey) -> String {
+    let mut prefixed = MULTICODEC_ED25519_PUB.to_vec();
+    prefixed.extend_from_slice(vk.as_bytes());
+    format!("did:key:z{}", base58_encode(&prefixed))
+}
+
+/// Parse a `did:key:z...` identifier back into the Ed25519 verifying key it
+/// encodes — the reverse of `did_key_from_verifying_key`.
+pub fn verifying_key_from_did_key(did: &str) -> anyhow::Result<ed25519_dalek::VerifyingKey> {
+    let multibase = did
+        .strip_prefix("did:key:z")
+        .ok_or_else(|| anyhow::anyhow!("not a base58btc did:key: {}", did))?;
+    let decoded = base58_decode(multibase)?;
+    let pubkey_bytes = decoded
+        .strip_prefix(MULTICODEC_ED25519_PUB.as_slice())
+        .ok_or_else(|| anyhow::anyhow!("did:key is not an ed25519-pub multicodec"))?;
+    let bytes: [u8; 32] = pubkey_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("did:key Ed25519 public key must be 32 bytes"))?;
+    ed25519_dalek::VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| anyhow::anyhow!("invalid Ed25519 public key: {}", e))
+}
+
+/// Ed25519 keypair this server signs Verifiable Credentials with, identified
+/// by the `did:key` it derives to — see `load_or_generate_credential_key`.
+pub struct CredentialSigningKey {
+    pub did: String,
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+/// Load the Ed25519 signing key from `path` (its raw 32-byte seed), or
+/// generate one and persist it there if the file doesn't exist yet — so a
+/// deployment keeps the same `did:key` identity (and every VC it's already
+/// issued stays verifiable) across restarts instead of rotating on every
+/// boot.
+pub fn load_or_generate_credential_key(path: &Path) -> anyhow::Result<CredentialSigningKey> {
+    let signing_key = if path.exists() {
+        let bytes = std::fs::read(path)?;
+        let seed: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("credential signing key at {:?} is not a 32-byte seed", path))?;
+        ed25519_dalek::SigningKey::from_bytes(&seed)
+    } else {
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, signing_key.to_bytes())?;
+        signing_key
+    };
+    let did = did_key_from_verifying_key(&signing_key.verifying_key());
+    Ok(CredentialSigningKey { did, signing_key })
+}
+
+fn base64url_no_pad(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Sign `canonical_payload` (the VC document with its `proof` block removed,
+/// serialized with sorted object keys — see `credential::canonicalize`)
+/// producing the detached (RFC 7797, `b64: false`) EdDSA JWS that goes in
+/// `proof.jws`. Detached so the JWS carries only a header and signature —
+/// the payload a verifier checks it against is the document itself, not a
+/// base64 copy embedded in the token.
+pub fn sign_credential_jws(key: &CredentialSigningKey, canonical_payload: &[u8]) -> String {
+    let header = base64url_no_pad(br#"{"alg":"EdDSA","b64":false,"crit":["b64"]}"#);
+    let signing_input = [header.as_bytes(), b".", canonical_payload].concat();
+    let signature = key.signing_key.sign(&signing_input);
+    format!("{}..{}", header, base64url_no_pad(&signature.to_bytes()))
+}
+
+/// Verify a detached EdDSA JWS (see `sign_credential_jws`) produced over
+/// `canonical_payload` against `verifying_key`.
+pub fn verify_credential_jws(
+    verifying_key: &ed25519_dalek::VerifyingKey,
+    canonical_payload: &[u8],
+    jws: &str,
+) -> anyhow::Result<bool> {
+    let (header_b64, signature_b64) = jws
+        .split_once("..")
+        .ok_or_else(|| anyhow::anyhow!("not a detached JWS (expected \"header..signature\")"))?;
+    let signing_input = [header_b64.as_bytes(), b".", canonical_payload].concat();
+    let signature_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(signature_b64)
+        .map_err(|e| anyhow::anyhow!("invalid JWS signature encoding: {}", e))?;
+    let signature = ed25519_dalek::Signature::from_slice(&signature_bytes)
+        .map_err(|e| anyhow::anyhow!("invalid Ed25519 signature: {}", e))?;
+    Ok(verifying_key.verify(&signing_input, &signature).is_ok())
+}
@@ -0,0 +1,81 @@
+//! Routes backing the ActivityPub `Announcer` — WebFinger discovery, the
+//! actor document, and the inbox that records followers. All three 404 when
+//! `state.activitypub` is `None` (the `activitypub` backend isn't listed in
+//! `ANNOUNCE_BACKENDS`, or `HTTP_SIGNATURE_KEY_ID`/`HTTP_SIGNATURE_PRIVATE_KEY_PEM`
+//! aren't set), same as the rest of the admin/optional surfaces in this repo.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct WebfingerQuery {
+    resource: Option<String>,
+}
+
+/// `GET /.well-known/webfinger?resource=acct:clawproof@host` — the discovery
+/// hop a remote fediverse server makes before it can find the actor document.
+pub async fn webfinger(
+    State(state): State<AppState>,
+    Query(query): Query<WebfingerQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let ap = state.activitypub.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let resource = query.resource.ok_or(StatusCode::BAD_REQUEST)?;
+    if resource != ap.webfinger_subject() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({
+        "subject": ap.webfinger_subject(),
+        "links": [{
+            "rel": "self",
+            "type": "application/activity+json",
+            "href": ap.actor_id(),
+        }],
+    })))
+}
+
+/// `GET /actors/clawproof` — the actor document: inbox and the public key
+/// remote servers use to verify `Signature` headers on deliveries from
+/// `ActivityPubAnnouncer`.
+pub async fn actor(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    let ap = state.activitypub.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(ap.actor_document()))
+}
+
+/// `POST /actors/clawproof/inbox` — accepts `Follow` activities (ignoring
+/// everything else), resolving the follower's actor to find its inbox URL.
+/// No `Accept{Follow}` is sent back: ClawProof is a bot-style broadcast-only
+/// actor and doesn't model a pending-vs-accepted follow state, so delivery
+/// succeeding is the only confirmation a follower gets.
+pub async fn inbox(State(state): State<AppState>, Json(activity): Json<Value>) -> StatusCode {
+    let Some(ap) = state.activitypub.as_ref() else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    if activity.get("type").and_then(|t| t.as_str()) != Some("Follow") {
+        return StatusCode::ACCEPTED;
+    }
+
+    if let Some(actor_url) = activity.get("actor").and_then(|a| a.as_str()) {
+        if let Some(follower_inbox) = resolve_inbox(actor_url).await {
+            ap.add_follower(follower_inbox).await;
+        }
+    }
+
+    StatusCode::ACCEPTED
+}
+
+async fn resolve_inbox(actor_url: &str) -> Option<String> {
+    let resp = reqwest::Client::new()
+        .get(actor_url)
+        .header("Accept", "application/activity+json")
+        .send()
+        .await
+        .ok()?;
+    let actor: Value = resp.json().await.ok()?;
+    actor.get("inbox").and_then(|i| i.as_str()).map(|s| s.to_string())
+}
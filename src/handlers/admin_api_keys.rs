@@ -0,0 +1,52 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct IssueApiKeyRequest {
+    pub label: String,
+    pub quota_per_min: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct IssueApiKeyResponse {
+    pub token: String,
+    pub label: String,
+    pub quota_per_min: Option<f64>,
+}
+
+/// POST /admin/api-keys — mint an opaque bearer token for
+/// `api_keys::require_api_key`. Gated by `admin_auth::require_admin`,
+/// layered onto this route (and the rest of `/admin/*`) in `main.rs`.
+pub async fn issue_api_key(
+    State(state): State<AppState>,
+    Json(request): Json<IssueApiKeyRequest>,
+) -> Json<IssueApiKeyResponse> {
+    let token = state.api_keys.issue(&request.label, request.quota_per_min);
+    Json(IssueApiKeyResponse {
+        token,
+        label: request.label,
+        quota_per_min: request.quota_per_min,
+    })
+}
+
+#[derive(Deserialize)]
+pub struct RevokeApiKeyRequest {
+    pub token: String,
+}
+
+/// DELETE /admin/api-keys — forget a token, rejecting every request that
+/// presents it from then on.
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Json(request): Json<RevokeApiKeyRequest>,
+) -> StatusCode {
+    if state.api_keys.revoke(&request.token) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
@@ -0,0 +1,55 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::auth;
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct IssueTokenRequest {
+    pub sub: String,
+    #[serde(default = "default_tier")]
+    pub tier: String,
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: i64,
+}
+
+fn default_tier() -> String {
+    "free".to_string()
+}
+
+fn default_ttl_secs() -> i64 {
+    3600
+}
+
+#[derive(Serialize)]
+pub struct IssueTokenResponse {
+    pub token: String,
+    pub tier: String,
+    pub expires_in_secs: i64,
+}
+
+/// POST /admin/tokens — mint a bearer token for `auth::require_auth`.
+/// Gated by `admin_auth::require_admin`, layered onto this route (and the
+/// rest of `/admin/*`) in `main.rs`, rather than a bespoke check here.
+pub async fn issue_token(
+    State(state): State<AppState>,
+    Json(request): Json<IssueTokenRequest>,
+) -> Result<Json<IssueTokenResponse>, StatusCode> {
+    let Some(jwt_secret) = state.config.jwt_secret.as_deref() else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let token = auth::issue_token(jwt_secret, &request.sub, &request.tier, request.ttl_secs)
+        .map_err(|e| {
+            tracing::error!("[clawproof] Failed to issue token: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(IssueTokenResponse {
+        token,
+        tier: request.tier,
+        expires_in_secs: request.ttl_secs,
+    }))
+}
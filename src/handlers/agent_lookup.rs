@@ -3,9 +3,10 @@ use axum::http::StatusCode;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use tracing::warn;
+use std::hash::{Hash, Hasher};
 
 use crate::state::AppState;
+use crate::trust_source::{RawAgentData, SourceError};
 
 #[derive(Deserialize)]
 pub struct AgentLookupRequest {
@@ -51,71 +52,24 @@ pub struct AgentRawData {
     pub is_claimed: bool,
     pub x_verified: bool,
     pub content_spam_score: f64,
+    /// `true` when the upstream source errored and this is the last
+    /// successfully cached copy rather than a fresh fetch.
+    pub stale: bool,
 }
 
 // ---------------------------------------------------------------------------
-// Moltbook API response types
+// URL / name / scheme parsing
 // ---------------------------------------------------------------------------
 
-#[derive(Deserialize)]
-struct MoltbookProfile {
-    #[serde(default)]
-    karma: i64,
-    #[serde(default)]
-    follower_count: i64,
-    #[serde(default)]
-    following_count: Option<i64>,
-    #[serde(default)]
-    is_claimed: Option<bool>,
-    #[serde(default)]
-    created_at: Option<String>,
-    #[serde(default)]
-    stats: Option<MoltbookStats>,
-    #[serde(default)]
-    owner: Option<MoltbookOwner>,
-    #[serde(default, rename = "recentPosts")]
-    recent_posts: Option<Vec<MoltbookPost>>,
-    #[serde(default, rename = "recentComments")]
-    recent_comments: Option<Vec<MoltbookComment>>,
-}
-
-#[derive(Deserialize, Default)]
-struct MoltbookStats {
-    #[serde(default)]
-    posts: i64,
-    #[serde(default)]
-    comments: i64,
-}
-
-#[derive(Deserialize, Default)]
-struct MoltbookOwner {
-    #[serde(default)]
-    x_verified: bool,
-}
-
-#[derive(Deserialize)]
-struct MoltbookPost {
-    #[serde(default)]
-    title: Option<String>,
-    #[serde(default)]
-    body: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct MoltbookComment {
-    #[serde(default)]
-    body: Option<String>,
-}
-
-// ---------------------------------------------------------------------------
-// URL / name parsing
-// ---------------------------------------------------------------------------
-
-/// Extract agent name from a URL like "https://www.moltbook.com/u/foo" or just "foo".
+/// Extract an agent name from a Moltbook URL ("https://www.moltbook.com/u/foo"),
+/// a Fediverse handle ("@alice@mastodon.social"), a Plume/Lemmy actor URL
+/// ("https://instance.example/@alice" or ".../users/alice"), or a bare
+/// Moltbook username. Fediverse identities are normalized to the canonical
+/// "user@domain" WebFinger form so `detect_scheme` can route on it.
 fn parse_agent_name(input: &str) -> Option<String> {
     let trimmed = input.trim().trim_end_matches('/');
 
-    // Try to parse as URL with /u/ path
+    // Moltbook URL with /u/ path
     if let Some(pos) = trimmed.find("/u/") {
         let name = &trimmed[pos + 3..];
         let name = name.split('/').next().unwrap_or(name);
@@ -125,7 +79,24 @@ fn parse_agent_name(input: &str) -> Option<String> {
         }
     }
 
-    // Otherwise treat the whole thing as a username (no slashes, no spaces)
+    // Fediverse "@user@domain" or "user@domain" handle
+    let without_at = trimmed.strip_prefix('@').unwrap_or(trimmed);
+    if without_at.matches('@').count() == 1 {
+        if let Some((user, domain)) = without_at.split_once('@') {
+            if !user.is_empty() && domain.contains('.') {
+                return Some(format!("{}@{}", user, domain));
+            }
+        }
+    }
+
+    // Fediverse actor URL, e.g. https://instance.example/@alice or
+    // https://instance.example/users/alice
+    if let Some(handle) = parse_fediverse_actor_url(trimmed) {
+        return Some(handle);
+    }
+
+    // Otherwise treat the whole thing as a Moltbook username (no slashes, no
+    // spaces, no dots)
     let name = trimmed.split('/').last().unwrap_or(trimmed);
     if !name.is_empty() && !name.contains(' ') && !name.contains('.') {
         return Some(name.to_string());
@@ -134,6 +105,35 @@ fn parse_agent_name(input: &str) -> Option<String> {
     None
 }
 
+fn parse_fediverse_actor_url(input: &str) -> Option<String> {
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))?;
+    let (domain, path) = rest.split_once('/')?;
+    if !domain.contains('.') {
+        return None;
+    }
+    let user = path
+        .strip_prefix('@')
+        .or_else(|| path.strip_prefix("users/"))
+        .map(|s| s.split('/').next().unwrap_or(s))?;
+    if user.is_empty() {
+        return None;
+    }
+    Some(format!("{}@{}", user, domain))
+}
+
+/// Which `TrustSource` a parsed agent name should be looked up against.
+/// Moltbook names are bare usernames; a "user@domain" shape is a Fediverse
+/// WebFinger handle, reserved for the "fediverse" source.
+fn detect_scheme(agent_name: &str) -> &'static str {
+    if agent_name.contains('@') {
+        "fediverse"
+    } else {
+        "moltbook"
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Bucketing functions
 // ---------------------------------------------------------------------------
@@ -246,6 +246,97 @@ fn derive_verification(is_claimed: bool, x_verified: bool) -> u32 {
 // Content similarity / spam analysis
 // ---------------------------------------------------------------------------
 
+/// Near-duplicate texts are those whose shingled Jaccard similarity exceeds
+/// this — trivial edits (typo fixes, emoji swaps) still clear it.
+const NEAR_DUPLICATE_THRESHOLD: f64 = 0.7;
+/// Number of independent hash functions in a MinHash signature. Higher
+/// means a better Jaccard estimate at the cost of more per-text work.
+const MINHASH_SIGNATURE_SIZE: usize = 64;
+/// Above this many texts, comparing MinHash signatures (O(MINHASH_SIGNATURE_SIZE)
+/// per pair) replaces comparing shingle sets directly (O(shingle set size)
+/// per pair), which is what keeps larger batches affordable.
+const PAIRWISE_TEXT_LIMIT: usize = 64;
+/// Hard ceiling on the number of texts the near-duplicate pass will ever
+/// compare, regardless of how many a `TrustSource` hands back. MinHash keeps
+/// each *pair* affordable above `PAIRWISE_TEXT_LIMIT`, but the pairwise loop
+/// itself is still O(n^2) in the count — this backstops sources (like a
+/// malicious fediverse instance's outbox) that don't cap their own output.
+const MAX_SPAM_SCORE_TEXTS: usize = 256;
+
+/// Overlapping 3-word shingles of a lowercased, whitespace-tokenized text.
+/// Texts under 3 words have none — callers fall back to exact-match
+/// comparison for those.
+fn shingles(text: &str) -> HashSet<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() < 3 {
+        return HashSet::new();
+    }
+    words.windows(3).map(|w| w.join(" ")).collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let intersection = a.intersection(b).count() as f64;
+    let union = a.union(b).count() as f64;
+    if union == 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
+
+/// A MinHash signature: the minimum hash value, under each of
+/// `MINHASH_SIGNATURE_SIZE` independent hash functions, over a text's
+/// shingle set. The fraction of matching slots between two signatures
+/// estimates their sets' Jaccard similarity.
+fn minhash_signature(shingle_set: &HashSet<String>) -> [u64; MINHASH_SIGNATURE_SIZE] {
+    let mut signature = [u64::MAX; MINHASH_SIGNATURE_SIZE];
+    for shingle in shingle_set {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        shingle.hash(&mut hasher);
+        let base = hasher.finish();
+        for (i, slot) in signature.iter_mut().enumerate() {
+            // Derive the i-th hash function by mixing a per-slot seed into
+            // the shingle's base hash, instead of re-hashing the shingle
+            // bytes MINHASH_SIGNATURE_SIZE times.
+            let seed = (i as u64)
+                .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                .wrapping_add(0xD1B5_4A32_D192_ED03);
+            let mut mixed = (base ^ seed).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            mixed ^= mixed >> 31;
+            if mixed < *slot {
+                *slot = mixed;
+            }
+        }
+    }
+    signature
+}
+
+fn minhash_similarity(a: &[u64; MINHASH_SIGNATURE_SIZE], b: &[u64; MINHASH_SIGNATURE_SIZE]) -> f64 {
+    let matches = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+    matches as f64 / MINHASH_SIGNATURE_SIZE as f64
+}
+
+/// Whether texts `i` and `j` (by index into `normalized`/`shingle_sets`) are
+/// near-duplicates. Falls back to exact-match comparison when either side
+/// has fewer than 3 words (and so no shingles to compare).
+fn is_near_duplicate(
+    i: usize,
+    j: usize,
+    normalized: &[String],
+    shingle_sets: &[HashSet<String>],
+    signatures: Option<&[[u64; MINHASH_SIGNATURE_SIZE]]>,
+) -> bool {
+    let (a, b) = (&shingle_sets[i], &shingle_sets[j]);
+    if a.is_empty() || b.is_empty() {
+        return normalized[i] == normalized[j];
+    }
+    let similarity = match signatures {
+        Some(sigs) => minhash_similarity(&sigs[i], &sigs[j]),
+        None => jaccard(a, b),
+    };
+    similarity > NEAR_DUPLICATE_THRESHOLD
+}
+
 /// Analyze an agent's recent posts and comments for spam-like patterns.
 ///
 /// Returns a raw score in 0.0..1.0 where higher = more spam-like.
@@ -254,46 +345,16 @@ fn derive_verification(is_claimed: bool, x_verified: bool) -> u32 {
 ///   2. Duplicate content: ratio of near-duplicate texts
 ///   3. Short-post ratio: fraction of very short posts (<30 chars)
 ///   4. Low vocabulary diversity: unique words / total words
-fn compute_spam_score(
-    recent_posts: &Option<Vec<MoltbookPost>>,
-    recent_comments: &Option<Vec<MoltbookComment>>,
-) -> f64 {
-    // Collect all text bodies
-    let mut texts: Vec<String> = Vec::new();
-
-    if let Some(posts) = recent_posts {
-        for p in posts {
-            // Combine title + body for posts
-            let mut text = String::new();
-            if let Some(t) = &p.title {
-                text.push_str(t);
-                text.push(' ');
-            }
-            if let Some(b) = &p.body {
-                text.push_str(b);
-            }
-            let text = text.trim().to_string();
-            if !text.is_empty() {
-                texts.push(text);
-            }
-        }
-    }
-
-    if let Some(comments) = recent_comments {
-        for c in comments {
-            if let Some(b) = &c.body {
-                let text = b.trim().to_string();
-                if !text.is_empty() {
-                    texts.push(text);
-                }
-            }
-        }
-    }
-
+fn compute_spam_score(texts: &[String]) -> f64 {
     if texts.is_empty() {
         // No content to analyze — neutral (not spam, not clearly legit)
         return 0.0;
     }
+    let texts = if texts.len() > MAX_SPAM_SCORE_TEXTS {
+        &texts[..MAX_SPAM_SCORE_TEXTS]
+    } else {
+        texts
+    };
 
     let n = texts.len() as f64;
 
@@ -304,7 +365,8 @@ fn compute_spam_score(
         .count() as f64;
     let link_ratio = link_count / n;
 
-    // Signal 2: Duplicate content — normalize and deduplicate
+    // Signal 2: Near-duplicate content — shingled MinHash Jaccard estimate.
+    // Catches trivially-edited reposts that byte-for-byte dedup misses.
     let normalized: Vec<String> = texts
         .iter()
         .map(|t| {
@@ -314,9 +376,21 @@ fn compute_spam_score(
                 .join(" ")
         })
         .collect();
-    let unique: HashSet<&str> = normalized.iter().map(|s| s.as_str()).collect();
     let duplicate_ratio = if normalized.len() > 1 {
-        1.0 - (unique.len() as f64 / normalized.len() as f64)
+        let shingle_sets: Vec<HashSet<String>> = normalized.iter().map(|t| shingles(t)).collect();
+        let signatures: Option<Vec<[u64; MINHASH_SIGNATURE_SIZE]>> = if normalized.len() > PAIRWISE_TEXT_LIMIT {
+            Some(shingle_sets.iter().map(minhash_signature).collect())
+        } else {
+            None
+        };
+
+        let near_duplicate_count = (0..normalized.len())
+            .filter(|&i| {
+                (0..normalized.len())
+                    .any(|j| i != j && is_near_duplicate(i, j, &normalized, &shingle_sets, signatures.as_deref()))
+            })
+            .count();
+        near_duplicate_count as f64 / normalized.len() as f64
     } else {
         0.0
     };
@@ -362,17 +436,31 @@ fn bucket_content_similarity(spam_score: f64) -> u32 {
 // Handler
 // ---------------------------------------------------------------------------
 
+fn source_error_response(scheme: &str, agent_name: &str, err: SourceError) -> (StatusCode, Json<serde_json::Value>) {
+    match err {
+        SourceError::NotConfigured => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": format!("Trust source '{}' is not configured", scheme)})),
+        ),
+        SourceError::NotFound => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Agent '{}' not found on {}", agent_name, scheme)})),
+        ),
+        SourceError::Upstream(msg) => (
+            StatusCode::BAD_GATEWAY,
+            Json(serde_json::json!({"error": msg})),
+        ),
+        SourceError::SigningFailed(msg) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to sign outbound request: {}", msg)})),
+        ),
+    }
+}
+
 pub async fn agent_lookup(
     State(state): State<AppState>,
     Json(req): Json<AgentLookupRequest>,
 ) -> Result<Json<AgentLookupResponse>, (StatusCode, Json<serde_json::Value>)> {
-    let api_key = state.config.moltbook_api_key.as_deref().ok_or_else(|| {
-        (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(serde_json::json!({"error": "Moltbook API key not configured"})),
-        )
-    })?;
-
     let agent_name = parse_agent_name(&req.agent).ok_or_else(|| {
         (
             StatusCode::BAD_REQUEST,
@@ -380,95 +468,53 @@ pub async fn agent_lookup(
         )
     })?;
 
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://www.moltbook.com/api/v1/agents/profile?name={}",
-        agent_name
-    );
-
-    let resp = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .send()
-        .await
-        .map_err(|e| {
-            warn!("[clawproof] Moltbook API request failed: {}", e);
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(serde_json::json!({"error": "Failed to reach Moltbook API"})),
-            )
-        })?;
-
-    if !resp.status().is_success() {
-        let status = resp.status().as_u16();
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": format!("Agent '{}' not found on Moltbook (status {})", agent_name, status)
-            })),
-        ));
-    }
-
-    let profile: MoltbookProfile = resp.json().await.map_err(|e| {
-        warn!("[clawproof] Failed to parse Moltbook profile: {}", e);
+    let scheme = detect_scheme(&agent_name);
+    let source = state.trust_sources.get(scheme).ok_or_else(|| {
         (
-            StatusCode::BAD_GATEWAY,
-            Json(serde_json::json!({"error": "Failed to parse Moltbook API response"})),
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": format!("No trust source registered for '{}'", scheme)})),
         )
     })?;
 
-    // Compute days since account creation
-    let days_old = profile
-        .created_at
-        .as_deref()
-        .and_then(|ts| {
-            chrono::DateTime::parse_from_rfc3339(ts)
-                .ok()
-                .map(|created| {
-                    let now = chrono::Utc::now();
-                    (now - created.with_timezone(&chrono::Utc))
-                        .num_seconds() as f64
-                        / 86400.0
-                })
-        })
-        .unwrap_or(0.0);
-
-    let stats = profile.stats.unwrap_or_default();
-    let owner = profile.owner.unwrap_or_default();
-    let is_claimed = profile.is_claimed.unwrap_or(false);
-    let total_posts = stats.posts + stats.comments;
+    let (raw, stale): (RawAgentData, bool) = state
+        .profile_cache
+        .get_or_fetch(scheme, &agent_name, &source)
+        .await
+        .map_err(|e| source_error_response(scheme, &agent_name, e))?;
 
     // Derive verification: 0=unclaimed, 1=claimed, 2=X-verified
-    let verification = derive_verification(is_claimed, owner.x_verified);
+    let verification = derive_verification(raw.is_claimed, raw.x_verified);
 
     // Analyze content for spam signals
-    let spam_score = compute_spam_score(&profile.recent_posts, &profile.recent_comments);
+    let spam_score = compute_spam_score(&raw.recent_texts);
+    let total_posts = raw.posts + raw.comments;
 
     let fields = AgentTrustFields {
-        karma: bucket_karma(profile.karma),
-        account_age: bucket_account_age(days_old),
-        follower_ratio: bucket_follower_ratio(profile.follower_count, profile.following_count),
-        post_frequency: bucket_post_frequency(total_posts, days_old),
+        karma: bucket_karma(raw.karma),
+        account_age: bucket_account_age(raw.days_old),
+        follower_ratio: bucket_follower_ratio(raw.follower_count, raw.following_count),
+        post_frequency: bucket_post_frequency(total_posts, raw.days_old),
         verification,
         content_similarity: bucket_content_similarity(spam_score),
         interaction_type: parse_interaction(&req.interaction),
     };
 
-    let raw = AgentRawData {
-        karma: profile.karma,
-        follower_count: profile.follower_count,
-        following_count: profile.following_count,
-        posts: stats.posts,
-        comments: stats.comments,
-        days_old,
-        is_claimed,
-        x_verified: owner.x_verified,
+    let raw_out = AgentRawData {
+        karma: raw.karma,
+        follower_count: raw.follower_count,
+        following_count: raw.following_count,
+        posts: raw.posts,
+        comments: raw.comments,
+        days_old: raw.days_old,
+        is_claimed: raw.is_claimed,
+        x_verified: raw.x_verified,
         content_spam_score: (spam_score * 1000.0).round() / 1000.0, // 3 decimal places
+        stale,
     };
 
     Ok(Json(AgentLookupResponse {
         agent_name,
         fields,
-        raw,
+        raw: raw_out,
     }))
 }
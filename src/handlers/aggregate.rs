@@ -0,0 +1,131 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::prove::ErrorResponse;
+use crate::prover;
+use crate::receipt::AggregateReceipt;
+use crate::state::AppState;
+
+/// Keeps a single aggregation request's re-verification work (and the
+/// resulting DB row) bounded — matches the spirit of `batch::batch_prove`'s
+/// per-request cap.
+const MAX_AGGREGATE_MEMBERS: usize = 100;
+
+#[derive(Deserialize)]
+pub struct AggregateRequest {
+    pub receipt_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct AggregateResponse {
+    pub id: String,
+    pub member_ids: Vec<String>,
+    pub merkle_root: String,
+    pub leaf_hashes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AggregateReceipt> for AggregateResponse {
+    fn from(aggregate: AggregateReceipt) -> Self {
+        Self {
+            id: aggregate.id,
+            member_ids: aggregate.member_ids,
+            merkle_root: aggregate.merkle_root,
+            leaf_hashes: aggregate.leaf_hashes,
+            created_at: aggregate.created_at,
+        }
+    }
+}
+
+/// POST /aggregate — batches many already-verified receipts into a single
+/// aggregate record. Every member's persisted proof is re-verified against
+/// its model's cached verifier preprocessing before the aggregate is
+/// accepted, and a Merkle root is taken over each member's
+/// `(model_hash, input_hash, output_hash)` leaf so the aggregate commits to
+/// exactly which receipts it covers without re-embedding their full proof
+/// bytes.
+pub async fn aggregate(
+    State(state): State<AppState>,
+    Json(request): Json<AggregateRequest>,
+) -> Result<Json<AggregateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if request.receipt_ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "At least one receipt_id is required".to_string(),
+                hint: Some("Provide {\"receipt_ids\": [\"...\"]}".to_string()),
+            }),
+        ));
+    }
+    if request.receipt_ids.len() > MAX_AGGREGATE_MEMBERS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Maximum {} receipts per aggregate", MAX_AGGREGATE_MEMBERS),
+                hint: None,
+            }),
+        ));
+    }
+
+    let receipt_ids = request.receipt_ids.clone();
+    let receipt_store = state.receipts.clone();
+    let preprocessing = state.preprocessing.clone();
+    let proofs_dir = state.config.proofs_dir.clone();
+
+    let outcome = tokio::task::spawn_blocking(move || {
+        prover::aggregate_proofs(&receipt_ids, &receipt_store, &preprocessing, &proofs_dir)
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Aggregation task panicked: {:?}", e),
+                hint: None,
+            }),
+        )
+    })?
+    .map_err(|e| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: e,
+                hint: Some(
+                    "Every receipt_id must reference a Verified receipt with a persisted proof"
+                        .to_string(),
+                ),
+            }),
+        )
+    })?;
+
+    let aggregate = AggregateReceipt {
+        id: uuid::Uuid::new_v4().to_string(),
+        member_ids: request.receipt_ids,
+        merkle_root: outcome.merkle_root,
+        leaf_hashes: outcome.leaf_hashes,
+        created_at: Utc::now(),
+    };
+    state.receipts.insert_aggregate(&aggregate);
+
+    Ok(Json(aggregate.into()))
+}
+
+/// GET /aggregate/:id — look up a previously created aggregate record.
+pub async fn get_aggregate(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<AggregateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.receipts.get_aggregate(&id) {
+        Some(aggregate) => Ok(Json(aggregate.into())),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Aggregate not found".to_string(),
+                hint: Some("Check the aggregate ID".to_string()),
+            }),
+        )),
+    }
+}
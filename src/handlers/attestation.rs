@@ -0,0 +1,62 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::prove::ErrorResponse;
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct VerifyAttestationRequest {
+    pub receipt_id: String,
+}
+
+#[derive(Serialize)]
+pub struct VerifyAttestationResponse {
+    pub receipt_id: String,
+    pub attested: bool,
+    pub signer: Option<String>,
+}
+
+/// POST /attestation/verify — independently confirm that a verified
+/// receipt's `(r, s, v)` signature recovers to the signer it claims,
+/// without trusting the API surface that served the receipt.
+pub async fn verify_attestation(
+    State(state): State<AppState>,
+    Json(request): Json<VerifyAttestationRequest>,
+) -> Result<Json<VerifyAttestationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let receipt = state.receipts.get(&request.receipt_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Receipt not found".into(),
+                hint: Some("Check the receipt_id and try GET /receipt/{id}".to_string()),
+            }),
+        )
+    })?;
+
+    let (attestation, proof_hash) = match (&receipt.attestation, &receipt.proof_hash) {
+        (Some(a), Some(p)) => (a, p),
+        _ => {
+            return Ok(Json(VerifyAttestationResponse {
+                receipt_id: receipt.id,
+                attested: false,
+                signer: None,
+            }))
+        }
+    };
+
+    let attested = crate::crypto::verify_attestation(
+        &receipt.model_hash,
+        &receipt.input_hash,
+        proof_hash,
+        attestation,
+    )
+    .unwrap_or(false);
+
+    Ok(Json(VerifyAttestationResponse {
+        receipt_id: receipt.id,
+        attested,
+        signer: Some(attestation.signer.clone()),
+    }))
+}
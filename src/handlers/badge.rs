@@ -1,13 +1,68 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
 
 use crate::receipt::ReceiptStatus;
 use crate::state::AppState;
 
+#[derive(Deserialize, Default)]
+pub struct BadgeQuery {
+    #[serde(default)]
+    pub style: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub variant: Option<String>,
+}
+
+/// shields.io's [endpoint badge schema](https://shields.io/badges/endpoint-badge) —
+/// lets a README render this badge through shields.io's own CDN and styling
+/// options instead of embedding our SVG directly.
+#[derive(Serialize)]
+struct ShieldsEndpoint {
+    #[serde(rename = "schemaVersion")]
+    schema_version: u32,
+    label: String,
+    message: String,
+    color: String,
+}
+
+fn status_fields(status: &ReceiptStatus) -> (&'static str, &'static str, &'static str) {
+    match status {
+        ReceiptStatus::Proving => ("proving", "#856404", "#fff3cd"),
+        ReceiptStatus::Verified => ("verified", "#155724", "#d4edda"),
+        ReceiptStatus::Failed => ("failed", "#721c24", "#f8d7da"),
+    }
+}
+
+fn shields_color(status: &ReceiptStatus) -> &'static str {
+    match status {
+        ReceiptStatus::Proving => "yellow",
+        ReceiptStatus::Verified => "green",
+        ReceiptStatus::Failed => "red",
+    }
+}
+
+/// `?variant=timing` swaps the badge message for the proof's wall-clock
+/// time once it's `Verified` — falls back to the plain status text for
+/// every other status, since there's no duration to show yet.
+fn badge_message(receipt: &crate::receipt::Receipt, status_text: &str, variant: Option<&str>) -> String {
+    if variant == Some("timing") {
+        if let ReceiptStatus::Verified = receipt.status {
+            if let (Some(prove_ms), Some(verify_ms)) = (receipt.prove_time_ms, receipt.verify_time_ms) {
+                return format!("proved {prove_ms}ms / verified {verify_ms}ms");
+            }
+        }
+    }
+    status_text.to_string()
+}
+
 pub async fn badge(
     State(state): State<AppState>,
     Path(receipt_id): Path<String>,
+    Query(query): Query<BadgeQuery>,
 ) -> Response {
     let receipt = match state.receipts.get(&receipt_id) {
         Some(r) => r,
@@ -16,15 +71,25 @@ pub async fn badge(
         }
     };
 
-    let (status_text, color, bg_color, cache_control) = match receipt.status {
-        ReceiptStatus::Proving => ("proving", "#856404", "#fff3cd", "no-cache"),
-        ReceiptStatus::Verified => ("verified", "#155724", "#d4edda", "public, max-age=3600"),
-        ReceiptStatus::Failed => ("failed", "#721c24", "#f8d7da", "public, max-age=3600"),
+    let (status_text, color, bg_color) = status_fields(&receipt.status);
+    let cache_control = match receipt.status {
+        ReceiptStatus::Proving => "no-cache",
+        ReceiptStatus::Verified | ReceiptStatus::Failed => "public, max-age=3600",
+    };
+    let message = badge_message(&receipt, status_text, query.variant.as_deref());
+
+    let label = query.label.as_deref().unwrap_or("ClawProof");
+    let corner_radius: u32 = match query.style.as_deref() {
+        Some("flat-square") => 0,
+        Some("plastic") => 4,
+        _ => 3,
     };
+    // "plastic" style adds a taller gradient highlight; "flat"/"flat-square"
+    // are flush, matching shields.io's own three built-in styles.
+    let gradient_opacity = if query.style.as_deref() == Some("plastic") { ".25" } else { ".1" };
 
-    let label = "ClawProof";
     let label_width = label.len() as u32 * 7 + 10;
-    let value_width = status_text.len() as u32 * 7 + 10;
+    let value_width = message.len() as u32 * 7 + 10;
     let total_width = label_width + value_width;
 
     let label_x = label_width / 2;
@@ -35,13 +100,13 @@ pub async fn badge(
     let shadow = "#010101";
 
     let svg = format!(
-        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {status_text}">
-  <title>{label}: {status_text}</title>
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total_width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
   <linearGradient id="s" x2="0" y2="100%">
-    <stop offset="0" stop-color="{grad_stop}" stop-opacity=".1"/>
-    <stop offset="1" stop-opacity=".1"/>
+    <stop offset="0" stop-color="{grad_stop}" stop-opacity="{gradient_opacity}"/>
+    <stop offset="1" stop-opacity="{gradient_opacity}"/>
   </linearGradient>
-  <clipPath id="r"><rect width="{total_width}" height="20" rx="3" fill="{white}"/></clipPath>
+  <clipPath id="r"><rect width="{total_width}" height="20" rx="{corner_radius}" fill="{white}"/></clipPath>
   <g clip-path="url(#r)">
     <rect width="{label_width}" height="20" fill="{gray}"/>
     <rect x="{label_width}" width="{value_width}" height="20" fill="{bg_color}"/>
@@ -50,8 +115,8 @@ pub async fn badge(
   <g fill="{white}" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" text-rendering="geometricPrecision" font-size="110">
     <text aria-hidden="true" x="{label_x}0" y="150" fill="{shadow}" fill-opacity=".3" transform="scale(.1)">{label}</text>
     <text x="{label_x}0" y="140" transform="scale(.1)">{label}</text>
-    <text aria-hidden="true" x="{value_x}0" y="150" fill="{shadow}" fill-opacity=".3" transform="scale(.1)">{status_text}</text>
-    <text x="{value_x}0" y="140" transform="scale(.1)" fill="{color}">{status_text}</text>
+    <text aria-hidden="true" x="{value_x}0" y="150" fill="{shadow}" fill-opacity=".3" transform="scale(.1)">{message}</text>
+    <text x="{value_x}0" y="140" transform="scale(.1)" fill="{color}">{message}</text>
   </g>
 </svg>"#
     );
@@ -66,3 +131,42 @@ pub async fn badge(
     )
         .into_response()
 }
+
+/// `GET /badge/:receipt_id/endpoint.json` — the shields.io endpoint schema,
+/// so a README can point shields.io itself at this URL
+/// (`https://img.shields.io/endpoint?url=...`) and get shields.io's own
+/// styling, caching, and social-card rendering instead of our raw SVG.
+pub async fn badge_endpoint(
+    State(state): State<AppState>,
+    Path(receipt_id): Path<String>,
+    Query(query): Query<BadgeQuery>,
+) -> Response {
+    let receipt = match state.receipts.get(&receipt_id) {
+        Some(r) => r,
+        None => {
+            return (StatusCode::NOT_FOUND, "Receipt not found").into_response();
+        }
+    };
+
+    let (status_text, ..) = status_fields(&receipt.status);
+    let message = badge_message(&receipt, status_text, query.variant.as_deref());
+    let label = query.label.as_deref().unwrap_or("ClawProof");
+    let cache_control = match receipt.status {
+        ReceiptStatus::Proving => "no-cache",
+        ReceiptStatus::Verified | ReceiptStatus::Failed => "public, max-age=3600",
+    };
+
+    (
+        [
+            (header::CACHE_CONTROL, cache_control),
+            (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
+        ],
+        Json(ShieldsEndpoint {
+            schema_version: 1,
+            label: label.to_string(),
+            message,
+            color: shields_color(&receipt.status).to_string(),
+        }),
+    )
+        .into_response()
+}
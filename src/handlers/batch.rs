@@ -1,12 +1,18 @@
 use axum::extract::State;
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 
 use super::prove::{ErrorResponse, ProveInput, ProveResponse};
 use crate::handlers::prove::run_single_prove;
 use crate::state::AppState;
 
+/// Each item only enqueues a job onto `AppState::prove_queue` and returns —
+/// the actual proving runs under `Config::prove_concurrency` permits, not
+/// one task per batch item — so this is a sanity cap on request size, not
+/// a concurrency limit.
+const MAX_BATCH_SIZE: usize = 50;
+
 #[derive(Deserialize)]
 pub struct BatchRequest {
     pub requests: Vec<BatchItem>,
@@ -17,8 +23,10 @@ pub struct BatchItem {
     pub model_id: String,
     #[serde(default)]
     pub input: ProveInput,
-    #[serde(default)]
+    #[serde(default, alias = "callback_url")]
     pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub backend: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -26,10 +34,14 @@ pub struct BatchResponse {
     pub receipts: Vec<ProveResponse>,
 }
 
+/// Returns `202 Accepted` — each item's proof runs in the background the
+/// same way a lone `POST /prove` does; see `handlers::prove::prove`.
 pub async fn batch_prove(
     State(state): State<AppState>,
+    Extension(api_key): Extension<Option<crate::api_keys::ApiKeyIdentity>>,
     Json(request): Json<BatchRequest>,
-) -> Result<Json<BatchResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<BatchResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let api_key_label = api_key.map(|k| k.label);
     if request.requests.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -40,11 +52,11 @@ pub async fn batch_prove(
         ));
     }
 
-    if request.requests.len() > 5 {
+    if request.requests.len() > MAX_BATCH_SIZE {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: "Maximum 5 requests per batch".to_string(),
+                error: format!("Maximum {} requests per batch", MAX_BATCH_SIZE),
                 hint: None,
             }),
         ));
@@ -52,9 +64,18 @@ pub async fn batch_prove(
 
     let mut receipts = Vec::new();
     for item in request.requests {
-        let result = run_single_prove(&state, item.model_id, item.input, item.webhook_url).await?;
+        let result = run_single_prove(
+            &state,
+            item.model_id,
+            item.input,
+            item.webhook_url,
+            None,
+            item.backend,
+            api_key_label.clone(),
+        )
+        .await?;
         receipts.push(result);
     }
 
-    Ok(Json(BatchResponse { receipts }))
+    Ok((StatusCode::ACCEPTED, Json(BatchResponse { receipts })))
 }
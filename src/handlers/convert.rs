@@ -29,8 +29,10 @@ pub async fn convert(
     let client = reqwest::Client::new();
     let url = format!("{}/convert", converter_url);
 
-    // Read all fields and forward them
-    let mut form = reqwest::multipart::Form::new();
+    // Read all fields into owned buffers up front — a `reqwest::multipart::
+    // Form` isn't reusable across retries, so each attempt below rebuilds
+    // one from these instead of re-reading the (already-consumed) request.
+    let mut fields: Vec<(String, Vec<u8>)> = Vec::new();
     let mut mp = multipart;
     while let Ok(Some(field)) = mp.next_field().await {
         let name: String = field.name().unwrap_or("file").to_string();
@@ -47,11 +49,20 @@ pub async fn convert(
                     .into_response();
             }
         };
-        let part = reqwest::multipart::Part::bytes(bytes.to_vec()).file_name(name.clone());
-        form = form.part(name, part);
+        fields.push((name, bytes.to_vec()));
     }
 
-    match client.post(&url).multipart(form).send().await {
+    let result = crate::retry::retry_send(crate::retry::DEFAULT_MAX_RETRIES, || {
+        let mut form = reqwest::multipart::Form::new();
+        for (name, bytes) in &fields {
+            let part = reqwest::multipart::Part::bytes(bytes.clone()).file_name(name.clone());
+            form = form.part(name.clone(), part);
+        }
+        client.post(&url).multipart(form).send()
+    })
+    .await;
+
+    match result {
         Ok(resp) => {
             let status = StatusCode::from_u16(resp.status().as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
             let body = resp.bytes().await.unwrap_or_default();
@@ -62,7 +73,18 @@ pub async fn convert(
             )
                 .into_response()
         }
-        Err(e) => {
+        Err(crate::retry::RetryError::Exhausted) => {
+            error!("[clawproof] Converter proxy exhausted retries");
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                Json(ErrorResponse {
+                    error: "Converter service unavailable".to_string(),
+                    hint: Some("The model conversion sidecar did not recover after retries".to_string()),
+                }),
+            )
+                .into_response()
+        }
+        Err(crate::retry::RetryError::Terminal(e)) => {
             error!("[clawproof] Converter proxy failed: {:?}", e);
             (
                 StatusCode::BAD_GATEWAY,
@@ -0,0 +1,56 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::state::AppState;
+
+#[derive(Serialize)]
+pub struct VerifyCredentialResponse {
+    pub verified: bool,
+    pub error: Option<String>,
+}
+
+/// GET /did.json — the did:key document for this server's credential
+/// signing key, so a verifier can resolve the `issuer`/`verificationMethod`
+/// on a `?format=vc` receipt without needing to trust anything but the
+/// `did:key` itself (the public key is embedded in the identifier).
+pub async fn did_document(State(state): State<AppState>) -> Json<Value> {
+    let did = &state.credential_key.did;
+    Json(json!({
+        "@context": [
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/suites/ed25519-2020/v1",
+        ],
+        "id": did,
+        "verificationMethod": [{
+            "id": format!("{}#key-1", did),
+            "type": "Ed25519VerificationKey2020",
+            "controller": did,
+            "publicKeyMultibase": did.strip_prefix("did:key:").unwrap_or(did),
+        }],
+        "authentication": [format!("{}#key-1", did)],
+        "assertionMethod": [format!("{}#key-1", did)],
+    }))
+}
+
+/// POST /verify-credential — check a Verifiable Credential's `proof.jws`
+/// against the `did:key` in its own `proof.verificationMethod`. Takes the
+/// full credential document (not a receipt_id) so a third party can verify
+/// a receipt they received out-of-band, entirely offline from this server.
+pub async fn verify_credential(Json(document): Json<Value>) -> (StatusCode, Json<VerifyCredentialResponse>) {
+    match crate::credential::verify(&document) {
+        Ok(verified) => (
+            StatusCode::OK,
+            Json(VerifyCredentialResponse { verified, error: None }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(VerifyCredentialResponse {
+                verified: false,
+                error: Some(e.to_string()),
+            }),
+        ),
+    }
+}
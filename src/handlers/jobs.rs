@@ -0,0 +1,60 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+
+use super::prove::ErrorResponse;
+use crate::model_jobs::ModelJobRecord;
+use crate::receipt::Receipt;
+use crate::state::AppState;
+
+#[derive(Serialize)]
+pub struct JobStatusResponse {
+    pub job_id: String,
+    #[serde(flatten)]
+    pub receipt: Receipt,
+}
+
+/// GET /jobs/:id — a single status snapshot for callers that requested a
+/// `webhook_url`/`callback_url` but don't run a listener for it. A receipt
+/// IS the job record here (its id is the job id, and `status` already
+/// tracks Proving/Verified/Failed), so this is a thin job-shaped view over
+/// `ReceiptStore::get` rather than a second store. `GET /receipts/:id/poll`
+/// is the long-polling equivalent for callers that want to block until the
+/// next transition instead of a single snapshot.
+pub async fn get_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<JobStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let receipt = state.receipts.get(&id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Job not found".to_string(),
+                hint: Some("Check the job ID (the receipt ID returned by /prove, /prove/batch, or /prove/model)".to_string()),
+            }),
+        )
+    })?;
+
+    Ok(Json(JobStatusResponse { job_id: id, receipt }))
+}
+
+/// GET /jobs/model/:id — status of a `POST /prove/model` pipeline job.
+/// Before the model is preprocessed there's no receipt yet for
+/// `GET /jobs/:id` to serve, so this polls `AppState::model_jobs` instead;
+/// once the job reaches `proving` its `receipt_id` is also set, and
+/// `GET /jobs/:receipt_id` / `GET /receipt/:receipt_id` work from there on.
+pub async fn get_model_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ModelJobRecord>, (StatusCode, Json<ErrorResponse>)> {
+    state.model_jobs.get(&id).map(Json).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Job not found".to_string(),
+                hint: Some("Check the job ID returned by POST /prove/model".to_string()),
+            }),
+        )
+    })
+}
@@ -0,0 +1,37 @@
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+use crate::state::AppState;
+
+/// GET /manifest.webmanifest
+///
+/// Web app manifest for installing the playground as a PWA. Reuses
+/// `BrandConfig` rather than hardcoding a name/color, so a white-labeled
+/// deployment's installed app matches its receipt pages.
+pub async fn manifest(State(state): State<AppState>) -> Response {
+    let brand = &state.config.brand;
+    let icon_src = brand.logo_url.clone().unwrap_or_else(|| "/favicon.ico".to_string());
+    let theme_color = brand.accent.clone().unwrap_or_else(|| "#111827".to_string());
+
+    let manifest = serde_json::json!({
+        "name": brand.wordmark,
+        "short_name": brand.wordmark,
+        "description": "Cryptographic proof receipts for AI-driven transaction decisions.",
+        "start_url": "/",
+        "scope": "/",
+        "display": "standalone",
+        "background_color": "#ffffff",
+        "theme_color": theme_color,
+        "icons": [
+            { "src": icon_src, "sizes": "any", "type": "image/png" }
+        ]
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/manifest+json")],
+        Json(manifest),
+    )
+        .into_response()
+}
@@ -1,9 +1,13 @@
-use axum::extract::State;
-use axum::Json;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
 
-use crate::receipt::ReceiptStats;
-use crate::state::AppState;
-
-pub async fn metrics(State(state): State<AppState>) -> Json<ReceiptStats> {
-    Json(state.receipts.get_stats())
+/// GET /metrics — Prometheus text-format exposition of process-lifetime
+/// proof counters and latency histograms. See `crate::metrics`.
+pub async fn metrics() -> Response {
+    let body = crate::metrics::metrics().render();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
+        .into_response()
 }
@@ -0,0 +1,33 @@
+pub mod activitypub;
+pub mod admin_api_keys;
+pub mod admin_tokens;
+pub mod agent_lookup;
+pub mod aggregate;
+pub mod attestation;
+pub mod badge;
+pub mod batch;
+pub mod convert;
+pub mod credential;
+pub mod health;
+pub mod jobs;
+pub mod manifest;
+pub mod metrics;
+pub mod model_status;
+pub mod models;
+pub mod openapi;
+pub mod prove;
+pub mod prove_model;
+pub mod receipt;
+pub mod receipt_bundle;
+pub mod receipt_events;
+pub mod receipt_poll;
+pub mod receipt_proof;
+pub mod receipt_unlock;
+pub mod receipt_ws;
+pub mod receipts_list;
+pub mod scrub;
+pub mod service_worker;
+pub mod static_update;
+pub mod upload;
+pub mod upload_resumable;
+pub mod verify;
@@ -0,0 +1,26 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+
+use super::prove::ErrorResponse;
+use crate::preprocess_queue::PreprocessJobRecord;
+use crate::state::AppState;
+
+/// GET /models/:id/status — reports `upload_model`'s background
+/// preprocessing job state (`queued`/`running`/`done`/`failed`, with retry
+/// count and the last error) so a client can poll instead of guessing from
+/// the `"preprocessing"` string `upload_model` returns at submit time.
+pub async fn model_status(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<PreprocessJobRecord>, (StatusCode, Json<ErrorResponse>)> {
+    state.preprocess_queue.status_for_model(&model_id).map(Json).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "No preprocessing job found for this model".to_string(),
+                hint: Some("Check the model_id returned by POST /models/upload".to_string()),
+            }),
+        )
+    })
+}
@@ -1,6 +1,6 @@
 use axum::extract::State;
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use chrono::Utc;
 use onnx_tracer::{model, tensor::Tensor};
 use serde::{Deserialize, Serialize};
@@ -10,17 +10,37 @@ use tracing::{error, info};
 use crate::crypto;
 use crate::input::{build_onehot_vector, build_tfidf_vector, build_token_index_vector};
 use crate::models::InputType;
-use crate::prover;
 use crate::receipt::{InferenceOutput, Receipt, ReceiptStatus};
-use crate::state::{AppState, VocabData};
+use crate::state::{AppState, ProverBackendKind, VocabData};
 
 #[derive(Deserialize)]
 pub struct ProveRequest {
     pub model_id: String,
     #[serde(default)]
     pub input: ProveInput,
-    #[serde(default)]
+    /// `callback_url` is accepted as an alias — proving is asynchronous
+    /// (the receipt starts in `Proving` status and this POSTs its final
+    /// state), so "callback" describes it just as well as "webhook".
+    #[serde(default, alias = "callback_url")]
     pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub protect: Option<ProtectionOptions>,
+    /// Which `ProverBackendKind` to prove with — "jolt_atlas" (default) for
+    /// the real SNARK, or "mock" to skip proving for fast local/CI runs.
+    #[serde(default)]
+    pub backend: Option<String>,
+}
+
+/// Optional Firefox-Send-style sharing controls for the receipt page:
+/// an expiration window, a view-count cap, and/or a passphrase.
+#[derive(Deserialize, Default)]
+pub struct ProtectionOptions {
+    #[serde(default)]
+    pub expires_in_secs: Option<i64>,
+    #[serde(default)]
+    pub max_views: Option<u32>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -50,19 +70,77 @@ pub struct ErrorResponse {
     pub hint: Option<String>,
 }
 
+/// Returns `202 Accepted` rather than `200` — the receipt comes back in
+/// `Proving` status, with the actual proof enqueued on `state.prove_queue`
+/// for `queue::spawn_dispatcher`'s worker pool (see `queue::ProofQueue`);
+/// `GET /jobs/:id` or the `webhook_url` callback is how a caller learns it
+/// finished.
 pub async fn prove(
     State(state): State<AppState>,
+    Extension(api_key): Extension<Option<crate::api_keys::ApiKeyIdentity>>,
     Json(request): Json<ProveRequest>,
-) -> Result<Json<ProveResponse>, (StatusCode, Json<ErrorResponse>)> {
-    run_single_prove(&state, request.model_id, request.input, request.webhook_url).await
-        .map(Json)
+) -> Result<(StatusCode, Json<ProveResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let api_key_label = api_key.map(|k| k.label);
+    run_single_prove(
+        &state,
+        request.model_id,
+        request.input,
+        request.webhook_url,
+        request.protect,
+        request.backend,
+        api_key_label,
+    )
+    .await
+    .map(|response| (StatusCode::ACCEPTED, Json(response)))
 }
 
+/// Maps an error's `(StatusCode, message)` to a `clawproof_prove_requests_total`
+/// outcome label. Deliberately derives the label from the response instead of
+/// threading an outcome enum through every early return in
+/// `run_single_prove_inner` — those returns already have to pick the right
+/// status/message for the caller, so reusing that is less to keep in sync.
+fn outcome_for_error(status: StatusCode, message: &str) -> &'static str {
+    match status {
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::SERVICE_UNAVAILABLE => "model_loading",
+        StatusCode::UNPROCESSABLE_ENTITY if message.contains("unsupported") => "unsupported",
+        StatusCode::UNPROCESSABLE_ENTITY => "capacity_exceeded",
+        StatusCode::BAD_REQUEST => "validation_rejected",
+        StatusCode::INTERNAL_SERVER_ERROR if message.contains("crashed") => "inference_panic",
+        StatusCode::INTERNAL_SERVER_ERROR if message.to_lowercase().contains("inference") => "inference_failed",
+        _ => "internal_error",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run_single_prove(
     state: &AppState,
     model_id: String,
     input: ProveInput,
     webhook_url: Option<String>,
+    protect: Option<ProtectionOptions>,
+    backend: Option<String>,
+    api_key_label: Option<String>,
+) -> Result<ProveResponse, (StatusCode, Json<ErrorResponse>)> {
+    let metrics_model_id = model_id.clone();
+    let result = run_single_prove_inner(state, model_id, input, webhook_url, protect, backend, api_key_label).await;
+    let outcome = match &result {
+        Ok(_) => "accepted",
+        Err((status, Json(body))) => outcome_for_error(*status, &body.error),
+    };
+    crate::metrics::metrics().record_prove_request(&metrics_model_id, outcome);
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_single_prove_inner(
+    state: &AppState,
+    model_id: String,
+    input: ProveInput,
+    webhook_url: Option<String>,
+    protect: Option<ProtectionOptions>,
+    backend: Option<String>,
+    api_key_label: Option<String>,
 ) -> Result<ProveResponse, (StatusCode, Json<ErrorResponse>)> {
     // Validate webhook URL if provided
     if let Some(ref url) = webhook_url {
@@ -75,8 +153,47 @@ pub async fn run_single_prove(
                 }),
             ));
         }
+
+        // Resolve the host now so an internal address (cloud metadata,
+        // loopback, RFC 1918/4193 ranges) is rejected before a receipt is
+        // even created. `prover::fire_webhook` re-checks at delivery time
+        // too, since this resolution can go stale by then (DNS rebinding).
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        error: "webhook_url is not a valid URL".to_string(),
+                        hint: None,
+                    }),
+                )
+            })?;
+        if let Err(e) = crate::ssrf::check_host(&host).await {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "webhook_url is not reachable".to_string(),
+                    hint: Some(e),
+                }),
+            ));
+        }
     }
 
+    let backend_kind = match backend {
+        Some(ref raw) => ProverBackendKind::from_str(raw).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: format!("Unknown backend: {}", raw),
+                    hint: Some("Use \"jolt_atlas\" or \"mock\"".to_string()),
+                }),
+            )
+        })?,
+        None => ProverBackendKind::default(),
+    };
+
     let model_desc = {
         let registry = state.registry.read().expect("model registry lock poisoned");
         registry.get(&model_id).cloned().ok_or_else(|| {
@@ -90,7 +207,17 @@ pub async fn run_single_prove(
         })?
     };
 
-    if !state.preprocessing.contains_key(&model_id) {
+    if model_desc.status == crate::models::ModelStatus::Unsupported {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: format!("Model '{}' is unsupported", model_id),
+                hint: model_desc.unsupported_reason.clone(),
+            }),
+        ));
+    }
+
+    if !state.preprocessing.contains_key(&(model_id.clone(), backend_kind)) {
         return Err((
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ErrorResponse {
@@ -137,7 +264,7 @@ pub async fn run_single_prove(
             })?;
 
             match vocab {
-                VocabData::TfIdf(v) => build_tfidf_vector(text, v, model_desc.input_dim),
+                VocabData::TfIdf(v) => build_tfidf_vector(text, v, model_desc.input_dim, &model_desc.quantization),
                 VocabData::TokenIndex(v) => build_token_index_vector(text, v, model_desc.input_dim),
                 _ => {
                     return Err((
@@ -198,7 +325,7 @@ pub async fn run_single_prove(
 
             match vocab {
                 VocabData::OneHot(v) => {
-                    build_onehot_vector(fields, &field_names, v, model_desc.input_dim)
+                    build_onehot_vector(fields, &field_names, v, model_desc.input_dim, &model_desc.quantization)
                 }
                 _ => {
                     return Err((
@@ -236,7 +363,7 @@ pub async fn run_single_prove(
                 ));
             }
 
-            raw.clone()
+            raw.iter().map(|&v| model_desc.quantization.apply(v as f64)).collect()
         }
     };
 
@@ -255,11 +382,59 @@ pub async fn run_single_prove(
     // Run inference (forward pass only)
     let model_path = state.config.resolve_model_path(&model_id);
 
+    // Re-run the lightweight step count against this concrete input — the
+    // preprocessed trace_length was sized off a zero-valued probe input, and
+    // a pathological real input (e.g. one that drives more loop iterations
+    // through a data-dependent branch) could still overflow it.
+    let capacity_path = model_path.clone();
+    let capacity_tensor = input_tensor.clone();
+    let trace_length = model_desc.trace_length;
+    let fits_trace = tokio::task::spawn_blocking(move || {
+        crate::capacity::dry_run_step_count(&capacity_path, &capacity_tensor)
+    })
+    .await
+    .map_err(|e| {
+        error!("[clawproof] Capacity check task failed: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to validate input against model capacity".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+    match fits_trace {
+        Ok(step_count) if step_count <= trace_length => {}
+        Ok(step_count) => {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorResponse {
+                    error: format!(
+                        "Input requires {} steps, which exceeds the preprocessed trace_length of {}",
+                        step_count, trace_length
+                    ),
+                    hint: Some("Try a smaller or less complex input for this model".to_string()),
+                }),
+            ));
+        }
+        Err(e) => {
+            error!("[clawproof] Capacity dry run failed: {:?}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to validate input against model capacity".to_string(),
+                    hint: None,
+                }),
+            ));
+        }
+    }
+
     // Run inference in a blocking thread with panic protection to avoid
     // taking down the server if the ONNX tracer panics.
     let inference_path = model_path.clone();
     let inference_tensor = input_tensor.clone();
-    let raw_output: Vec<i32> = tokio::task::spawn_blocking(move || {
+    let inference_start = std::time::Instant::now();
+    let inference_result = tokio::task::spawn_blocking(move || {
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             let model_instance = model(&inference_path);
             let result = model_instance
@@ -268,7 +443,9 @@ pub async fn run_single_prove(
             Ok::<_, String>(result.outputs[0].data().to_vec())
         }))
     })
-    .await
+    .await;
+    crate::metrics::metrics().record_inference_duration(inference_start.elapsed().as_millis() as f64);
+    let raw_output: Vec<i32> = inference_result
     .map_err(|e| {
         error!("[clawproof] Inference task failed: {:?}", e);
         (
@@ -347,6 +524,29 @@ pub async fn run_single_prove(
         confidence,
     };
 
+    let (expires_at, max_views, passphrase_hash) = match protect {
+        Some(opts) => {
+            let expires_at = opts
+                .expires_in_secs
+                .map(|secs| Utc::now() + chrono::Duration::seconds(secs));
+            let passphrase_hash = match opts.passphrase {
+                Some(passphrase) => Some(crypto::hash_passphrase(&passphrase).map_err(|e| {
+                    error!("[clawproof] Failed to hash sharing passphrase: {:?}", e);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "Failed to set up receipt passphrase".to_string(),
+                            hint: None,
+                        }),
+                    )
+                })?),
+                None => None,
+            };
+            (expires_at, opts.max_views, passphrase_hash)
+        }
+        None => (None, None, None),
+    };
+
     let receipt = Receipt {
         id: receipt_id.clone(),
         model_id: model_id.clone(),
@@ -363,24 +563,46 @@ pub async fn run_single_prove(
         prove_time_ms: None,
         verify_time_ms: None,
         error: None,
+        webhook_error: None,
+        attestation: None,
+        expires_at,
+        max_views,
+        view_count: 0,
+        passphrase_hash,
+        backend: backend_kind.as_str().to_string(),
+        api_key_label,
     };
 
     state.receipts.insert(receipt);
+    state.proof_progress.publish(
+        &receipt_id,
+        crate::state::ProofProgress::new(crate::state::ProofStage::Queued, Some(0), None),
+    );
 
     info!(
-        "[clawproof] Receipt {} created, spawning proof for model {}",
+        "[clawproof] Receipt {} created, enqueuing proof for model {}",
         receipt_id, model_id
     );
 
-    prover::prove_and_verify(
-        receipt_id.clone(),
-        state.receipts.clone(),
-        state.preprocessing.clone(),
-        model_id.clone(),
-        state.config.clone(),
-        input_tensor,
-        webhook_url,
-    );
+    if let Err(e) = state.prove_queue.enqueue(
+        &receipt_id,
+        &model_id,
+        backend_kind,
+        &model_desc.input_shape,
+        &input_vector,
+        webhook_url.as_deref(),
+    ) {
+        error!("[clawproof] Failed to enqueue proof job for receipt {}: {:?}", receipt_id, e);
+        state.receipts.update(&receipt_id, |r| {
+            r.status = ReceiptStatus::Failed;
+            r.error = Some("Failed to enqueue proof job".to_string());
+            r.completed_at = Some(Utc::now());
+        });
+        state.proof_progress.publish(
+            &receipt_id,
+            crate::state::ProofProgress::failed(None, "Failed to enqueue proof job".to_string()),
+        );
+    }
 
     let receipt_url = format!("{}/receipt/{}", state.config.base_url, receipt_id);
 
@@ -4,47 +4,68 @@ use axum::Json;
 use serde::Serialize;
 use tracing::{error, info};
 
-use super::prove::{ErrorResponse, ProveInput, run_single_prove};
+use super::prove::{ProveInput, run_single_prove};
+use crate::api_error::ApiError;
+use crate::model_jobs::ModelJobStatus;
 use crate::models::{InputType, ModelDescriptor};
-use crate::state::{AppState, PreprocessingCache};
+use crate::npy;
+use crate::prover;
+use crate::state::{AppState, ProverBackendKind};
 
-use ark_bn254::Fr;
-use jolt_core::poly::commitment::dory::DoryCommitmentScheme;
-use jolt_core::transcripts::KeccakTranscript;
 use onnx_tracer::model;
-use zkml_jolt_core::jolt::JoltSNARK;
-
-#[allow(clippy::upper_case_acronyms)]
-type PCS = DoryCommitmentScheme;
-type Snark = JoltSNARK<Fr, PCS, KeccakTranscript>;
 
 #[derive(Serialize)]
-pub struct ProveModelResponse {
-    pub receipt_id: String,
-    pub receipt_url: String,
-    pub model_id: String,
-    pub output: crate::receipt::InferenceOutput,
+pub struct ProveModelJobResponse {
+    pub job_id: String,
+    pub job_url: String,
     pub status: String,
 }
 
+/// Payload fired at `webhook_url` when the pipeline fails before a `Receipt`
+/// exists to carry it — once proving is enqueued, the receipt's own
+/// terminal-state webhook (see `prover::fire_webhook`) takes over instead.
+#[derive(Serialize)]
+struct ModelJobWebhookPayload<'a> {
+    job_id: &'a str,
+    status: &'a str,
+    code: &'static str,
+    error: &'a str,
+}
+
 /// Unified endpoint: upload a model file + input, get a proof back.
 ///
 /// Accepts multipart form with:
 ///   - `onnx_file` or `model_file`: the model (ONNX, or .pt/.pkl/.pb if converter is available)
 ///   - `source_format` (optional): "onnx" (default), "pytorch", "sklearn", "tensorflow"
-///   - `input_raw`: JSON array of i32 (the raw input vector)
+///   - `input_raw`: JSON array of i32 (the flattened input vector) — mutually
+///     exclusive with `input_tensor`
+///   - `input_tensor` (optional): a binary `.npy` or `.npz` file; its data is
+///     flattened into the same buffer `input_raw` would have held, and its
+///     own shape is used unless `input_shape` overrides it
+///   - `input_shape` (optional): JSON array of dimensions, e.g. `[1,1,28,28]`
+///     — must multiply out to the flattened input's length; defaults to the
+///     uploaded tensor's own shape, or `[1, input_dim]` for a flat `input_raw`
 ///   - `input_dim`: integer, required
 ///   - `labels`: JSON array of strings (optional, defaults to ["class_0", "class_1"])
 ///   - `trace_length`: integer (optional, defaults to 16384)
 ///   - `name`: model name (optional)
 ///   - `webhook_url`: HTTPS callback URL (optional)
+///
+/// Returns `202 Accepted` with a `job_id` immediately — conversion,
+/// preprocessing and proving all run in the background, since a large
+/// `trace_length` can otherwise tie up the connection for minutes. Poll
+/// `GET /jobs/model/:job_id` for progress through `queued` / `converting` /
+/// `preprocessing` / `proving` / `failed`; `proving` is the last state it
+/// reports, with the `receipt_id` to follow from then on.
 pub async fn prove_model(
     State(state): State<AppState>,
     mut multipart: Multipart,
-) -> Result<Json<ProveModelResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<ProveModelJobResponse>), ApiError> {
     let mut model_bytes: Option<Vec<u8>> = None;
     let mut source_format = "onnx".to_string();
     let mut input_raw: Option<Vec<i32>> = None;
+    let mut input_tensor_bytes: Option<Vec<u8>> = None;
+    let mut input_shape: Option<Vec<usize>> = None;
     let mut input_dim: usize = 0;
     let mut labels: Vec<String> = Vec::new();
     let mut trace_length: usize = 1 << 14;
@@ -55,23 +76,12 @@ pub async fn prove_model(
         let field_name: String = field.name().unwrap_or("").to_string();
         match field_name.as_str() {
             "onnx_file" | "model_file" => {
-                let bytes = field.bytes().await.map_err(|e| {
-                    (
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse {
-                            error: format!("Failed to read model file: {}", e),
-                            hint: None,
-                        }),
-                    )
-                })?;
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::InvalidMultipart(e.to_string()))?;
                 if bytes.len() > 5 * 1024 * 1024 {
-                    return Err((
-                        StatusCode::PAYLOAD_TOO_LARGE,
-                        Json(ErrorResponse {
-                            error: "Model file exceeds 5MB limit".to_string(),
-                            hint: None,
-                        }),
-                    ));
+                    return Err(ApiError::ModelTooLarge);
                 }
                 model_bytes = Some(bytes.to_vec());
             }
@@ -80,14 +90,26 @@ pub async fn prove_model(
             }
             "input_raw" => {
                 let text = field.text().await.unwrap_or_default();
-                input_raw = Some(serde_json::from_str(&text).map_err(|e| {
-                    (
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse {
-                            error: format!("Invalid input_raw JSON: {}", e),
-                            hint: Some("Provide a JSON array of integers, e.g. [0, 1, 2, ...]".to_string()),
-                        }),
-                    )
+                input_raw = Some(serde_json::from_str(&text).map_err(|e| ApiError::InvalidJson {
+                    field: "input_raw",
+                    detail: e.to_string(),
+                })?);
+            }
+            "input_tensor" => {
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::InvalidMultipart(e.to_string()))?;
+                if bytes.len() > 5 * 1024 * 1024 {
+                    return Err(ApiError::InputTensorTooLarge);
+                }
+                input_tensor_bytes = Some(bytes.to_vec());
+            }
+            "input_shape" => {
+                let text = field.text().await.unwrap_or_default();
+                input_shape = Some(serde_json::from_str(&text).map_err(|e| ApiError::InvalidJson {
+                    field: "input_shape",
+                    detail: e.to_string(),
                 })?);
             }
             "input_dim" => {
@@ -105,96 +127,203 @@ pub async fn prove_model(
             "name" => {
                 name = field.text().await.unwrap_or_default();
             }
-            "webhook_url" => {
+            "webhook_url" | "callback_url" => {
                 webhook_url = Some(field.text().await.unwrap_or_default());
             }
             _ => {}
         }
     }
 
-    let model_bytes = model_bytes.ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Missing model file (onnx_file or model_file field)".to_string(),
-                hint: Some("Upload an ONNX model as multipart field 'onnx_file'".to_string()),
-            }),
-        )
-    })?;
-
-    let input_raw = input_raw.ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Missing input_raw field".to_string(),
-                hint: Some("Provide input as JSON array: input_raw=[0, 1, 2, ...]".to_string()),
-            }),
-        )
-    })?;
+    let model_bytes = model_bytes.ok_or(ApiError::MissingField("onnx_file or model_file"))?;
+
+    if input_raw.is_some() && input_tensor_bytes.is_some() {
+        return Err(ApiError::InvalidInput("Provide either input_raw or input_tensor, not both".to_string()));
+    }
+
+    let (input_raw, tensor_shape) = if let Some(bytes) = input_tensor_bytes {
+        let tensor = npy::parse_tensor(&bytes).map_err(|e| ApiError::InvalidInput(e.to_string()))?;
+        (tensor.data, Some(tensor.shape))
+    } else {
+        (input_raw.ok_or(ApiError::MissingField("input_raw or input_tensor"))?, None)
+    };
 
     if input_dim == 0 {
         input_dim = input_raw.len();
     }
 
+    // An explicit `input_shape` field wins over the uploaded tensor's own
+    // shape, which in turn wins over the `[1, input_dim]` default applied
+    // once `run_pipeline` knows `input_dim`'s final value.
+    let input_shape = input_shape.or(tensor_shape);
+    if let Some(shape) = &input_shape {
+        let product: usize = shape.iter().product();
+        if product != input_raw.len() {
+            return Err(ApiError::InvalidInput(format!(
+                "input_shape {:?} has {} elements but the input has {}",
+                shape,
+                product,
+                input_raw.len()
+            )));
+        }
+    }
+
     if labels.is_empty() {
         labels = vec!["class_0".to_string(), "class_1".to_string()];
     }
 
+    if source_format != "onnx" && state.config.converter_url.is_none() {
+        return Err(ApiError::UnsupportedSourceFormat(source_format));
+    }
+
+    let job_id = uuid::Uuid::new_v4().simple().to_string();
+    state.model_jobs.insert_queued(&job_id);
+
+    tokio::spawn(run_pipeline(
+        state.clone(),
+        job_id.clone(),
+        model_bytes,
+        source_format,
+        input_raw,
+        input_dim,
+        input_shape,
+        labels,
+        trace_length,
+        name,
+        webhook_url,
+    ));
+
+    let job_url = format!("{}/jobs/model/{}", state.config.base_url, job_id);
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ProveModelJobResponse {
+            job_id,
+            job_url,
+            status: ModelJobStatus::Queued.as_str().to_string(),
+        }),
+    ))
+}
+
+/// Fail the job, log, clean up `model_dir` if it was already created, and
+/// fire `webhook_url` with a job-shaped payload (there's no `Receipt` yet
+/// for `prover::fire_webhook`'s signed, retried delivery to attach to).
+fn fail_job(state: &AppState, job_id: &str, model_dir: Option<&std::path::Path>, webhook_url: Option<&str>, err: ApiError) {
+    let code = err.code();
+    let message = err.message();
+    error!("[clawproof] prove_model job {} failed ({}): {}", job_id, code, message);
+    if let Some(dir) = model_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    state.model_jobs.fail(job_id, code, message.clone());
+    if let Some(url) = webhook_url {
+        let payload = ModelJobWebhookPayload {
+            job_id,
+            status: ModelJobStatus::Failed.as_str(),
+            code,
+            error: &message,
+        };
+        let job_id = job_id.to_string();
+        prover::fire_webhook_payload(url, &payload, state.config.webhook_signing_secret.clone(), move |delivery_err| {
+            error!("[clawproof] Webhook delivery for failed job {} also failed: {}", job_id, delivery_err);
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_pipeline(
+    state: AppState,
+    job_id: String,
+    model_bytes: Vec<u8>,
+    source_format: String,
+    input_raw: Vec<i32>,
+    mut input_dim: usize,
+    input_shape: Option<Vec<usize>>,
+    labels: Vec<String>,
+    trace_length: usize,
+    name: String,
+    webhook_url: Option<String>,
+) {
     // Convert to ONNX if needed
+    state.model_jobs.set_status(&job_id, ModelJobStatus::Converting);
     let onnx_bytes = if source_format == "onnx" {
         model_bytes
     } else {
-        let converter_url = state.config.converter_url.as_ref().ok_or_else(|| {
-            (
-                StatusCode::NOT_IMPLEMENTED,
-                Json(ErrorResponse {
-                    error: format!("Conversion from '{}' requires the converter sidecar", source_format),
-                    hint: Some("Upload an ONNX file directly, or wait for the converter service".to_string()),
-                }),
-            )
-        })?;
+        // `converter_url.is_some()` was already checked before this job was
+        // queued, so this only re-validates it hasn't disappeared since.
+        let converter_url = match state.config.converter_url.as_ref() {
+            Some(url) => url,
+            None => {
+                fail_job(
+                    &state,
+                    &job_id,
+                    None,
+                    webhook_url.as_deref(),
+                    ApiError::ConverterUnavailable("Converter sidecar is no longer configured".to_string()),
+                );
+                return;
+            }
+        };
 
         let client = reqwest::Client::new();
         let url = format!("{}/convert", converter_url);
-        let part = reqwest::multipart::Part::bytes(model_bytes)
+        let part = match reqwest::multipart::Part::bytes(model_bytes)
             .file_name("model")
             .mime_str("application/octet-stream")
-            .unwrap();
+        {
+            Ok(part) => part,
+            Err(e) => {
+                fail_job(
+                    &state,
+                    &job_id,
+                    None,
+                    webhook_url.as_deref(),
+                    ApiError::ConverterUnavailable(format!("Failed to build conversion request: {}", e)),
+                );
+                return;
+            }
+        };
         let form = reqwest::multipart::Form::new()
             .part("file", part)
             .text("source_format", source_format.clone());
 
-        let resp = client.post(&url).multipart(form).send().await.map_err(|e| {
-            error!("[clawproof] Converter proxy failed: {:?}", e);
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(ErrorResponse {
-                    error: "Converter service unavailable".to_string(),
-                    hint: Some("The model conversion sidecar is not responding".to_string()),
-                }),
-            )
-        })?;
+        let resp = match client.post(&url).multipart(form).send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                fail_job(
+                    &state,
+                    &job_id,
+                    None,
+                    webhook_url.as_deref(),
+                    ApiError::ConverterUnavailable(format!("Converter service unavailable: {:?}", e)),
+                );
+                return;
+            }
+        };
 
         if !resp.status().is_success() {
             let detail = resp.text().await.unwrap_or_default();
-            return Err((
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(ErrorResponse {
-                    error: format!("Model conversion failed: {}", detail),
-                    hint: None,
-                }),
-            ));
+            fail_job(
+                &state,
+                &job_id,
+                None,
+                webhook_url.as_deref(),
+                ApiError::ConverterUnavailable(format!("Model conversion failed: {}", detail)),
+            );
+            return;
         }
 
-        resp.bytes().await.map_err(|e| {
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(ErrorResponse {
-                    error: format!("Failed to read converted model: {}", e),
-                    hint: None,
-                }),
-            )
-        })?.to_vec()
+        match resp.bytes().await {
+            Ok(bytes) => bytes.to_vec(),
+            Err(e) => {
+                fail_job(
+                    &state,
+                    &job_id,
+                    None,
+                    webhook_url.as_deref(),
+                    ApiError::ConverterUnavailable(format!("Failed to read converted model: {}", e)),
+                );
+                return;
+            }
+        }
     };
 
     // Save ONNX to temp model directory
@@ -205,42 +334,36 @@ pub async fn prove_model(
     );
 
     let model_dir = state.config.uploaded_models_dir.join(&model_id);
-    std::fs::create_dir_all(&model_dir).map_err(|e| {
-        error!("[clawproof] Failed to create model dir: {:?}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Failed to save model".to_string(),
-                hint: None,
-            }),
-        )
-    })?;
+    if let Err(e) = std::fs::create_dir_all(&model_dir) {
+        fail_job(&state, &job_id, None, webhook_url.as_deref(), ApiError::Internal(format!("Failed to save model: {:?}", e)));
+        return;
+    }
 
     let onnx_path = model_dir.join("network.onnx");
-    std::fs::write(&onnx_path, &onnx_bytes).map_err(|e| {
-        error!("[clawproof] Failed to write ONNX file: {:?}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Failed to save model".to_string(),
-                hint: None,
-            }),
-        )
-    })?;
+    if let Err(e) = std::fs::write(&onnx_path, &onnx_bytes) {
+        fail_job(
+            &state,
+            &job_id,
+            Some(&model_dir),
+            webhook_url.as_deref(),
+            ApiError::Internal(format!("Failed to save model: {:?}", e)),
+        );
+        return;
+    }
 
     // Quick magic-byte check before attempting to load
     if onnx_bytes.len() < 4 || &onnx_bytes[..4] != b"\x08\x03\x12\x04" && &onnx_bytes[..2] != b"\x08\x03" {
         // ONNX protobuf files start with field 1 (ir_version) varint tag 0x08
         // Do a best-effort check — if it doesn't even look like protobuf, reject early
         if onnx_bytes.len() < 2 || onnx_bytes[0] != 0x08 {
-            let _ = std::fs::remove_dir_all(&model_dir);
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse {
-                    error: "File does not appear to be an ONNX model".to_string(),
-                    hint: Some("Upload a valid .onnx file (ONNX protobuf format)".to_string()),
-                }),
-            ));
+            fail_job(
+                &state,
+                &job_id,
+                Some(&model_dir),
+                webhook_url.as_deref(),
+                ApiError::InvalidOnnx("File does not appear to be an ONNX model".to_string()),
+            );
+            return;
         }
     }
 
@@ -250,22 +373,26 @@ pub async fn prove_model(
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             let _ = model(&onnx_path_clone);
         }))
-    }).await;
-
-    match validation {
-        Ok(Ok(())) => {}
-        _ => {
-            let _ = std::fs::remove_dir_all(&model_dir);
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse {
-                    error: "Invalid ONNX model — failed to load".to_string(),
-                    hint: Some("Ensure the file is a valid ONNX model with supported operations".to_string()),
-                }),
-            ));
-        }
+    })
+    .await;
+
+    if !matches!(validation, Ok(Ok(()))) {
+        fail_job(
+            &state,
+            &job_id,
+            Some(&model_dir),
+            webhook_url.as_deref(),
+            ApiError::InvalidOnnx("Invalid ONNX model — failed to load".to_string()),
+        );
+        return;
+    }
+
+    if input_dim == 0 {
+        input_dim = input_raw.len();
     }
 
+    let input_shape = input_shape.unwrap_or_else(|| vec![1, input_dim]);
+
     // Save model.toml
     let toml_content = format!(
         r#"id = "{model_id}"
@@ -273,13 +400,14 @@ name = "{name}"
 description = "Uploaded via /prove/model"
 input_type = "raw"
 input_dim = {input_dim}
-input_shape = [1, {input_dim}]
+input_shape = [{input_shape_str}]
 labels = [{labels_str}]
 trace_length = {trace_length}
 "#,
         model_id = model_id,
         name = name,
         input_dim = input_dim,
+        input_shape_str = input_shape.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "),
         labels_str = labels.iter().map(|l| format!("\"{}\"", l)).collect::<Vec<_>>().join(", "),
         trace_length = trace_length,
     );
@@ -292,10 +420,14 @@ trace_length = {trace_length}
         description: "Uploaded via /prove/model".to_string(),
         input_type: InputType::Raw,
         input_dim,
-        input_shape: vec![1, input_dim],
+        input_shape,
         labels,
         trace_length,
+        status: crate::models::ModelStatus::Ready,
+        quantization: crate::models::QuantizationConfig::default(),
+        unsupported_reason: None,
         fields: None,
+        model_hash: None,
     };
 
     {
@@ -303,64 +435,78 @@ trace_length = {trace_length}
         registry.register(descriptor);
     }
 
-    // Preprocess synchronously — we need it to prove
-    info!("[clawproof] Preprocessing uploaded model {} for immediate proof", model_id);
+    // Preprocess
+    state.model_jobs.set_status(&job_id, ModelJobStatus::Preprocessing);
+    info!("[clawproof] Preprocessing uploaded model {} (job {})", model_id, job_id);
     let preprocess_onnx_path = onnx_path.clone();
     let preprocess_trace = trace_length;
     let preprocessing = tokio::task::spawn_blocking(move || {
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            let model_fn = || model(&preprocess_onnx_path);
-            Snark::prover_preprocess(model_fn, preprocess_trace)
+            prover::backend_for(ProverBackendKind::JoltAtlas).preprocess(&preprocess_onnx_path, preprocess_trace)
         }))
     })
-    .await
-    .map_err(|e| {
-        error!("[clawproof] Preprocessing task failed for {}: {:?}", model_id, e);
-        let _ = std::fs::remove_dir_all(&model_dir);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: "Model preprocessing failed".to_string(),
-                hint: Some("The model may use unsupported ONNX operations".to_string()),
-            }),
-        )
-    })?
-    .map_err(|_| {
-        error!("[clawproof] Preprocessing panicked for {}", model_id);
-        let _ = std::fs::remove_dir_all(&model_dir);
-        (
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Model preprocessing crashed — likely uses unsupported operations".to_string(),
-                hint: Some("Use a simpler ONNX model with supported ops (Gemm, Relu, Add, etc.)".to_string()),
-            }),
-        )
-    })?;
-
-    let verifier_preprocessing = (&preprocessing).into();
-    state.preprocessing.insert(
-        model_id.clone(),
-        PreprocessingCache {
-            prover: preprocessing,
-            verifier: verifier_preprocessing,
-        },
-    );
-    info!("[clawproof] Model {} preprocessed, running proof", model_id);
+    .await;
+
+    let preprocessing = match preprocessing {
+        Ok(Ok(Ok(preprocessing))) => preprocessing,
+        Ok(Ok(Err(e))) => {
+            fail_job(
+                &state,
+                &job_id,
+                Some(&model_dir),
+                webhook_url.as_deref(),
+                ApiError::UnsupportedOps(format!("{:?}", e)),
+            );
+            return;
+        }
+        Ok(Err(_)) => {
+            fail_job(&state, &job_id, Some(&model_dir), webhook_url.as_deref(), ApiError::PreprocessingCrashed);
+            return;
+        }
+        Err(e) => {
+            fail_job(
+                &state,
+                &job_id,
+                Some(&model_dir),
+                webhook_url.as_deref(),
+                ApiError::Internal(format!("Preprocessing task failed: {:?}", e)),
+            );
+            return;
+        }
+    };
+
+    state.preprocessing.insert((model_id.clone(), ProverBackendKind::JoltAtlas), std::sync::Arc::new(preprocessing));
+    info!("[clawproof] Model {} preprocessed, running proof (job {})", model_id, job_id);
 
-    // Now prove
+    // Now prove — this only enqueues the proving job and returns, so the
+    // job flips to `proving` and the receipt itself (and its own
+    // `webhook_url` delivery on the terminal state) takes over from here.
     let prove_input = ProveInput {
         text: None,
         fields: None,
         raw: Some(input_raw),
     };
 
-    let result = run_single_prove(&state, model_id.clone(), prove_input, webhook_url).await?;
-
-    Ok(Json(ProveModelResponse {
-        receipt_id: result.receipt_id,
-        receipt_url: result.receipt_url,
-        model_id: result.model_id,
-        output: result.output,
-        status: result.status,
-    }))
+    match run_single_prove(
+        &state,
+        model_id.clone(),
+        prove_input,
+        webhook_url.clone(),
+        None,
+        Some(ProverBackendKind::JoltAtlas.as_str().to_string()),
+        None,
+    )
+    .await
+    {
+        Ok(result) => {
+            // Proving itself still runs in the background under
+            // `ProofQueue` — the job flips to `proving` and carries the
+            // receipt id from here; `GET /jobs/:receipt_id` or
+            // `GET /receipt/:receipt_id` report the eventual `verified`/`failed`.
+            state.model_jobs.set_proving(&job_id, &result.receipt_id);
+        }
+        Err((_, Json(body))) => {
+            fail_job(&state, &job_id, Some(&model_dir), webhook_url.as_deref(), ApiError::Internal(body.error));
+        }
+    }
 }
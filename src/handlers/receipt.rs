@@ -4,6 +4,7 @@ use axum::response::{Html, IntoResponse, Response};
 use axum::Json;
 use serde::Deserialize;
 
+use crate::locale::Locale;
 use crate::state::AppState;
 use crate::templates::receipt_page;
 
@@ -11,6 +12,8 @@ use crate::templates::receipt_page;
 pub struct ReceiptQuery {
     #[serde(default)]
     pub format: Option<String>,
+    #[serde(default)]
+    pub lang: Option<String>,
 }
 
 pub async fn get_receipt(
@@ -30,6 +33,53 @@ pub async fn get_receipt(
         }
     };
 
+    // Content negotiation: JSON if Accept: application/json, HTML otherwise
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("text/html");
+
+    let default_locale = Locale::from_code(&state.config.default_locale).unwrap_or(Locale::En);
+    let accept_language = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok());
+    let locale = Locale::resolve(query.lang.as_deref(), accept_language, default_locale);
+
+    // These sharing-control checks must run before every response path below
+    // (including the `?format=jsonld`/`?format=vc` branches) — otherwise an
+    // expired, view-limited, or passphrase-protected receipt's full hashes
+    // and prediction (and, for `?format=vc`, a signed Verifiable Credential)
+    // are still fetchable by anyone who knows the receipt ID.
+    if let Some(denial) = receipt.access_denial() {
+        return if accept.contains("application/json") {
+            (
+                StatusCode::GONE,
+                Json(serde_json::json!({"error": "Receipt no longer available", "reason": format!("{:?}", denial)})),
+            )
+                .into_response()
+        } else {
+            Html(receipt_page::render_unavailable(&denial, &locale)).into_response()
+        };
+    }
+
+    if receipt.is_passphrase_protected() {
+        return if accept.contains("application/json") {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Receipt is passphrase-protected", "hint": format!("POST the passphrase to /receipt/{}/unlock", receipt.id)})),
+            )
+                .into_response()
+        } else {
+            Html(receipt_page::render_locked(&receipt.id, &locale, false)).into_response()
+        };
+    }
+
+    // Every surviving response path below counts as a view — including
+    // ?format=jsonld/?format=vc — otherwise a receipt with max_views set
+    // could be fetched through either format an unlimited number of times
+    // without ever tripping the view-limit sharing control.
+    state.receipts.record_view(&id);
+
     // Check for ?format=jsonld
     if query.format.as_deref() == Some("jsonld") {
         let jsonld = serde_json::json!({
@@ -72,11 +122,16 @@ pub async fn get_receipt(
             .into_response();
     }
 
-    // Content negotiation: JSON if Accept: application/json, HTML otherwise
-    let accept = headers
-        .get(header::ACCEPT)
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("text/html");
+    // Check for ?format=vc — a signed W3C Verifiable Credential, unlike
+    // ?format=jsonld (unsigned) and proof_string (an unverifiable tag).
+    if query.format.as_deref() == Some("vc") {
+        let vc = crate::credential::issue(&receipt, &state.credential_key, &state.config.base_url);
+        return (
+            [(header::CONTENT_TYPE, "application/vc+ld+json")],
+            serde_json::to_string_pretty(&vc).unwrap_or_default(),
+        )
+            .into_response();
+    }
 
     if accept.contains("application/json") {
         let mut json = serde_json::to_value(&receipt).unwrap_or_default();
@@ -87,7 +142,7 @@ pub async fn get_receipt(
         }
         Json(json).into_response()
     } else {
-        let html = receipt_page::render(&receipt, &state.config.base_url);
+        let html = receipt_page::render(&receipt, &state.config.base_url, &locale, &state.config.brand);
         Html(html).into_response()
     }
 }
@@ -0,0 +1,139 @@
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use base64::Engine;
+use serde::Serialize;
+
+use crate::receipt::ReceiptStatus;
+use crate::state::AppState;
+
+/// The public-parameters the JOLT-Atlas verifier needs to re-check a proof
+/// independently. Dory uses a transparent setup, so there's no trusted-setup
+/// artifact to ship — just the scheme identifiers plus the per-model
+/// `trace_length` the preprocessing was derived from.
+#[derive(Serialize)]
+struct PublicParameters {
+    proof_system: &'static str,
+    commitment: &'static str,
+    curve: &'static str,
+    transcript: &'static str,
+    trace_length: usize,
+}
+
+/// Self-contained bundle a third party can use to re-verify a receipt's
+/// SNARK offline, without trusting this server. See field docs below for
+/// the layout an external verifier binary should expect.
+#[derive(Serialize)]
+struct VerificationBundle {
+    /// Bumped whenever a field is added, renamed, or removed.
+    manifest_version: u32,
+    receipt_id: String,
+    model_id: String,
+    model_name: String,
+    /// Keccak256 commitment to the exact ONNX model weights.
+    model_hash: String,
+    /// Keccak256 of the input tensor.
+    input_hash: String,
+    /// Keccak256 of the raw inference output.
+    output_hash: String,
+    /// Keccak256 of `proof_base64` once decoded.
+    proof_hash: String,
+    proof_size: usize,
+    public_parameters: PublicParameters,
+    /// `ark-serialize` compressed `JoltSNARK`, base64-encoded.
+    proof_base64: String,
+    /// `onnx_tracer::ProgramIO` as JSON, the public inputs/outputs the
+    /// verifier checks the proof against.
+    program_io: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attestation: Option<crate::crypto::Attestation>,
+}
+
+/// GET /receipt/:id/bundle — everything needed to re-verify a receipt's
+/// SNARK offline: the proof bytes, the public-parameters identifier, the
+/// model/input/output hashes, and the public inputs (`ProgramIO`). Only
+/// available once a receipt reaches `Verified`, since that's when the
+/// prover persists the proof bytes to `proofs_dir`.
+pub async fn receipt_bundle(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    let receipt = match state.receipts.get(&id) {
+        Some(r) => r,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Receipt not found", "hint": "Check the receipt ID"})),
+            )
+                .into_response();
+        }
+    };
+
+    if receipt.status != ReceiptStatus::Verified {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "Verification bundle not available",
+                "hint": "Only verified receipts have a downloadable bundle; check GET /receipt/{id} for status",
+            })),
+        )
+            .into_response();
+    }
+
+    let (proof_bytes, program_io_json) = match crate::proof_archive::load_proof_artifacts(
+        &state.config.proofs_dir,
+        state.proof_archive.as_deref(),
+        &id,
+        receipt.proof_hash.as_deref(),
+    )
+    .await
+    {
+        Some(artifacts) => artifacts,
+        None => {
+            tracing::error!("[clawproof] Proof artifacts unavailable for bundle {} (checked local disk and archive)", id);
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Proof artifact not found for this receipt"})),
+            )
+                .into_response();
+        }
+    };
+    let program_io: serde_json::Value = serde_json::from_str(&program_io_json).unwrap_or(serde_json::Value::Null);
+
+    let trace_length = {
+        let registry = state.registry.read().expect("model registry lock poisoned");
+        registry
+            .get(&receipt.model_id)
+            .map(|m| m.trace_length)
+            .unwrap_or(0)
+    };
+
+    let bundle = VerificationBundle {
+        manifest_version: 1,
+        receipt_id: receipt.id.clone(),
+        model_id: receipt.model_id.clone(),
+        model_name: receipt.model_name.clone(),
+        model_hash: receipt.model_hash.clone(),
+        input_hash: receipt.input_hash.clone(),
+        output_hash: receipt.output_hash.clone(),
+        proof_hash: receipt.proof_hash.clone().unwrap_or_default(),
+        proof_size: receipt.proof_size.unwrap_or(proof_bytes.len()),
+        public_parameters: PublicParameters {
+            proof_system: "JOLT (lookup-based SNARK)",
+            commitment: "Dory",
+            curve: "BN254",
+            transcript: "Keccak256",
+            trace_length,
+        },
+        proof_base64: base64::engine::general_purpose::STANDARD.encode(&proof_bytes),
+        program_io,
+        attestation: receipt.attestation.clone(),
+    };
+
+    (
+        [(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}-bundle.json\"", id))],
+        Json(bundle),
+    )
+        .into_response()
+}
@@ -0,0 +1,43 @@
+use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream};
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::state::AppState;
+
+/// GET /receipt/:id/events — SSE stream of `ProofProgress` stage
+/// transitions (queued → witness_generation → proving → verifying →
+/// done/failed) for a single receipt's in-flight proving run. The terminal
+/// `done` event also carries `proof_hash`/`proof_size`/`prove_time_ms`/
+/// `verify_time_ms`, and `failed` carries `error`, so a subscriber can
+/// render the finished receipt straight off the stream.
+///
+/// Replaces the old meta-refresh polling on the receipt page: the browser
+/// holds one connection open and updates the spinner/notice text in place
+/// instead of reloading the whole page every few seconds.
+pub async fn receipt_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.proof_progress.subscribe(&id);
+    let stream = stream::unfold(receiver, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let data = serde_json::to_string(&event).unwrap_or_default();
+                    let sse_event = Event::default().event(event.stage.as_str()).data(data);
+                    return Some((Ok(sse_event), rx));
+                }
+                // A slow subscriber missed some events — the next recv()
+                // picks up where the channel still has them buffered.
+                Err(RecvError::Lagged(_)) => continue,
+                // Receipt reached a terminal stage and the broadcaster
+                // dropped the channel — end the stream.
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
@@ -0,0 +1,51 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::prove::ErrorResponse;
+use crate::receipt::Receipt;
+use crate::state::AppState;
+
+const MAX_TIMEOUT_MS: u64 = 60_000;
+
+#[derive(Deserialize)]
+pub struct PollParams {
+    /// Status the caller last observed; the request blocks until the
+    /// receipt leaves this status or the timeout elapses.
+    pub since: String,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_timeout_ms() -> u64 {
+    30_000
+}
+
+/// GET /receipts/{id}/poll?since=<status>&timeout_ms=30000
+///
+/// Long-polls a receipt for a status transition instead of requiring the
+/// caller to re-poll GET /receipt/{id} in a loop.
+pub async fn poll_receipt(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<PollParams>,
+) -> Result<Json<Receipt>, (StatusCode, Json<ErrorResponse>)> {
+    let timeout = Duration::from_millis(params.timeout_ms.min(MAX_TIMEOUT_MS));
+
+    state
+        .receipts
+        .wait_for_status_change(&id, &params.since, timeout)
+        .await
+        .map(Json)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Receipt not found".to_string(),
+                    hint: Some("Check the receipt ID".to_string()),
+                }),
+            )
+        })
+}
@@ -0,0 +1,161 @@
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use crate::receipt::ReceiptStatus;
+use crate::state::AppState;
+
+/// A parsed `Range: bytes=start-end` request. `end` is inclusive, as in the
+/// header itself (absent end means "through the last byte").
+struct ByteRange {
+    start: u64,
+    end: Option<u64>,
+}
+
+/// Parses a single-range `Range: bytes=<start>-<end>` header. Multi-range
+/// requests and suffix ranges (`bytes=-500`) aren't supported — the only
+/// client here (the mcp-server's `download_proof` tool) always resumes from
+/// a known offset with `bytes=<start>-`.
+fn parse_byte_range(headers: &HeaderMap) -> Option<ByteRange> {
+    let value = headers.get(header::RANGE)?.to_str().ok()?;
+    let spec = value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse().ok()?)
+    };
+    Some(ByteRange { start, end })
+}
+
+/// GET /receipt/:id/proof — the raw `ark-serialize`-compressed SNARK bytes
+/// for a verified receipt, streamed from disk rather than loaded whole into
+/// memory (unlike `/receipt/:id/bundle`, which base64-encodes the same
+/// bytes into a JSON response). Honors a `Range: bytes=start-[end]` request
+/// with a `206 Partial Content` response so an interrupted download can
+/// resume instead of restarting. An absent or unparseable range falls back
+/// to a full `200` response that still streams rather than buffers; a range
+/// that starts past the last byte gets `416 Range Not Satisfiable`.
+pub async fn download_proof(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let receipt = match state.receipts.get(&id) {
+        Some(r) => r,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Receipt not found", "hint": "Check the receipt ID"})),
+            )
+                .into_response();
+        }
+    };
+
+    if receipt.status != ReceiptStatus::Verified {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "Proof artifact not available",
+                "hint": "Only verified receipts have a downloadable proof; check GET /receipt/{id} for status",
+            })),
+        )
+            .into_response();
+    }
+
+    let proof_path = state.config.proofs_dir.join(format!("{}.proof", id));
+    if tokio::fs::metadata(&proof_path).await.is_err() {
+        // Not on local disk — fall back to the object-store archive (if
+        // configured) and, on success, cache it back to `proofs_dir` so the
+        // stat below finds it.
+        if crate::proof_archive::load_proof_artifacts(
+            &state.config.proofs_dir,
+            state.proof_archive.as_deref(),
+            &id,
+            receipt.proof_hash.as_deref(),
+        )
+        .await
+        .is_none()
+        {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Proof artifact not found for this receipt"})),
+            )
+                .into_response();
+        }
+    }
+
+    let total_len = match tokio::fs::metadata(&proof_path).await {
+        Ok(m) => m.len(),
+        Err(e) => {
+            tracing::error!("[clawproof] Failed to stat proof artifact for {}: {:?}", id, e);
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Proof artifact not found for this receipt"})),
+            )
+                .into_response();
+        }
+    };
+
+    let range = parse_byte_range(&headers);
+    let (start, end, status) = match range {
+        Some(r) => {
+            let end = r.end.unwrap_or(total_len.saturating_sub(1)).min(total_len.saturating_sub(1));
+            if total_len == 0 || r.start > end {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, format!("bytes */{}", total_len))],
+                )
+                    .into_response();
+            }
+            (r.start, end, StatusCode::PARTIAL_CONTENT)
+        }
+        None => (0, total_len.saturating_sub(1), StatusCode::OK),
+    };
+    let content_len = end - start + 1;
+
+    let mut file = match tokio::fs::File::open(&proof_path).await {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!("[clawproof] Failed to open proof artifact for {}: {:?}", id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Failed to read proof artifact"})),
+            )
+                .into_response();
+        }
+    };
+    if let Err(e) = file.seek(std::io::SeekFrom::Start(start)).await {
+        tracing::error!("[clawproof] Failed to seek proof artifact for {}: {:?}", id, e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Failed to read proof artifact"})),
+        )
+            .into_response();
+    }
+
+    let body = Body::from_stream(ReaderStream::new(file.take(content_len)));
+
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(header::CONTENT_LENGTH, content_len)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.proof\"", id),
+        );
+    if status == StatusCode::PARTIAL_CONTENT {
+        response = response.header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end, total_len),
+        );
+    }
+
+    response.body(body).expect("response with known-valid headers always builds")
+}
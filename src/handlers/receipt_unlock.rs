@@ -0,0 +1,59 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::{Form, Json};
+use serde::Deserialize;
+
+use crate::crypto;
+use crate::locale::Locale;
+use crate::state::AppState;
+use crate::templates::receipt_page;
+
+#[derive(Deserialize)]
+pub struct UnlockForm {
+    pub passphrase: String,
+}
+
+/// Verify a passphrase submitted against a passphrase-protected receipt's
+/// unlock form, returning the full proof page on success or the locked page
+/// with an error message on a wrong guess.
+pub async fn unlock_receipt(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<UnlockForm>,
+) -> Response {
+    let receipt = match state.receipts.get(&id) {
+        Some(r) => r,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Receipt not found", "hint": "Check the receipt ID"})),
+            )
+                .into_response();
+        }
+    };
+
+    let default_locale = Locale::from_code(&state.config.default_locale).unwrap_or(Locale::En);
+    let accept_language = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok());
+    let locale = Locale::resolve(None, accept_language, default_locale);
+
+    if let Some(denial) = receipt.access_denial() {
+        return Html(receipt_page::render_unavailable(&denial, &locale)).into_response();
+    }
+
+    let Some(ref hash) = receipt.passphrase_hash else {
+        // Not actually protected — fall through to the normal proof page.
+        state.receipts.record_view(&id);
+        return Html(receipt_page::render(&receipt, &state.config.base_url, &locale, &state.config.brand)).into_response();
+    };
+
+    if !crypto::verify_passphrase(&form.passphrase, hash) {
+        return Html(receipt_page::render_locked(&receipt.id, &locale, true)).into_response();
+    }
+
+    state.receipts.record_view(&id);
+    Html(receipt_page::render(&receipt, &state.config.base_url, &locale, &state.config.brand)).into_response()
+}
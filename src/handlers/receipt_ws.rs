@@ -0,0 +1,140 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct SubscribeParams {
+    receipt_id: String,
+}
+
+#[derive(Deserialize)]
+struct UnsubscribeParams {
+    subscription_id: String,
+}
+
+/// GET /receipts/subscribe — WebSocket JSON-RPC pubsub for receipt status.
+///
+/// `receipt/subscribe` takes `{"receipt_id": "..."}` and returns a
+/// `subscription_id`; the connection then receives `receipt/update`
+/// notifications as that receipt transitions, carrying the same fields a
+/// webhook payload would. `receipt/unsubscribe` releases one by id.
+pub async fn receipt_ws(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState) {
+    let mut subscriptions: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
+    let (forward_tx, mut forward_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(t))) => t,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) => break,
+                };
+
+                let request: RpcRequest = match serde_json::from_str(&text) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = socket
+                            .send(Message::Text(json!({"error": format!("Parse error: {}", e)}).to_string()))
+                            .await;
+                        continue;
+                    }
+                };
+
+                match request.method.as_str() {
+                    "receipt/subscribe" => {
+                        let params: SubscribeParams = match serde_json::from_value(request.params) {
+                            Ok(p) => p,
+                            Err(_) => {
+                                let _ = socket
+                                    .send(Message::Text(
+                                        json!({"id": request.id, "error": "Missing 'receipt_id' in params"}).to_string(),
+                                    ))
+                                    .await;
+                                continue;
+                            }
+                        };
+
+                        let subscription_id = uuid::Uuid::new_v4().to_string();
+                        let mut receiver = state.receipts.subscribe(&params.receipt_id);
+                        let forward_tx = forward_tx.clone();
+                        let sub_id_for_task = subscription_id.clone();
+                        let handle = tokio::spawn(async move {
+                            while let Ok(event) = receiver.recv().await {
+                                let notification = json!({
+                                    "method": "receipt/update",
+                                    "subscription_id": sub_id_for_task,
+                                    "params": event,
+                                });
+                                if forward_tx.send(notification.to_string()).is_err() {
+                                    break;
+                                }
+                            }
+                        });
+                        subscriptions.insert(subscription_id.clone(), handle);
+
+                        let _ = socket
+                            .send(Message::Text(
+                                json!({"id": request.id, "result": {"subscription_id": subscription_id}}).to_string(),
+                            ))
+                            .await;
+                    }
+                    "receipt/unsubscribe" => {
+                        let params: UnsubscribeParams = match serde_json::from_value(request.params) {
+                            Ok(p) => p,
+                            Err(_) => {
+                                let _ = socket
+                                    .send(Message::Text(
+                                        json!({"id": request.id, "error": "Missing 'subscription_id' in params"}).to_string(),
+                                    ))
+                                    .await;
+                                continue;
+                            }
+                        };
+                        if let Some(handle) = subscriptions.remove(&params.subscription_id) {
+                            handle.abort();
+                        }
+                        let _ = socket
+                            .send(Message::Text(json!({"id": request.id, "result": {"unsubscribed": true}}).to_string()))
+                            .await;
+                    }
+                    other => {
+                        let _ = socket
+                            .send(Message::Text(json!({"id": request.id, "error": format!("Method not found: {}", other)}).to_string()))
+                            .await;
+                    }
+                }
+            }
+            Some(notification) = forward_rx.recv() => {
+                if socket.send(Message::Text(notification)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Connection dropped (or closed) — release every forwarding task so no
+    // subscription outlives the socket that asked for it.
+    for (_, handle) in subscriptions {
+        handle.abort();
+    }
+}
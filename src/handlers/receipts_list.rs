@@ -1,19 +1,37 @@
 use axum::extract::{Query, State};
-use axum::Json;
+use axum::{Extension, Json};
 use serde::Deserialize;
 
-use crate::receipt::ReceiptSummary;
+use crate::receipt::{ReceiptFilter, ReceiptPage};
 use crate::state::AppState;
 
 #[derive(Deserialize)]
 pub struct RecentParams {
     pub limit: Option<u64>,
+    /// Opaque cursor from a previous page's `next_cursor`.
+    pub cursor: Option<String>,
+    pub status: Option<String>,
+    pub model_id: Option<String>,
 }
 
+/// Callers authenticated with an API key (`api_keys::require_api_key`) only
+/// ever see their own receipts — the label is forced from the authenticated
+/// identity rather than taken as a query param, so one key can't page
+/// through another's.
 pub async fn recent(
     State(state): State<AppState>,
+    Extension(api_key): Extension<Option<crate::api_keys::ApiKeyIdentity>>,
     Query(params): Query<RecentParams>,
-) -> Json<Vec<ReceiptSummary>> {
+) -> Json<ReceiptPage> {
     let limit = params.limit.unwrap_or(10).min(50);
-    Json(state.receipts.list_recent(limit))
+    let filter = ReceiptFilter {
+        status: params.status,
+        model_id: params.model_id,
+        api_key_label: api_key.map(|k| k.label),
+    };
+    Json(
+        state
+            .receipts
+            .list_recent(limit, params.cursor.as_deref(), &filter),
+    )
 }
@@ -0,0 +1,11 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::receipt::ScrubReport;
+use crate::state::AppState;
+
+/// POST /admin/scrub — run an on-demand integrity scrub of the receipt
+/// store instead of waiting for the periodic background pass.
+pub async fn scrub(State(state): State<AppState>) -> Json<ScrubReport> {
+    Json(state.receipts.scrub().await)
+}
@@ -0,0 +1,13 @@
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+
+const SERVICE_WORKER_JS: &str = include_str!("../static/sw.js");
+
+/// GET /sw.js
+///
+/// Served at the origin root (not under a subpath) so its scope covers the
+/// whole app — a service worker can only control paths at or below where
+/// it's fetched from.
+pub async fn service_worker() -> Response {
+    ([(header::CONTENT_TYPE, "application/javascript")], SERVICE_WORKER_JS).into_response()
+}
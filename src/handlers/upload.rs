@@ -1,13 +1,19 @@
+use axum::extract::multipart::Field;
 use axum::extract::{Multipart, State};
 use axum::http::StatusCode;
 use axum::Json;
 use serde::Serialize;
+use sha3::{Digest, Keccak256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tracing::{error, info};
 
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use super::prove::ErrorResponse;
 use crate::crypto;
-use crate::models::{InputType, ModelDescriptor, ModelTomlOutput};
-use crate::state::{AppState, PreprocessingCache, Snark};
+use crate::models::{InputType, ModelDescriptor, ModelTomlOutput, UploadAttempt, UploadPolicy};
+use crate::state::{AppState, BackendPreprocessing, ProverBackendKind};
 
 use onnx_tracer::model;
 
@@ -18,40 +24,92 @@ pub struct UploadResponse {
     pub status: String,
 }
 
+/// Streams an `onnx_file` multipart field straight to a staging file on
+/// disk chunk-by-chunk, rather than buffering the whole upload into a
+/// `Vec<u8>` before anything is checked. Size is enforced and the keccak
+/// hash accumulated incrementally as each chunk arrives, so an oversized
+/// upload is aborted (and its partial staging file removed) the moment it
+/// crosses `max_upload_bytes` instead of after the whole body has already
+/// been read into memory.
+async fn stream_onnx_field(
+    field: &mut Field<'_>,
+    max_upload_bytes: usize,
+) -> Result<(PathBuf, usize, String), (StatusCode, Json<ErrorResponse>)> {
+    let staging_path = std::env::temp_dir().join(format!("clawproof-ingest-{}.onnx", uuid::Uuid::new_v4()));
+    let mut staging_file = tokio::fs::File::create(&staging_path).await.map_err(|e| {
+        error!("[clawproof] Failed to open upload staging file: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to save model".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let mut hasher = Keccak256::new();
+    let mut bytes_written: usize = 0;
+
+    loop {
+        let chunk = field.chunk().await.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: format!("Failed to read ONNX file: {}", e),
+                    hint: None,
+                }),
+            )
+        })?;
+        let Some(chunk) = chunk else { break };
+
+        bytes_written += chunk.len();
+        if bytes_written > max_upload_bytes {
+            let _ = tokio::fs::remove_file(&staging_path).await;
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ErrorResponse {
+                    error: format!("ONNX file exceeds {}-byte limit", max_upload_bytes),
+                    hint: None,
+                }),
+            ));
+        }
+
+        hasher.update(&chunk);
+        if let Err(e) = staging_file.write_all(&chunk).await {
+            let _ = tokio::fs::remove_file(&staging_path).await;
+            error!("[clawproof] Failed to write upload staging file: {:?}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to save model".to_string(),
+                    hint: None,
+                }),
+            ));
+        }
+    }
+
+    let model_hash = format!("0x{}", hex::encode(hasher.finalize()));
+    Ok((staging_path, bytes_written, model_hash))
+}
+
 pub async fn upload_model(
     State(state): State<AppState>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let mut onnx_bytes: Option<Vec<u8>> = None;
+    let mut onnx_staged: Option<(PathBuf, usize, String)> = None;
     let mut name: Option<String> = None;
     let mut description = String::new();
     let mut input_dim: usize = 0;
     let mut labels: Vec<String> = Vec::new();
     let mut trace_length: usize = 1 << 14;
+    let mut policy_b64: Option<String> = None;
+    let mut policy_signature: Option<String> = None;
 
-    while let Ok(Some(field)) = multipart.next_field().await {
+    while let Ok(Some(mut field)) = multipart.next_field().await {
         let field_name: String = field.name().unwrap_or("").to_string();
         match field_name.as_str() {
             "onnx_file" => {
-                let bytes: axum::body::Bytes = field.bytes().await.map_err(|e| {
-                    (
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse {
-                            error: format!("Failed to read ONNX file: {}", e),
-                            hint: None,
-                        }),
-                    )
-                })?;
-                if bytes.len() > 5 * 1024 * 1024 {
-                    return Err((
-                        StatusCode::PAYLOAD_TOO_LARGE,
-                        Json(ErrorResponse {
-                            error: "ONNX file exceeds 5MB limit".to_string(),
-                            hint: None,
-                        }),
-                    ));
-                }
-                onnx_bytes = Some(bytes.to_vec());
+                onnx_staged = Some(stream_onnx_field(&mut field, state.config.max_upload_bytes).await?);
             }
             "name" => {
                 name = Some(field.text().await.unwrap_or_default());
@@ -71,20 +129,62 @@ pub async fn upload_model(
                 let text: String = field.text().await.unwrap_or_default();
                 trace_length = text.parse().unwrap_or(1 << 14);
             }
+            "policy" => {
+                policy_b64 = Some(field.text().await.unwrap_or_default());
+            }
+            "x-amz-signature" => {
+                policy_signature = Some(field.text().await.unwrap_or_default());
+            }
             _ => {}
         }
     }
 
-    let onnx_bytes = onnx_bytes.ok_or_else(|| {
-        (
+    let Some((onnx_path, onnx_len, model_hash)) = onnx_staged else {
+        return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
                 error: "Missing onnx_file field".to_string(),
                 hint: Some("Upload ONNX model as multipart form field 'onnx_file'".to_string()),
             }),
-        )
-    })?;
+        ));
+    };
 
+    // Everything past this point validates other fields, not the file
+    // itself — its bytes are handed to `state.store` (or the dedup alias
+    // path skips them entirely) inside `upload_model_inner`, so the
+    // staging file is disposable either way once it returns.
+    let result = upload_model_inner(
+        state,
+        onnx_path.clone(),
+        onnx_len,
+        model_hash,
+        name,
+        description,
+        input_dim,
+        labels,
+        trace_length,
+        policy_b64,
+        policy_signature,
+    )
+    .await;
+    let _ = tokio::fs::remove_file(&onnx_path).await;
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_model_inner(
+    state: AppState,
+    onnx_path: PathBuf,
+    onnx_len: usize,
+    model_hash: String,
+    name: Option<String>,
+    description: String,
+    input_dim: usize,
+    labels: Vec<String>,
+    trace_length: usize,
+    policy_b64: Option<String>,
+    policy_signature: Option<String>,
+) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
     let name = name.ok_or_else(|| {
         (
             StatusCode::BAD_REQUEST,
@@ -115,6 +215,41 @@ pub async fn upload_model(
         ));
     }
 
+    // A `policy` field opts into S3 PostObject-style scoped, time-limited
+    // upload grants — checked in full (signature, expiration, every
+    // condition) before anything is written to the model store. The ONNX
+    // bytes are already on disk in a temp staging file by this point
+    // (stream_onnx_field ran before upload_model_inner was called), but
+    // staging is discarded on rejection and never reaches `state.store`.
+    // No `policy` field submitted means the old unauthenticated path,
+    // unchanged.
+    if let Some(policy_b64) = &policy_b64 {
+        let forbidden = |error: String, hint: Option<String>| (StatusCode::FORBIDDEN, Json(ErrorResponse { error, hint }));
+
+        let signature = policy_signature.as_deref().ok_or_else(|| {
+            forbidden(
+                "Missing x-amz-signature field".to_string(),
+                Some("A signed policy must be accompanied by its x-amz-signature".to_string()),
+            )
+        })?;
+
+        let secret = state.config.upload_policy_secret.as_deref().ok_or_else(|| {
+            forbidden("Upload policies are not accepted by this server".to_string(), None)
+        })?;
+
+        let expected_signature = crypto::sign_upload_policy(secret, policy_b64);
+        if !crypto::constant_time_eq(&expected_signature, signature) {
+            return Err(forbidden("Upload policy signature is invalid".to_string(), None));
+        }
+
+        let policy = UploadPolicy::decode(policy_b64)
+            .map_err(|e| forbidden(format!("Invalid upload policy: {}", e), None))?;
+
+        policy
+            .check(&UploadAttempt { content_length: onnx_len as u64, name: &name })
+            .map_err(|msg| forbidden(msg, None))?;
+    }
+
     // Generate model ID
     let model_id = name
         .to_lowercase()
@@ -127,10 +262,30 @@ pub async fn upload_model(
         &uuid::Uuid::new_v4().to_string()[..8]
     );
 
-    // Save ONNX file
-    let model_dir = state.config.uploaded_models_dir.join(&model_id);
-    std::fs::create_dir_all(&model_dir).map_err(|e| {
-        error!("[clawproof] Failed to create model dir: {:?}", e);
+    // An identical ONNX model may already be uploaded under a different
+    // model_id. If so, skip the multi-second `Snark::prover_preprocess` run
+    // entirely and alias this model_id onto the existing preprocessing.
+    if let Some((canonical_id, preprocessing)) = find_existing_preprocessed(&state, &model_hash) {
+        return register_aliased_model(
+            state,
+            &canonical_id,
+            preprocessing,
+            model_id,
+            name,
+            description,
+            input_dim,
+            labels,
+            trace_length,
+            model_hash,
+        )
+        .await;
+    }
+
+    // Hand the already size-checked staging file to the configured model
+    // store (local disk or S3) in one bounded read, capped at
+    // `max_upload_bytes` by `stream_onnx_field` above.
+    let onnx_bytes = tokio::fs::read(&onnx_path).await.map_err(|e| {
+        error!("[clawproof] Failed to read upload staging file: {:?}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -139,9 +294,7 @@ pub async fn upload_model(
             }),
         )
     })?;
-
-    let onnx_path = model_dir.join("network.onnx");
-    std::fs::write(&onnx_path, &onnx_bytes).map_err(|e| {
+    state.store.put(&model_id, "network.onnx", &onnx_bytes).await.map_err(|e| {
         error!("[clawproof] Failed to write ONNX file: {:?}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -152,9 +305,172 @@ pub async fn upload_model(
         )
     })?;
 
+    finalize_uploaded_model(state, model_id, name, description, input_dim, labels, trace_length, model_hash).await
+}
+
+/// Looks up `model_hash` in `AppState::model_hash_index` and, if the
+/// canonical model_id it points at still has live preprocessing (it always
+/// should, since the index is only populated once preprocessing succeeds),
+/// returns both so the caller can alias onto it instead of recomputing.
+fn find_existing_preprocessed(state: &AppState, model_hash: &str) -> Option<(String, Arc<BackendPreprocessing>)> {
+    let canonical_id = state.model_hash_index.get(model_hash)?.clone();
+    let preprocessing = state
+        .preprocessing
+        .get(&(canonical_id.clone(), ProverBackendKind::JoltAtlas))?
+        .clone();
+    Some((canonical_id, preprocessing))
+}
+
+/// Registers a new model_id as a dedup alias of `canonical_id`: the ONNX
+/// blob is aliased (hard-linked or S3-copied, never re-uploaded) rather than
+/// rewritten, and the already-computed preprocessing is shared via a cheap
+/// `Arc` clone, so the model is `Ready` immediately with no background work.
+#[allow(clippy::too_many_arguments)]
+async fn register_aliased_model(
+    state: AppState,
+    canonical_id: &str,
+    preprocessing: Arc<BackendPreprocessing>,
+    model_id: String,
+    name: String,
+    description: String,
+    input_dim: usize,
+    labels: Vec<String>,
+    trace_length: usize,
+    model_hash: String,
+) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    // `preprocessing` was built from the canonical upload's own trace_length
+    // (ProverBackend::preprocess bakes it into the SNARK prover/verifier), so
+    // a caller-supplied trace_length/input_dim that disagrees with it would
+    // describe proofs the aliased model doesn't actually produce. Reject the
+    // mismatch instead of silently advertising the wrong shape.
+    {
+        let registry = state.registry.read().expect("model registry lock poisoned");
+        let canonical = registry.get(canonical_id).ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to save model".to_string(),
+                    hint: None,
+                }),
+            )
+        })?;
+        if canonical.trace_length != trace_length || canonical.input_dim != input_dim {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(ErrorResponse {
+                    error: format!(
+                        "Model hash matches an existing upload ({}) with trace_length={} input_dim={}, which differs from the submitted trace_length={} input_dim={}",
+                        canonical_id, canonical.trace_length, canonical.input_dim, trace_length, input_dim
+                    ),
+                    hint: Some("Upload with matching trace_length/input_dim, or use a different model_hash".to_string()),
+                }),
+            ));
+        }
+    }
+
+    state.store.alias(canonical_id, &model_id, "network.onnx").await.map_err(|e| {
+        error!("[clawproof] Failed to alias ONNX file for {} onto {}: {:?}", model_id, canonical_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to save model".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let toml_output = ModelTomlOutput {
+        id: model_id.clone(),
+        name: name.clone(),
+        description: description.clone(),
+        input_type: "raw".to_string(),
+        input_dim,
+        input_shape: vec![1, input_dim],
+        labels: labels.clone(),
+        trace_length,
+    };
+    if let Ok(content) = toml::to_string_pretty(&toml_output) {
+        let _ = state.store.put(&model_id, "model.toml", content.as_bytes()).await;
+    }
+
+    let descriptor = ModelDescriptor {
+        id: model_id.clone(),
+        name: name.clone(),
+        description,
+        input_type: InputType::Raw,
+        input_dim,
+        input_shape: vec![1, input_dim],
+        labels,
+        trace_length,
+        status: crate::models::ModelStatus::Ready,
+        quantization: crate::models::QuantizationConfig::default(),
+        unsupported_reason: None,
+        fields: None,
+        model_hash: Some(model_hash.clone()),
+    };
+
+    {
+        let mut registry = state.registry.write().expect("model registry lock poisoned");
+        registry.register(descriptor);
+    }
+
+    state.preprocessing.insert((model_id.clone(), ProverBackendKind::JoltAtlas), preprocessing);
+    if let Err(e) = state.preprocess_queue.record_done(&model_id, &model_hash, ProverBackendKind::JoltAtlas, trace_length) {
+        error!("[clawproof] Failed to record preprocess status for aliased model {}: {:?}", model_id, e);
+    }
+    info!("[clawproof] Uploaded model {} deduplicated onto existing preprocessing for {}", model_id, canonical_id);
+
+    Ok(Json(UploadResponse {
+        model_id,
+        name,
+        status: "ready".to_string(),
+    }))
+}
+
+/// Validates a fully-written ONNX file, registers its model descriptor, and
+/// kicks off background preprocessing. Shared by the single-shot
+/// `upload_model` handler above (which writes the whole file in one go) and
+/// `upload_resumable::complete_upload` (which assembles the file from
+/// chunked parts first) — both hand off to this once the bytes have been
+/// committed to `state.store`, so validation/registration logic lives in
+/// exactly one place.
+///
+/// Reads and writes go through `state.store` rather than the filesystem
+/// directly, so a model uploaded to one instance can still be validated,
+/// preprocessed, and proved against on another (see `model_store::ModelStore`).
+#[allow(clippy::too_many_arguments)]
+pub async fn finalize_uploaded_model(
+    state: AppState,
+    model_id: String,
+    name: String,
+    description: String,
+    input_dim: usize,
+    labels: Vec<String>,
+    trace_length: usize,
+    model_hash: String,
+) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    // Materialize the ONNX bytes to a local path — `onnx_tracer::model` and
+    // `ProverBackend::preprocess` both need a real `&Path` to read, which an
+    // object-storage backend can't hand out directly.
+    let onnx_path = state.store.local_path(&model_id, "network.onnx").await.map_err(|e| {
+        error!("[clawproof] Failed to materialize uploaded model {}: {:?}", model_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to save model".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
     // Quick magic-byte check before attempting to load
-    if onnx_bytes.len() < 2 || onnx_bytes[0] != 0x08 {
-        let _ = std::fs::remove_dir_all(&model_dir);
+    let mut header = [0u8; 2];
+    let valid_header = match tokio::fs::File::open(&onnx_path).await {
+        Ok(mut f) => matches!(f.read(&mut header).await, Ok(n) if n >= 2 && header[0] == 0x08),
+        Err(_) => false,
+    };
+    if !valid_header {
+        state.store.delete(&model_id).await;
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -176,7 +492,7 @@ pub async fn upload_model(
     match validation_result {
         Ok(Ok(())) => {}
         _ => {
-            let _ = std::fs::remove_dir_all(&model_dir);
+            state.store.delete(&model_id).await;
             return Err((
                 StatusCode::BAD_REQUEST,
                 Json(ErrorResponse {
@@ -198,11 +514,9 @@ pub async fn upload_model(
         labels: labels.clone(),
         trace_length,
     };
-    let _ = toml::to_string_pretty(&toml_output)
-        .map(|content| std::fs::write(model_dir.join("model.toml"), content));
-
-    // Compute model hash from the ONNX bytes we already have in memory
-    let model_hash = Some(crypto::keccak256(&onnx_bytes));
+    if let Ok(content) = toml::to_string_pretty(&toml_output) {
+        let _ = state.store.put(&model_id, "model.toml", content.as_bytes()).await;
+    }
 
     // Register in model registry
     let descriptor = ModelDescriptor {
@@ -214,8 +528,11 @@ pub async fn upload_model(
         input_shape: vec![1, input_dim],
         labels,
         trace_length,
+        status: crate::models::ModelStatus::Ready,
+        quantization: crate::models::QuantizationConfig::default(),
+        unsupported_reason: None,
         fields: None,
-        model_hash,
+        model_hash: Some(model_hash.clone()),
     };
 
     {
@@ -223,40 +540,27 @@ pub async fn upload_model(
         registry.register(descriptor);
     }
 
-    // Spawn background preprocessing
-    let bg_state = state.clone();
-    let bg_model_id = model_id.clone();
-    let bg_model_path = onnx_path;
-    tokio::spawn(async move {
-        info!("[clawproof] Starting preprocessing for uploaded model {}", bg_model_id);
-        let result = tokio::task::spawn_blocking(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                let model_fn = || model(&bg_model_path);
-                Snark::prover_preprocess(model_fn, trace_length)
-            }))
-        })
-        .await;
-
-        match result {
-            Ok(Ok(preprocessing)) => {
-                let verifier_preprocessing = (&preprocessing).into();
-                bg_state.preprocessing.insert(
-                    bg_model_id.clone(),
-                    PreprocessingCache {
-                        prover: preprocessing,
-                        verifier: verifier_preprocessing,
-                    },
-                );
-                info!("[clawproof] Uploaded model {} preprocessed successfully", bg_model_id);
-            }
-            Ok(Err(_)) => {
-                error!("[clawproof] Preprocessing panicked for uploaded model {}", bg_model_id);
-            }
-            Err(e) => {
-                error!("[clawproof] Failed to preprocess uploaded model {}: {:?}", bg_model_id, e);
-            }
-        }
-    });
+    // Enqueue background preprocessing — `preprocess_queue::spawn_dispatcher`'s
+    // bounded worker pool drains it, retrying on failure and surviving a
+    // restart, unlike the bare `tokio::spawn` this used to fire directly.
+    // The dispatcher re-resolves `onnx_path` itself via `state.store.local_path`
+    // when it actually claims the job, so nothing further is needed here.
+    if let Err(e) = state.preprocess_queue.enqueue(
+        &model_id,
+        &model_hash,
+        ProverBackendKind::JoltAtlas,
+        trace_length,
+        state.config.preprocess_max_attempts,
+    ) {
+        error!("[clawproof] Failed to enqueue preprocessing for uploaded model {}: {:?}", model_id, e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to queue model for preprocessing".to_string(),
+                hint: None,
+            }),
+        ));
+    }
 
     Ok(Json(UploadResponse {
         model_id,
@@ -0,0 +1,428 @@
+//! Resumable, chunked counterpart to `handlers::upload` for ONNX models
+//! larger than the single-shot 5MB cap. A client calls `begin_upload` once
+//! to register metadata and get an `upload_id`, then streams the file as a
+//! sequence of `upload_part` calls — retrying any part that didn't land,
+//! since the server always reports the next part number it's still
+//! waiting for — then calls `complete_upload` to finalize and kick off
+//! preprocessing, reusing the same validation/registration path as the
+//! single-shot upload.
+
+use axum::extract::{Multipart, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::{error, warn};
+
+use super::prove::ErrorResponse;
+use super::upload::{finalize_uploaded_model, UploadResponse};
+use crate::crypto;
+use crate::state::{AppState, UploadSession};
+
+fn default_trace_length() -> usize {
+    1 << 14
+}
+
+#[derive(Deserialize)]
+pub struct BeginUploadRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub labels: Vec<String>,
+    pub input_dim: usize,
+    #[serde(default = "default_trace_length")]
+    pub trace_length: usize,
+    /// Total size of the ONNX file in bytes, so `complete_upload` can tell
+    /// a finished transfer from a still-in-progress one.
+    pub total_size: u64,
+}
+
+#[derive(Serialize)]
+pub struct BeginUploadResponse {
+    pub upload_id: String,
+    pub next_part: u32,
+}
+
+pub async fn begin_upload(
+    State(state): State<AppState>,
+    Json(req): Json<BeginUploadRequest>,
+) -> Result<Json<BeginUploadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if req.input_dim == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "input_dim must be > 0".to_string(),
+                hint: None,
+            }),
+        ));
+    }
+
+    if req.labels.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "labels must be a non-empty array".to_string(),
+                hint: Some("Provide a non-empty labels array, e.g. [\"class_a\",\"class_b\"]".to_string()),
+            }),
+        ));
+    }
+
+    if req.total_size == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "total_size must be > 0".to_string(),
+                hint: None,
+            }),
+        ));
+    }
+
+    if req.total_size > state.config.max_resumable_upload_bytes {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(ErrorResponse {
+                error: format!("total_size exceeds {}-byte limit", state.config.max_resumable_upload_bytes),
+                hint: None,
+            }),
+        ));
+    }
+
+    // Each session holds an on-disk staging file until complete_upload or
+    // the reaper cleans it up — cap how many can be open at once so a flood
+    // of begin_upload calls that never finish can't exhaust disk.
+    if state.pending_uploads.len() >= state.config.max_pending_uploads {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error: "Too many uploads in progress".to_string(),
+                hint: Some("Complete or abandon an existing upload before starting another".to_string()),
+            }),
+        ));
+    }
+
+    let model_id = req
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    let model_id = format!("{}_{}", model_id, &uuid::Uuid::new_v4().to_string()[..8]);
+
+    let model_dir = state.config.uploaded_models_dir.join(&model_id);
+    std::fs::create_dir_all(&model_dir).map_err(|e| {
+        error!("[clawproof] Failed to create model dir: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to start upload".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let onnx_path = model_dir.join("network.onnx");
+    // Create an empty file up front so `upload_part` can just append to it.
+    std::fs::write(&onnx_path, []).map_err(|e| {
+        error!("[clawproof] Failed to create upload file: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to start upload".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let upload_id = uuid::Uuid::new_v4().to_string();
+    state.pending_uploads.insert(
+        upload_id.clone(),
+        UploadSession {
+            model_id,
+            model_dir,
+            onnx_path,
+            name: req.name,
+            description: req.description,
+            input_dim: req.input_dim,
+            labels: req.labels,
+            trace_length: req.trace_length,
+            total_size: req.total_size,
+            bytes_received: 0,
+            next_part: 1,
+            created_at: chrono::Utc::now(),
+        },
+    );
+
+    Ok(Json(BeginUploadResponse {
+        upload_id,
+        next_part: 1,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct UploadPartResponse {
+    pub upload_id: String,
+    pub bytes_received: u64,
+    pub total_size: u64,
+    pub next_part: u32,
+}
+
+pub async fn upload_part(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<UploadPartResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let mut upload_id: Option<String> = None;
+    let mut part_number: Option<u32> = None;
+    let mut chunk: Option<axum::body::Bytes> = None;
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        match field.name().unwrap_or("") {
+            "upload_id" => upload_id = Some(field.text().await.unwrap_or_default()),
+            "part_number" => {
+                let text = field.text().await.unwrap_or_default();
+                part_number = text.parse().ok();
+            }
+            "chunk" => {
+                chunk = Some(field.bytes().await.map_err(|e| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse {
+                            error: format!("Failed to read chunk: {}", e),
+                            hint: None,
+                        }),
+                    )
+                })?);
+            }
+            _ => {}
+        }
+    }
+
+    let upload_id = upload_id.ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Missing upload_id field".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let part_number = part_number.ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Missing or invalid part_number field".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let chunk = chunk.ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Missing chunk field".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    let mut session = state.pending_uploads.get_mut(&upload_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Unknown upload_id".to_string(),
+                hint: Some("Call begin_upload first".to_string()),
+            }),
+        )
+    })?;
+
+    if part_number < session.next_part {
+        // Already-received part — most likely the client retrying after a
+        // dropped response. Report current progress instead of appending
+        // the bytes a second time.
+        return Ok(Json(UploadPartResponse {
+            upload_id,
+            bytes_received: session.bytes_received,
+            total_size: session.total_size,
+            next_part: session.next_part,
+        }));
+    }
+
+    if part_number > session.next_part {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: format!(
+                    "Out-of-order part: expected {}, got {}",
+                    session.next_part, part_number
+                ),
+                hint: Some("Resume from the next_part reported by the last successful upload_part call".to_string()),
+            }),
+        ));
+    }
+
+    // total_size was already capped at max_resumable_upload_bytes in
+    // begin_upload, but nothing stops a client from declaring a small
+    // total_size and then sending parts past it — check the running total
+    // against it too.
+    if session.bytes_received + chunk.len() as u64 > session.total_size {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(ErrorResponse {
+                error: format!(
+                    "Part would exceed the declared total_size of {} bytes",
+                    session.total_size
+                ),
+                hint: None,
+            }),
+        ));
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .append(true)
+        .open(&session.onnx_path)
+        .await
+        .map_err(|e| {
+            error!(
+                "[clawproof] Failed to open upload file for part {}: {:?}",
+                part_number, e
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to write part".to_string(),
+                    hint: None,
+                }),
+            )
+        })?;
+
+    file.write_all(&chunk).await.map_err(|e| {
+        error!("[clawproof] Failed to write part {}: {:?}", part_number, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to write part".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    session.bytes_received += chunk.len() as u64;
+    session.next_part += 1;
+
+    Ok(Json(UploadPartResponse {
+        upload_id,
+        bytes_received: session.bytes_received,
+        total_size: session.total_size,
+        next_part: session.next_part,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct CompleteUploadRequest {
+    pub upload_id: String,
+}
+
+pub async fn complete_upload(
+    State(state): State<AppState>,
+    Json(req): Json<CompleteUploadRequest>,
+) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (_, session) = state.pending_uploads.remove(&req.upload_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Unknown upload_id".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+
+    if session.bytes_received != session.total_size {
+        let bytes_received = session.bytes_received;
+        let total_size = session.total_size;
+        // Put it back so the client can keep resuming instead of having to
+        // start over after a premature complete_upload call.
+        state.pending_uploads.insert(req.upload_id, session);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!(
+                    "Upload incomplete: received {} of {} bytes",
+                    bytes_received, total_size
+                ),
+                hint: Some("Call upload_part for the remaining bytes before completing".to_string()),
+            }),
+        ));
+    }
+
+    // Parts are assembled in a local staging file (random-access appends
+    // don't map onto an object store), then the finished bytes are handed
+    // to `state.store` so everything downstream of this point — validation,
+    // registration, preprocessing — goes through the same path as the
+    // single-shot `upload_model` handler.
+    let onnx_bytes = tokio::fs::read(&session.onnx_path).await.map_err(|e| {
+        error!("[clawproof] Failed to read assembled upload {}: {:?}", session.model_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to save model".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+    state.store.put(&session.model_id, "network.onnx", &onnx_bytes).await.map_err(|e| {
+        error!("[clawproof] Failed to commit assembled upload {}: {:?}", session.model_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to save model".to_string(),
+                hint: None,
+            }),
+        )
+    })?;
+    let _ = tokio::fs::remove_dir_all(&session.model_dir).await;
+
+    let model_hash = crypto::keccak256(&onnx_bytes);
+    finalize_uploaded_model(
+        state,
+        session.model_id,
+        session.name,
+        session.description,
+        session.input_dim,
+        session.labels,
+        session.trace_length,
+        model_hash,
+    )
+    .await
+}
+
+/// Spawn the background task that sweeps `pending_uploads` on `interval`,
+/// deleting the staging directory and forgetting any session older than
+/// `ttl` — a client that calls `begin_upload` and never finishes (or
+/// never calls `complete_upload`) would otherwise leak a directory and a
+/// `pending_uploads` slot forever. Call once at startup, same as
+/// `ReceiptStore::spawn_scrub_task`.
+pub fn spawn_pending_upload_reaper(state: AppState, interval: std::time::Duration, ttl: chrono::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now();
+            let stale: Vec<String> = state
+                .pending_uploads
+                .iter()
+                .filter(|entry| now - entry.value().created_at > ttl)
+                .map(|entry| entry.key().clone())
+                .collect();
+            for upload_id in stale {
+                if let Some((_, session)) = state.pending_uploads.remove(&upload_id) {
+                    let _ = tokio::fs::remove_dir_all(&session.model_dir).await;
+                    warn!(
+                        "[clawproof] Reaped abandoned upload {} ({} of {} bytes received)",
+                        upload_id, session.bytes_received, session.total_size
+                    );
+                }
+            }
+        }
+    });
+}
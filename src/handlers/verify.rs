@@ -1,9 +1,8 @@
 use axum::extract::State;
-use axum::http::StatusCode;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
-use super::prove::ErrorResponse;
+use crate::api_error::ApiError;
 use crate::receipt::ReceiptStatus;
 use crate::state::AppState;
 
@@ -22,16 +21,11 @@ pub struct VerifyResponse {
 pub async fn verify(
     State(state): State<AppState>,
     Json(request): Json<VerifyRequest>,
-) -> Result<Json<VerifyResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let receipt = state.receipts.get(&request.receipt_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Receipt not found".into(),
-                hint: Some("Check the receipt_id and try GET /receipt/{id}".to_string()),
-            }),
-        )
-    })?;
+) -> Result<Json<VerifyResponse>, ApiError> {
+    let receipt = state
+        .receipts
+        .get(&request.receipt_id)
+        .ok_or(ApiError::NotFound("Receipt"))?;
 
     match receipt.status {
         ReceiptStatus::Verified => Ok(Json(VerifyResponse {
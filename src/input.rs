@@ -3,11 +3,16 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::LazyLock;
 
+use crate::models::QuantizationConfig;
+
 static TOKENIZER: LazyLock<regex::Regex> =
     LazyLock::new(|| regex::Regex::new(r"\w+|[^\w\s]").unwrap());
 
-/// TF-IDF vocab: word -> (index, idf_scaled)
-pub type TfIdfVocab = HashMap<String, (usize, i32)>;
+/// TF-IDF vocab: word -> (index, idf). `idf` is kept as the raw float from
+/// `vocab.json` — fixed-point scaling is applied once, by the caller's
+/// `QuantizationConfig`, when the accumulated vector is built rather than
+/// per vocab entry at load time.
+pub type TfIdfVocab = HashMap<String, (usize, f64)>;
 
 /// One-hot vocab: feature_key -> index
 pub type OneHotVocab = HashMap<String, usize>;
@@ -23,7 +28,7 @@ pub fn load_tfidf_vocab(path: &Path) -> anyhow::Result<TfIdfVocab> {
                 data.get("index").and_then(|v| v.as_u64()),
                 data.get("idf").and_then(|v| v.as_f64()),
             ) {
-                vocab.insert(word, (index as usize, (idf * 1000.0) as i32));
+                vocab.insert(word, (index as usize, idf));
             }
         }
     }
@@ -47,8 +52,12 @@ pub fn load_onehot_vocab(path: &Path) -> anyhow::Result<OneHotVocab> {
     Ok(vocab)
 }
 
-pub fn build_tfidf_vector(text: &str, vocab: &TfIdfVocab, dim: usize) -> Vec<i32> {
-    let mut vec = vec![0i32; dim];
+/// Accumulates raw IDF weights per token, then quantizes each element once
+/// via `quant` — e.g. a model exported expecting the old hardcoded `* 1000`
+/// fixed-point convention now needs `quantization.scale = 1000` in its
+/// `model.toml` to match.
+pub fn build_tfidf_vector(text: &str, vocab: &TfIdfVocab, dim: usize, quant: &QuantizationConfig) -> Vec<i32> {
+    let mut vec = vec![0f64; dim];
 
     for cap in TOKENIZER.captures_iter(text) {
         let token = cap.get(0).unwrap().as_str().to_lowercase();
@@ -59,7 +68,7 @@ pub fn build_tfidf_vector(text: &str, vocab: &TfIdfVocab, dim: usize) -> Vec<i32
         }
     }
 
-    vec
+    vec.into_iter().map(|v| quant.apply(v)).collect()
 }
 
 pub fn build_onehot_vector(
@@ -67,19 +76,20 @@ pub fn build_onehot_vector(
     field_names: &[&str],
     vocab: &OneHotVocab,
     dim: usize,
+    quant: &QuantizationConfig,
 ) -> Vec<i32> {
-    let mut vec = vec![0i32; dim];
+    let mut vec = vec![0f64; dim];
 
     for &field_name in field_names {
         if let Some(&value) = fields.get(field_name) {
             let feature_key = format!("{}_{}", field_name, value);
             if let Some(&index) = vocab.get(&feature_key) {
                 if index < dim {
-                    vec[index] = 1;
+                    vec[index] = 1.0;
                 }
             }
         }
     }
 
-    vec
+    vec.into_iter().map(|v| quant.apply(v)).collect()
 }
@@ -0,0 +1,282 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Supported receipt-page locales. Add a new variant plus a `messages()`
+/// arm (and, if needed, locale-specific formatting below) to support
+/// another language — no template changes required.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    De,
+}
+
+/// Every locale this app ships a catalog for, in the order a language
+/// selector should list them.
+pub const ALL_LOCALES: &[Locale] = &[Locale::En, Locale::De];
+
+impl Locale {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            _ => None,
+        }
+    }
+
+    /// The `?lang=` query value / `Accept-Language` primary tag for this locale.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// Human-readable name for a language selector, in that language itself.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    /// Pick the first `Accept-Language` entry (ignoring `q=` weights, which
+    /// this app doesn't need to rank beyond "first listed wins") this repo
+    /// recognizes, e.g. "de-DE,de;q=0.9,en;q=0.8" -> `Locale::De`.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        header.split(',').find_map(|entry| {
+            let tag = entry.split(';').next().unwrap_or("").trim();
+            let primary = tag.split('-').next().unwrap_or("");
+            Locale::from_code(primary)
+        })
+    }
+
+    /// Resolve the locale to render a receipt page in: an explicit
+    /// `?lang=` query param wins, then `Accept-Language`, then `fallback`
+    /// (the operator-configured default).
+    pub fn resolve(lang_param: Option<&str>, accept_language: Option<&str>, fallback: Locale) -> Self {
+        lang_param
+            .and_then(Locale::from_code)
+            .or_else(|| accept_language.and_then(Locale::from_accept_language))
+            .unwrap_or(fallback)
+    }
+
+    /// Locale-appropriate confidence percentage, e.g. "72.3%" vs "72,3 %".
+    pub fn format_confidence(&self, fraction: f64) -> String {
+        let pct = fraction * 100.0;
+        match self {
+            Locale::En => format!("{:.1}%", pct),
+            Locale::De => format!("{:.1} %", pct).replace('.', ","),
+        }
+    }
+
+    /// Locale-appropriate receipt timestamp formatting.
+    pub fn format_datetime(&self, dt: &DateTime<Utc>) -> String {
+        match self {
+            Locale::En => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            Locale::De => dt.format("%d.%m.%Y %H:%M:%S UTC").to_string(),
+        }
+    }
+}
+
+/// Message-ID -> translated-string lookup for the receipt and playground
+/// page templates. Mirrors the locale-file approach of keeping every
+/// user-facing string in one place per language instead of scattering
+/// literals through the HTML.
+#[derive(Clone, Copy, Serialize)]
+pub struct Messages {
+    pub status_proving: &'static str,
+    pub status_verified: &'static str,
+    pub status_failed: &'static str,
+    pub confidence_suffix: &'static str,
+    pub card_model: &'static str,
+    pub card_hashes: &'static str,
+    pub card_proof: &'static str,
+    pub card_error: &'static str,
+    pub card_metadata: &'static str,
+    pub row_name: &'static str,
+    pub row_id: &'static str,
+    pub row_hash: &'static str,
+    pub row_input: &'static str,
+    pub row_output: &'static str,
+    pub row_proof_hash: &'static str,
+    pub row_size: &'static str,
+    pub row_prove_time: &'static str,
+    pub row_verify_time: &'static str,
+    pub row_receipt_id: &'static str,
+    pub row_created: &'static str,
+    pub row_completed: &'static str,
+    pub unit_bytes: &'static str,
+    pub unit_ms: &'static str,
+    pub proving_notice: &'static str,
+    pub unknown_error: &'static str,
+    pub share_section_header: &'static str,
+    pub share_on_x: &'static str,
+    pub copy_verify_me: &'static str,
+    pub copy_proof_string: &'static str,
+    pub download_bundle: &'static str,
+    pub copy_button: &'static str,
+    pub proof_id_label: &'static str,
+    pub toast_link_copied: &'static str,
+    pub toast_proof_string_copied: &'static str,
+    pub toast_verify_me_copied: &'static str,
+    pub footer_open_source: &'static str,
+    pub unavailable_title: &'static str,
+    pub unavailable_expired: &'static str,
+    pub unavailable_view_limit: &'static str,
+    pub locked_title: &'static str,
+    pub locked_prompt: &'static str,
+    pub locked_passphrase_label: &'static str,
+    pub locked_unlock_button: &'static str,
+    pub locked_incorrect: &'static str,
+
+    // Playground page (`templates/playground.html`).
+    pub pg_tagline: &'static str,
+    pub pg_model_label: &'static str,
+    pub pg_parameters_label: &'static str,
+    pub pg_input_text_label: &'static str,
+    /// Contains a `{dim}` placeholder, filled in client-side.
+    pub pg_raw_input_label: &'static str,
+    pub pg_generate_button: &'static str,
+    pub pg_generating_button: &'static str,
+    pub pg_please_enter_text: &'static str,
+    pub pg_invalid_raw_json: &'static str,
+    /// Contains `{field}`/`{min}`/`{max}` placeholders, filled in client-side.
+    pub pg_field_range_error: &'static str,
+    pub pg_connection_lost: &'static str,
+    pub pg_status_offline: &'static str,
+    pub pg_for_agents: &'static str,
+    pub pg_for_compliance: &'static str,
+    pub pg_agent_tool_definition: &'static str,
+    pub pg_generated_for_model: &'static str,
+}
+
+pub fn messages(locale: Locale) -> Messages {
+    match locale {
+        Locale::En => Messages {
+            status_proving: "Proving",
+            status_verified: "Verified",
+            status_failed: "Failed",
+            confidence_suffix: "confidence",
+            card_model: "Model",
+            card_hashes: "Hashes",
+            card_proof: "Proof",
+            card_error: "Error",
+            card_metadata: "Metadata",
+            row_name: "Name",
+            row_id: "ID",
+            row_hash: "Hash",
+            row_input: "Input",
+            row_output: "Output",
+            row_proof_hash: "Proof hash",
+            row_size: "Size",
+            row_prove_time: "Prove time",
+            row_verify_time: "Verify time",
+            row_receipt_id: "Receipt ID",
+            row_created: "Created",
+            row_completed: "Completed",
+            unit_bytes: "bytes",
+            unit_ms: "ms",
+            proving_notice: "Generating SNARK proof. This page refreshes automatically.",
+            unknown_error: "Unknown error",
+            share_section_header: "Share this proof",
+            share_on_x: "Share on X",
+            copy_verify_me: "Copy \"Verify me\"",
+            copy_proof_string: "Copy proof string",
+            download_bundle: "Download verification bundle",
+            copy_button: "Copy",
+            proof_id_label: "Proof ID",
+            toast_link_copied: "Link copied",
+            toast_proof_string_copied: "Proof string copied",
+            toast_verify_me_copied: "\"Verify me\" message copied",
+            footer_open_source: "Open source (MIT)",
+            unavailable_title: "No longer available",
+            unavailable_expired: "This receipt's sharing link has expired.",
+            unavailable_view_limit: "This receipt has reached its view limit.",
+            locked_title: "Passphrase required",
+            locked_prompt: "This receipt is passphrase-protected. Enter the passphrase to view it.",
+            locked_passphrase_label: "Passphrase",
+            locked_unlock_button: "Unlock",
+            locked_incorrect: "Incorrect passphrase. Try again.",
+
+            pg_tagline: "Cryptographic proof receipts for AI-driven transaction decisions. Built for agentic commerce and AI security.",
+            pg_model_label: "Model",
+            pg_parameters_label: "Parameters",
+            pg_input_text_label: "Input text",
+            pg_raw_input_label: "Raw input vector (JSON array of {dim} integers)",
+            pg_generate_button: "Generate proof",
+            pg_generating_button: "Generating...",
+            pg_please_enter_text: "Please enter some text.",
+            pg_invalid_raw_json: "Invalid JSON array for raw input.",
+            pg_field_range_error: "{field} must be between {min} and {max}.",
+            pg_connection_lost: "Connection lost",
+            pg_status_offline: "Offline — showing cached status",
+            pg_for_agents: "For Agents",
+            pg_for_compliance: "For Compliance",
+            pg_agent_tool_definition: "Agent tool definition",
+            pg_generated_for_model: "Generated for the model currently selected above",
+        },
+        Locale::De => Messages {
+            status_proving: "Wird bewiesen",
+            status_verified: "Verifiziert",
+            status_failed: "Fehlgeschlagen",
+            confidence_suffix: "Konfidenz",
+            card_model: "Modell",
+            card_hashes: "Hashes",
+            card_proof: "Beweis",
+            card_error: "Fehler",
+            card_metadata: "Metadaten",
+            row_name: "Name",
+            row_id: "ID",
+            row_hash: "Hash",
+            row_input: "Eingabe",
+            row_output: "Ausgabe",
+            row_proof_hash: "Beweis-Hash",
+            row_size: "Größe",
+            row_prove_time: "Beweiszeit",
+            row_verify_time: "Verifizierungszeit",
+            row_receipt_id: "Beleg-ID",
+            row_created: "Erstellt",
+            row_completed: "Abgeschlossen",
+            unit_bytes: "Bytes",
+            unit_ms: "ms",
+            proving_notice: "SNARK-Beweis wird erstellt. Diese Seite aktualisiert sich automatisch.",
+            unknown_error: "Unbekannter Fehler",
+            share_section_header: "Diesen Beweis teilen",
+            share_on_x: "Auf X teilen",
+            copy_verify_me: "„Verify me“ kopieren",
+            copy_proof_string: "Beweis-String kopieren",
+            download_bundle: "Verifizierungspaket herunterladen",
+            copy_button: "Kopieren",
+            proof_id_label: "Beweis-ID",
+            toast_link_copied: "Link kopiert",
+            toast_proof_string_copied: "Beweis-String kopiert",
+            toast_verify_me_copied: "„Verify me“-Nachricht kopiert",
+            footer_open_source: "Open Source (MIT)",
+            unavailable_title: "Nicht mehr verfügbar",
+            unavailable_expired: "Der Freigabelink dieses Belegs ist abgelaufen.",
+            unavailable_view_limit: "Dieser Beleg hat sein Aufruflimit erreicht.",
+            locked_title: "Passwort erforderlich",
+            locked_prompt: "Dieser Beleg ist durch ein Passwort geschützt. Gib das Passwort ein, um ihn anzuzeigen.",
+            locked_passphrase_label: "Passwort",
+            locked_unlock_button: "Entsperren",
+            locked_incorrect: "Falsches Passwort. Versuche es erneut.",
+
+            pg_tagline: "Kryptografische Beweisbelege für KI-gestützte Transaktionsentscheidungen. Für Agentic Commerce und KI-Sicherheit.",
+            pg_model_label: "Modell",
+            pg_parameters_label: "Parameter",
+            pg_input_text_label: "Eingabetext",
+            pg_raw_input_label: "Roher Eingabevektor (JSON-Array mit {dim} Ganzzahlen)",
+            pg_generate_button: "Beweis erzeugen",
+            pg_generating_button: "Wird erzeugt...",
+            pg_please_enter_text: "Bitte gib einen Text ein.",
+            pg_invalid_raw_json: "Ungültiges JSON-Array für die Roheingabe.",
+            pg_field_range_error: "{field} muss zwischen {min} und {max} liegen.",
+            pg_connection_lost: "Verbindung verloren",
+            pg_status_offline: "Offline — zeigt zwischengespeicherten Status",
+            pg_for_agents: "Für Agenten",
+            pg_for_compliance: "Für Compliance",
+            pg_agent_tool_definition: "Agenten-Tool-Definition",
+            pg_generated_for_model: "Erzeugt für das oben ausgewählte Modell",
+        },
+    }
+}
@@ -1,147 +1,58 @@
+mod admin_auth;
+mod announce;
+mod api_error;
+mod api_keys;
+mod auth;
+mod capacity;
 mod config;
+mod credential;
 mod crypto;
 mod handlers;
 mod input;
+mod locale;
+mod metrics;
+mod model_jobs;
+mod model_store;
 mod models;
+mod npy;
+mod preprocess_queue;
+mod profile_cache;
+mod proof_archive;
 mod prover;
+mod queue;
+mod rate_limit;
 mod receipt;
+mod retry;
+mod ssrf;
 mod state;
 mod templates;
+mod trust_source;
 
+use axum::extract::{Query, State};
+use axum::middleware;
 use axum::response::Html;
 use axum::routing::{get, post, put};
 use axum::Router;
 use std::collections::HashMap;
-use std::sync::{Arc, LazyLock, RwLock};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use axum::error_handling::HandleErrorLayer;
-use axum::http::StatusCode;
+use axum::http::{header, HeaderMap, StatusCode};
 use tower::ServiceBuilder;
 use tower::limit::RateLimitLayer;
 use tower::buffer::BufferLayer;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing::info;
 
-use onnx_tracer::model;
-
 use crate::config::Config;
 use crate::input::{load_onehot_vocab, load_tfidf_vocab, load_token_index_vocab};
+use crate::model_store::{LocalModelStore, ModelStore, S3ModelStore};
 use crate::models::{InputType, ModelRegistry};
+use crate::profile_cache::{InMemoryProfileCache, ProfileCache, ProfileCacheBackend, S3ProfileCache};
+use crate::proof_archive::ProofArchive;
+use crate::trust_source::{fediverse::FediverseSource, moltbook::MoltbookSource, TrustSourceRegistry};
 use crate::receipt::ReceiptStore;
-use crate::state::{AppState, PreprocessingCache, Snark, VocabData};
-
-static RE_DUP: LazyLock<regex::Regex> =
-    LazyLock::new(|| regex::Regex::new(r"(.)\1{2,}").unwrap());
-static RE_WS: LazyLock<regex::Regex> =
-    LazyLock::new(|| regex::Regex::new(r"\s+").unwrap());
-
-/// Solve Moltbook verification challenges (lobster-themed arithmetic).
-/// Strips junk chars, extracts number words, determines operation, computes answer.
-fn solve_moltbook_challenge(challenge: &str) -> Option<String> {
-    // Strip non-alpha/space chars, normalize to lowercase
-    let clean: String = challenge.chars()
-        .map(|c| if c.is_alphabetic() || c.is_whitespace() { c.to_ascii_lowercase() } else { ' ' })
-        .collect();
-    // Collapse repeated letters (e.g., "looobster" -> "lobster", "thhree" -> "three")
-    let clean = RE_DUP.replace_all(&clean, "$1$1");
-    // Collapse whitespace
-    let clean = RE_WS.replace_all(&clean, " ");
-
-    let word_to_num: Vec<(&str, f64)> = vec![
-        ("zero", 0.0), ("one", 1.0), ("two", 2.0), ("three", 3.0), ("four", 4.0),
-        ("five", 5.0), ("six", 6.0), ("seven", 7.0), ("eight", 8.0), ("nine", 9.0),
-        ("ten", 10.0), ("eleven", 11.0), ("twelve", 12.0), ("thirteen", 13.0),
-        ("fourteen", 14.0), ("fifteen", 15.0), ("sixteen", 16.0), ("seventeen", 17.0),
-        ("eighteen", 18.0), ("nineteen", 19.0), ("twenty", 20.0), ("thirty", 30.0),
-        ("forty", 40.0), ("fifty", 50.0), ("sixty", 60.0), ("seventy", 70.0),
-        ("eighty", 80.0), ("ninety", 90.0), ("hundred", 100.0),
-    ];
-
-    // Extract all number words in order and build compound numbers
-    let words: Vec<&str> = clean.split_whitespace().collect();
-    let mut numbers: Vec<f64> = Vec::new();
-    let mut current: Option<f64> = None;
-
-    for w in &words {
-        if let Some(&(_, val)) = word_to_num.iter().find(|&&(name, _)| name == *w) {
-            if val == 100.0 {
-                // "hundred" multiplies the current accumulator
-                current = Some(current.unwrap_or(1.0) * 100.0);
-            } else if val >= 20.0 && val < 100.0 {
-                // Tens place — start or extend a compound
-                if let Some(c) = current {
-                    if c < 20.0 {
-                        // previous was a single digit that's part of a different number
-                        numbers.push(c);
-                        current = Some(val);
-                    } else {
-                        numbers.push(c);
-                        current = Some(val);
-                    }
-                } else {
-                    current = Some(val);
-                }
-            } else {
-                // Units (0-19)
-                if let Some(c) = current {
-                    if c >= 20.0 && c % 10.0 == 0.0 && c < 100.0 {
-                        // Compound: twenty + three = 23
-                        current = Some(c + val);
-                    } else {
-                        numbers.push(c);
-                        current = Some(val);
-                    }
-                } else {
-                    current = Some(val);
-                }
-            }
-        } else if current.is_some() {
-            // Non-number word breaks the current compound
-            if let Some(c) = current.take() {
-                numbers.push(c);
-            }
-        }
-    }
-    if let Some(c) = current {
-        numbers.push(c);
-    }
-
-    if numbers.len() < 2 {
-        return None;
-    }
-
-    // Determine operation from cleaned text
-    let is_subtract = clean.contains("slow") || clean.contains("lose")
-        || clean.contains("less") || clean.contains("subtract")
-        || clean.contains("minus") || clean.contains("decreas")
-        || clean.contains("reduc") || clean.contains("drop")
-        || clean.contains("fell") || clean.contains("lost");
-
-    let is_multiply = clean.contains("times") || clean.contains("multipl")
-        || clean.contains("product");
-
-    let is_divide = clean.contains("divid") || clean.contains("split")
-        || clean.contains("per each") || clean.contains("shared equal");
-
-    let a = numbers[0];
-    let b = numbers[1];
-
-    let result = if is_subtract {
-        a - b
-    } else if is_multiply {
-        a * b
-    } else if is_divide && b != 0.0 {
-        a / b
-    } else {
-        a + b // default: addition (total, combined, adds, etc.)
-    };
-
-    if result.fract() == 0.0 {
-        Some(format!("{}", result as i64))
-    } else {
-        Some(format!("{:.2}", result))
-    }
-}
+use crate::state::{AppState, ProverBackendKind, VocabData};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -159,9 +70,27 @@ async fn main() -> anyhow::Result<()> {
     info!("[clawproof] Database path: {:?}", config.database_path);
 
     // Initialize SQLite
-    let receipts = ReceiptStore::new(&config.database_path)?;
+    let receipts = ReceiptStore::new(&config.database_path, config.max_cache_entries)?;
     info!("[clawproof] SQLite receipt store initialized");
 
+    let prove_queue = Arc::new(queue::ProofQueue::new(&config.database_path)?);
+    let preprocess_queue = Arc::new(preprocess_queue::PreprocessQueue::new(
+        &config.database_path,
+        config.preprocess_backoff_base_secs,
+    )?);
+
+    // Seed the Prometheus counters from the current SQL snapshot so a
+    // restart doesn't reset totals a scraper has already observed.
+    metrics::metrics().seed_from_stats(&receipts.get_stats());
+
+    // Coalesce per-receipt writes into periodic batched transactions instead
+    // of one SQLite commit per insert/update.
+    receipts.spawn_write_coalescer(Duration::from_millis(250));
+
+    // Periodic integrity scrub — catches receipts left corrupt by a crash
+    // between writes or on-disk bitrot.
+    receipts.spawn_scrub_task(Duration::from_secs(config.scrub_interval_secs));
+
     let mut registry = ModelRegistry::new();
 
     // Scan built-in models directory
@@ -225,14 +154,220 @@ async fn main() -> anyhow::Result<()> {
 
     let registry = Arc::new(RwLock::new(registry));
 
+    let attestation_key = match &config.attestation_signing_key {
+        Some(hex_key) => match crate::crypto::load_signing_key(hex_key) {
+            Ok(key) => {
+                info!("[clawproof] Attestation signing enabled");
+                Some(Arc::new(key))
+            }
+            Err(e) => {
+                tracing::warn!("[clawproof] Invalid ATTESTATION_SIGNING_KEY, attestations disabled: {:?}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let credential_key = match crate::crypto::load_or_generate_credential_key(&config.signing_key_path) {
+        Ok(key) => {
+            info!("[clawproof] Verifiable Credential signing enabled, issuer {}", key.did);
+            Arc::new(key)
+        }
+        Err(e) => {
+            tracing::error!("[clawproof] Failed to load or generate credential signing key at {:?}: {:?}", config.signing_key_path, e);
+            std::process::exit(1);
+        }
+    };
+
+    let http_signature_key = match (&config.http_signature_key_id, &config.http_signature_private_key_pem) {
+        (Some(key_id), Some(pem)) => match crate::crypto::load_http_signature_key(key_id, pem) {
+            Ok(key) => {
+                info!("[clawproof] Fediverse authorized-fetch signing enabled");
+                Some(Arc::new(key))
+            }
+            Err(e) => {
+                tracing::warn!("[clawproof] Invalid HTTP_SIGNATURE_PRIVATE_KEY_PEM, authorized fetch disabled: {:?}", e);
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let mut trust_sources = TrustSourceRegistry::new();
+    trust_sources.register(
+        "moltbook",
+        Arc::new(MoltbookSource::new(config.moltbook_api_key.clone())),
+    );
+    trust_sources.register("fediverse", Arc::new(FediverseSource::new(http_signature_key.clone())));
+
+    // Receipt announcers — which backends are active is chosen by
+    // `ANNOUNCE_BACKENDS`; each also needs its own config to actually start
+    // (an API key, or a signing key), so a name listed there without the
+    // matching config just logs a warning and is skipped.
+    let mut announce_backends: Vec<Arc<dyn announce::Announcer>> = Vec::new();
+
+    let activitypub_announcer = if config.announce_backends.iter().any(|b| b == "activitypub") {
+        match &http_signature_key {
+            Some(key) => match announce::activitypub::ActivityPubAnnouncer::new(config.base_url.clone(), key.clone()) {
+                Ok(ap) => {
+                    let ap = Arc::new(ap);
+                    announce_backends.push(ap.clone());
+                    Some(ap)
+                }
+                Err(e) => {
+                    tracing::warn!("[clawproof] Failed to initialize ActivityPub announcer: {:?}", e);
+                    None
+                }
+            },
+            None => {
+                tracing::warn!("[clawproof] activitypub announcer enabled but HTTP_SIGNATURE_KEY_ID/HTTP_SIGNATURE_PRIVATE_KEY_PEM aren't set");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    if config.announce_backends.iter().any(|b| b == "moltbook") {
+        match &config.moltbook_api_key {
+            Some(key) => {
+                announce_backends.push(Arc::new(announce::moltbook::MoltbookAnnouncer::new(
+                    key.clone(),
+                    config.base_url.clone(),
+                )));
+            }
+            None => tracing::warn!("[clawproof] moltbook announcer enabled but MOLTBOOK_API_KEY isn't set"),
+        }
+    }
+
+    let announcers = announce::AnnouncerSet::new(announce_backends);
+    if !announcers.is_empty() {
+        info!("[clawproof] Announce backends enabled: {}", config.announce_backends.join(", "));
+    }
+
+    let profile_cache_backend: Arc<dyn ProfileCacheBackend> = match config.profile_cache_backend.as_str() {
+        "s3" => match &config.profile_cache_s3_bucket {
+            Some(bucket) => {
+                info!("[clawproof] Using S3 profile cache backend (bucket: {})", bucket);
+                Arc::new(
+                    S3ProfileCache::new(
+                        bucket.clone(),
+                        config.profile_cache_s3_endpoint.clone(),
+                        config.profile_cache_s3_region.clone(),
+                    )
+                    .await,
+                )
+            }
+            None => {
+                tracing::warn!("[clawproof] PROFILE_CACHE_BACKEND=s3 but PROFILE_CACHE_S3_BUCKET is unset, falling back to in-memory cache");
+                Arc::new(InMemoryProfileCache::new())
+            }
+        },
+        _ => Arc::new(InMemoryProfileCache::new()),
+    };
+    let profile_cache = Arc::new(ProfileCache::new(profile_cache_backend, config.profile_cache_ttl_secs));
+
+    let proof_archive = match &config.proof_archive_s3_bucket {
+        Some(bucket) => {
+            info!("[clawproof] Proof archival to object storage enabled (bucket: {})", bucket);
+            Some(Arc::new(
+                ProofArchive::new(
+                    bucket.clone(),
+                    config.proof_archive_s3_endpoint.clone(),
+                    config.proof_archive_s3_region.clone(),
+                )
+                .await,
+            ))
+        }
+        None => None,
+    };
+
+    let store: Arc<dyn ModelStore> = match config.model_store_backend.as_str() {
+        "s3" => match &config.model_store_s3_bucket {
+            Some(bucket) => {
+                info!("[clawproof] Using S3 model store backend (bucket: {})", bucket);
+                Arc::new(
+                    S3ModelStore::new(
+                        bucket.clone(),
+                        config.model_store_s3_endpoint.clone(),
+                        config.model_store_s3_region.clone(),
+                    )
+                    .await,
+                )
+            }
+            None => {
+                tracing::warn!("[clawproof] MODEL_STORE_BACKEND=s3 but MODEL_STORE_S3_BUCKET is unset, falling back to local model store");
+                Arc::new(LocalModelStore::new(config.uploaded_models_dir.clone()))
+            }
+        },
+        _ => Arc::new(LocalModelStore::new(config.uploaded_models_dir.clone())),
+    };
+
     let state = AppState {
         config: config.clone(),
         receipts,
         registry: registry.clone(),
         vocabs: Arc::new(vocabs),
         preprocessing: Arc::new(dashmap::DashMap::new()),
+        attestation_key,
+        proof_progress: crate::state::ProgressBroadcaster::new(),
+        trust_sources: Arc::new(trust_sources),
+        profile_cache,
+        pending_uploads: Arc::new(dashmap::DashMap::new()),
+        proof_archive,
+        prove_limiter: rate_limit::RateLimiter::new(),
+        batch_limiter: rate_limit::RateLimiter::new(),
+        upload_limiter: rate_limit::RateLimiter::new(),
+        prove_model_limiter: rate_limit::RateLimiter::new(),
+        announcers,
+        activitypub: activitypub_announcer,
+        credential_key,
+        prove_queue: prove_queue.clone(),
+        api_keys: api_keys::ApiKeyStore::seed(&config.api_keys),
+        model_jobs: model_jobs::ModelJobStore::new(),
+        store,
+        model_hash_index: Arc::new(dashmap::DashMap::new()),
+        preprocess_queue: preprocess_queue.clone(),
     };
 
+    preprocess_queue::spawn_dispatcher(
+        preprocess_queue,
+        config.preprocess_concurrency,
+        preprocess_queue::PreprocessDispatcherContext::from_state(&state),
+    );
+
+    queue::spawn_dispatcher(
+        prove_queue,
+        config.prove_concurrency,
+        queue::DispatcherContext {
+            receipt_store: state.receipts.clone(),
+            progress: state.proof_progress.clone(),
+            preprocessing: state.preprocessing.clone(),
+            models_dir: config.models_dir.clone(),
+            uploaded_models_dir: config.uploaded_models_dir.clone(),
+            proofs_dir: config.proofs_dir.clone(),
+            webhook_signing_secret: config.webhook_signing_secret.clone(),
+            attestation_key: state.attestation_key.clone(),
+            proof_archive: state.proof_archive.clone(),
+        },
+    );
+
+    handlers::upload_resumable::spawn_pending_upload_reaper(
+        state.clone(),
+        Duration::from_secs(600),
+        chrono::Duration::seconds(config.pending_upload_ttl_secs as i64),
+    );
+
+    let rate_limit_bucket_ttl = Duration::from_secs(config.rate_limit_bucket_ttl_secs);
+    for limiter in [
+        &state.prove_limiter,
+        &state.batch_limiter,
+        &state.upload_limiter,
+        &state.prove_model_limiter,
+    ] {
+        limiter.spawn_reaper(Duration::from_secs(600), rate_limit_bucket_ttl);
+    }
+
     // Spawn background preprocessing — server starts immediately so Render
     // health checks pass while models are being preprocessed.
     let bg_state = state.clone();
@@ -252,41 +387,87 @@ async fn main() -> anyhow::Result<()> {
                 continue;
             }
 
-            let trace_length = model_desc.trace_length;
-
-            info!(
-                "[clawproof] Preprocessing {} (trace_length: {})...",
-                model_id, trace_length
-            );
-
-            let model_path_clone = model_path.clone();
-            let preprocessing = match tokio::task::spawn_blocking(move || {
-                let model_fn = || model(&model_path_clone);
-                Snark::prover_preprocess(model_fn, trace_length)
+            let estimate_path = model_path.clone();
+            let estimate_id = model_id.clone();
+            let estimate_shape = model_desc.input_shape.clone();
+            let trace_length = match tokio::task::spawn_blocking(move || {
+                capacity::estimate_trace_length(&estimate_id, &estimate_path, &estimate_shape)
             })
             .await
             {
-                Ok(p) => p,
+                Ok(Ok(estimated)) => estimated,
+                Ok(Err(e)) => {
+                    tracing::error!(
+                        "[clawproof] Capacity estimate failed for {}, falling back to model.toml trace_length: {:?}",
+                        model_id, e
+                    );
+                    model_desc.trace_length
+                }
                 Err(e) => {
                     tracing::error!(
-                        "[clawproof] Failed to preprocess {}: {:?}",
-                        model_id,
-                        e
+                        "[clawproof] Capacity estimate task panicked for {}: {:?}",
+                        model_id, e
                     );
                     continue;
                 }
             };
 
-            let verifier_preprocessing = (&preprocessing).into();
-            info!("[clawproof] {} preprocessed successfully", model_id);
+            if trace_length > bg_config.max_trace_length {
+                let reason = format!(
+                    "estimated trace_length {} exceeds max_trace_length {}",
+                    trace_length, bg_config.max_trace_length
+                );
+                tracing::warn!("[clawproof] Marking {} unsupported: {}", model_id, reason);
+                let mut reg = bg_state.registry.write().expect("model registry lock poisoned");
+                reg.mark_unsupported(&model_id, reason);
+                continue;
+            }
+
+            {
+                let mut reg = bg_state.registry.write().expect("model registry lock poisoned");
+                reg.set_trace_length(&model_id, trace_length);
+            }
 
-            bg_state.preprocessing.insert(
-                model_id,
-                PreprocessingCache {
-                    prover: preprocessing,
-                    verifier: verifier_preprocessing,
-                },
+            info!(
+                "[clawproof] Preprocessing {} (auto-estimated trace_length: {})...",
+                model_id, trace_length
             );
+
+            // Warm every backend concurrently — `Mock` preprocessing is
+            // effectively free, and warming it alongside the real JOLT-Atlas
+            // pass means `/prove` requests with `backend: "mock"` never hit
+            // the "still loading" 503 that a lazily-preprocessed backend
+            // would cause.
+            let handles = [ProverBackendKind::Mock, ProverBackendKind::JoltAtlas].map(|backend_kind| {
+                let model_path_clone = model_path.clone();
+                let handle = tokio::task::spawn_blocking(move || {
+                    prover::backend_for(backend_kind).preprocess(&model_path_clone, trace_length)
+                });
+                (backend_kind, handle)
+            });
+            for (backend_kind, handle) in handles {
+                let model_id_clone = model_id.clone();
+                let preprocessing = match handle.await {
+                    Ok(Ok(p)) => p,
+                    Ok(Err(e)) => {
+                        tracing::error!(
+                            "[clawproof] Failed to preprocess {} with {:?} backend: {:?}",
+                            model_id_clone, backend_kind, e
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[clawproof] Preprocessing task panicked for {} with {:?} backend: {:?}",
+                            model_id_clone, backend_kind, e
+                        );
+                        continue;
+                    }
+                };
+
+                info!("[clawproof] {} preprocessed successfully with {:?} backend", model_id_clone, backend_kind);
+                bg_state.preprocessing.insert((model_id_clone, backend_kind), Arc::new(preprocessing));
+            }
         }
         info!("[clawproof] All models preprocessed and ready");
     });
@@ -302,276 +483,31 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    // Moltbook heartbeat — engagement cycle + combo posting every 30 min
-    if let Some(ref key) = config.moltbook_api_key {
-        let api_key = key.clone();
-        let moltbook_receipts = state.receipts.clone();
-        let moltbook_base_url = config.base_url.clone();
+    // Announce heartbeat — picks up the most recently verified receipt and
+    // broadcasts it to every backend in `ANNOUNCE_BACKENDS`.
+    if !state.announcers.is_empty() {
+        let announce_receipts = state.receipts.clone();
+        let announcers = state.announcers.clone();
+        let interval_secs = config.announce_interval_secs;
         tokio::spawn(async move {
-            let client = reqwest::Client::new();
-            let base = "https://www.moltbook.com/api/v1";
-            let start = tokio::time::Instant::now() + Duration::from_secs(1800);
-            let mut interval = tokio::time::interval_at(start, Duration::from_secs(1800));
-            let mut cycle: u64 = 0;
-            let mut consecutive_failures: u32 = 0;
-
-            // Submolts to rotate through
-            let submolts = ["tools", "ai", "programming", "crypto", "openclaw"];
-
+            let start = tokio::time::Instant::now() + Duration::from_secs(interval_secs);
+            let mut interval = tokio::time::interval_at(start, Duration::from_secs(interval_secs));
             loop {
                 interval.tick().await;
-
-                // Exponential backoff after 3+ consecutive failures
-                if consecutive_failures >= 3 {
-                    let backoff_multiplier = 1u64 << (consecutive_failures - 3).min(3); // cap at 8x
-                    let extra_sleep = Duration::from_secs(1800 * backoff_multiplier);
-                    tracing::warn!(
-                        "[moltbook] {} consecutive failures, backing off for {}s",
-                        consecutive_failures,
-                        extra_sleep.as_secs()
-                    );
-                    tokio::time::sleep(extra_sleep).await;
-                }
-
-                let auth = format!("Bearer {}", api_key);
-
-                // --- Engagement: home, notifications, feed ---
-                let _ = client.get(format!("{}/home", base))
-                    .header("Authorization", &auth).send().await
-                    .map(|r| info!("[moltbook] home: {}", r.status()))
-                    .map_err(|e| tracing::warn!("[moltbook] home failed: {:?}", e));
-
-                if let Ok(resp) = client.get(format!("{}/notifications", base))
-                    .header("Authorization", &auth).send().await
-                {
-                    if resp.status().is_success() {
-                        let _ = client.post(format!("{}/notifications/read-all", base))
-                            .header("Authorization", &auth).send().await;
+                let recent = announce_receipts
+                    .list_recent(5, None, &crate::receipt::ReceiptFilter::default())
+                    .receipts;
+                if let Some(summary) = recent.into_iter().find(|r| r.status == "verified") {
+                    if let Some(receipt) = announce_receipts.get(&summary.id) {
+                        announcers.announce_all(&receipt).await;
                     }
                 }
-
-                let _ = client.get(format!("{}/feed", base))
-                    .header("Authorization", &auth).send().await;
-
-                // --- Combo posting: rotate through post types ---
-                let submolt = submolts[(cycle as usize) % submolts.len()];
-                let stats = moltbook_receipts.get_stats();
-                let recent = moltbook_receipts.list_recent(5);
-
-                let (title, content) = match cycle % 5 {
-                    // 0: Stats update
-                    0 => {
-                        (
-                            format!("ClawProof stats: {} proofs generated, {} verified", stats.total_proofs, stats.verified),
-                            format!(
-                                "Platform update from ClawProof — zkML proof-as-a-service.\n\n\
-                                **Live stats:**\n\
-                                - Total proofs: {}\n\
-                                - Verified: {}\n\
-                                - Proving: {}\n\
-                                - Avg prove time: {} ms\n\
-                                - Avg verify time: {} ms\n\n\
-                                Generate your own proof (no auth):\n\
-                                ```\ncurl -X POST {}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                Dashboard: {}\n\
-                                Source: https://github.com/hshadab/clawproof (MIT)",
-                                stats.total_proofs, stats.verified, stats.proving,
-                                stats.avg_prove_time_ms.map(|t| format!("{:.0}", t)).unwrap_or_else(|| "-".to_string()),
-                                stats.avg_verify_time_ms.map(|t| format!("{:.0}", t)).unwrap_or_else(|| "-".to_string()),
-                                moltbook_base_url, moltbook_base_url,
-                            )
-                        )
-                    },
-                    // 1: Proof showcase (share a recent verified receipt)
-                    1 => {
-                        let verified = recent.iter().find(|r| r.status == "verified");
-                        if let Some(r) = verified {
-                            (
-                                format!("Live SNARK proof: {} classified as {} ({:.1}% confidence)", r.model_name, r.label, r.confidence * 100.0),
-                                format!(
-                                    "Just proved ML inference with a real JOLT-Atlas SNARK.\n\n\
-                                    **Model:** {}\n\
-                                    **Result:** {} ({:.1}% confidence)\n\
-                                    **Prove time:** {} ms\n\
-                                    **Verify time:** {} ms\n\n\
-                                    View the receipt: {}/receipt/{}\n\
-                                    Badge: ![proof]({}/badge/{})\n\n\
-                                    The proof cryptographically guarantees this model produced this output for this input. Anyone can verify without re-running inference.\n\n\
-                                    Try it yourself:\n\
-                                    ```\ncurl -X POST {}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                    -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                    Source: https://github.com/hshadab/clawproof (MIT)",
-                                    r.model_name, r.label, r.confidence * 100.0,
-                                    r.prove_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
-                                    r.verify_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
-                                    moltbook_base_url, r.id,
-                                    moltbook_base_url, r.id,
-                                    moltbook_base_url,
-                                )
-                            )
-                        } else {
-                            (
-                                "[Skill Drop] Prove ML inference with one curl call".to_string(),
-                                format!(
-                                    "ClawProof generates cryptographic SNARK proofs of ML inference. No API keys, no auth.\n\n\
-                                    ```\ncurl -X POST {}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                    -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                    Full skill: https://raw.githubusercontent.com/hshadab/clawproof/main/SKILL.md\n\
-                                    Source: https://github.com/hshadab/clawproof (MIT)",
-                                    moltbook_base_url,
-                                )
-                            )
-                        }
-                    },
-                    // 2: Skill drop
-                    2 => {
-                        (
-                            "[Skill Drop] Prove your ML inference with one curl call — no auth".to_string(),
-                            format!(
-                                "Need to prove that a model actually produced a specific output? ClawProof generates cryptographic SNARK proofs of ML inference. No API keys, no signup.\n\n\
-                                **Prove it:**\n\
-                                ```\ncurl -X POST {base}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                You get back a `receipt_id` immediately with the prediction. The SNARK proof generates in the background (~5-10s). Then:\n\
-                                ```\ncurl -H \"Accept: application/json\" {base}/receipt/{{receipt_id}}\n```\n\n\
-                                Your receipt has cryptographic hashes of the model, input, output, and proof. Anyone can verify it without seeing your data.\n\n\
-                                **Embed a proof badge:** `![proof]({base}/badge/{{receipt_id}})`\n\n\
-                                Full skill: https://raw.githubusercontent.com/hshadab/clawproof/main/SKILL.md\n\
-                                Open source (MIT): https://github.com/hshadab/clawproof",
-                                base = moltbook_base_url,
-                            )
-                        )
-                    },
-                    // 3: Guide — how to prove AI decisions
-                    3 => {
-                        (
-                            "How to prove your AI decisions are real — zkML proofs in 3 steps".to_string(),
-                            format!(
-                                "When an agent says \"my model classified this as AUTHORIZED,\" how does anyone verify that? They can't — unless there's a cryptographic proof.\n\n\
-                                **Zero-knowledge ML (zkML)** solves this. A SNARK proof mathematically guarantees that a specific model produced a specific output for a specific input.\n\n\
-                                ## Step 1: Call the API\n\
-                                ```\ncurl -X POST {base}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                ## Step 2: Wait for the proof\n\
-                                The JOLT-Atlas SNARK proof generates in ~5-10 seconds. Poll:\n\
-                                ```\ncurl -H \"Accept: application/json\" {base}/receipt/{{receipt_id}}\n```\n\n\
-                                ## Step 3: Verify\n\
-                                ```\ncurl -X POST {base}/verify \\\n  -H \"Content-Type: application/json\" \\\n  -d '{{\"receipt_id\":\"YOUR_ID\"}}'\n```\n\n\
-                                **Use cases:** verifiable AI decisions, audit trails, agent reputation, composable trust.\n\n\
-                                Skill: https://raw.githubusercontent.com/hshadab/clawproof/main/SKILL.md\n\
-                                Source: https://github.com/hshadab/clawproof (MIT)",
-                                base = moltbook_base_url,
-                            )
-                        )
-                    },
-                    // 4: Crypto showcase — technical deep dive
-                    _ => {
-                        (
-                            "Live SNARK proof of ML inference — JOLT-Atlas on BN254".to_string(),
-                            format!(
-                                "Generated a real JOLT-Atlas SNARK proof of neural network inference. The proof system uses Dory polynomial commitment on BN254.\n\n\
-                                **Cryptographic receipt contains:**\n\
-                                - `model_hash` — Keccak256 commitment to the exact ONNX weights\n\
-                                - `input_hash` — Keccak256 of the input tensor\n\
-                                - `output_hash` — Keccak256 of the inference output\n\
-                                - `proof_hash` — Keccak256 of the serialized SNARK proof\n\n\
-                                **Verify it yourself:**\n\
-                                ```\ncurl -X POST {base}/prove \\\n  -H \"Content-Type: application/json\" \\\n  \
-                                -d '{{\"model_id\":\"authorization\",\"input\":{{\"fields\":{{\"budget\":13,\"trust\":3,\"amount\":4,\"category\":1,\"velocity\":1,\"day\":2,\"time\":0}}}}}}'\n```\n\n\
-                                **Technical details:**\n\
-                                - Proof system: JOLT (lookup-based SNARK)\n\
-                                - Commitment: Dory vector commitment (transparent setup)\n\
-                                - Curve: BN254\n\
-                                - Model: ONNX format, i32 arithmetic\n\n\
-                                No API keys. Open source (MIT): https://github.com/hshadab/clawproof",
-                                base = moltbook_base_url,
-                            )
-                        )
-                    },
-                };
-
-                // Post to Moltbook
-                let post_body = serde_json::json!({
-                    "title": title,
-                    "content": content,
-                    "submolt": submolt,
-                    "type": "text"
-                });
-
-                match client.post(format!("{}/posts", base))
-                    .header("Authorization", &auth)
-                    .header("Content-Type", "application/json")
-                    .body(post_body.to_string())
-                    .send().await
-                {
-                    Ok(resp) => {
-                        let status = resp.status();
-                        info!("[moltbook] Posted to m/{} (cycle {}): {} — {}", submolt, cycle, status, title);
-
-                        // Parse response to solve verification challenge
-                        if let Ok(body) = resp.text().await {
-                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
-                                let verification = json.get("post")
-                                    .and_then(|p| p.get("verification"))
-                                    .or_else(|| json.get("verification"));
-
-                                if let Some(v) = verification {
-                                    let code = v.get("verification_code")
-                                        .and_then(|c| c.as_str());
-                                    let challenge = v.get("challenge_text")
-                                        .and_then(|c| c.as_str());
-
-                                    if let (Some(code), Some(challenge)) = (code, challenge) {
-                                        info!("[moltbook] Verification challenge: {}", challenge);
-                                        if let Some(answer) = solve_moltbook_challenge(challenge) {
-                                            info!("[moltbook] Solving with answer: {}", answer);
-                                            let verify_body = serde_json::json!({
-                                                "verification_code": code,
-                                                "answer": answer
-                                            });
-                                            match client.post(format!("{}/verify", base))
-                                                .header("Authorization", &auth)
-                                                .header("Content-Type", "application/json")
-                                                .body(verify_body.to_string())
-                                                .send().await
-                                            {
-                                                Ok(vr) => {
-                                                    let vs = vr.status();
-                                                    let vb = vr.text().await.unwrap_or_default();
-                                                    if vs.is_success() {
-                                                        info!("[moltbook] Verification solved! Post is live. (cycle {})", cycle);
-                                                    } else {
-                                                        tracing::warn!("[moltbook] Verification failed {}: {} (cycle {})", vs, vb, cycle);
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    tracing::warn!("[moltbook] Verify request failed: {:?}", e);
-                                                }
-                                            }
-                                        } else {
-                                            tracing::warn!("[moltbook] Could not solve challenge: {}", challenge);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!("[moltbook] Post failed (cycle {}): {:?}", cycle, e);
-                        consecutive_failures += 1;
-                        cycle += 1;
-                        continue;
-                    }
-                }
-
-                consecutive_failures = 0;
-                cycle += 1;
             }
         });
-        info!("[clawproof] Moltbook heartbeat + posting enabled (every 30 min)");
+        info!("[clawproof] Announce heartbeat enabled (every {}s)", config.announce_interval_secs);
     }
 
+
     // CORS configuration
     let cors = if let Some(ref origins) = config.cors_origins {
         let origins: Vec<_> = origins
@@ -589,63 +525,126 @@ async fn main() -> anyhow::Result<()> {
             .allow_headers(Any)
     };
 
-    // Rate limit middleware builders
-    let prove_rate_limit = ServiceBuilder::new()
-        .layer(HandleErrorLayer::new(|_: tower::BoxError| async {
-            StatusCode::TOO_MANY_REQUESTS
-        }))
-        .layer(BufferLayer::new(32))
-        .layer(RateLimitLayer::new(10, Duration::from_secs(60)));
-
-    let batch_rate_limit = ServiceBuilder::new()
-        .layer(HandleErrorLayer::new(|_: tower::BoxError| async {
-            StatusCode::TOO_MANY_REQUESTS
-        }))
-        .layer(BufferLayer::new(8))
-        .layer(RateLimitLayer::new(2, Duration::from_secs(60)));
-
-    let upload_rate_limit = ServiceBuilder::new()
+    // Rate limit middleware builders.
+    //
+    // `/prove`, `/prove/batch`, `/models/upload`, and `/prove/model` used to
+    // sit behind a `RateLimitLayer` here too, shared by every caller
+    // combined. They're now each gated by `rate_limit::limit_*`, a
+    // per-subject token bucket layered onto the route below instead, so
+    // those four builders are gone from this block. `/models/upload/begin`
+    // and `/models/upload/part` weren't part of that change and keep their
+    // global limiters as before.
+    let upload_begin_rate_limit = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|_: tower::BoxError| async {
             StatusCode::TOO_MANY_REQUESTS
         }))
         .layer(BufferLayer::new(4))
         .layer(RateLimitLayer::new(1, Duration::from_secs(300)));
 
-    let prove_model_rate_limit = ServiceBuilder::new()
+    // Far more permissive than the "start an upload" limiters above — a
+    // single large model upload legitimately needs many `upload_part` calls
+    // in quick succession.
+    let upload_part_rate_limit = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|_: tower::BoxError| async {
             StatusCode::TOO_MANY_REQUESTS
         }))
-        .layer(BufferLayer::new(4))
-        .layer(RateLimitLayer::new(1, Duration::from_secs(300)));
+        .layer(BufferLayer::new(16))
+        .layer(RateLimitLayer::new(60, Duration::from_secs(60)));
 
     let app = Router::new()
         .route("/", get(playground))
+        .route("/manifest.webmanifest", get(handlers::manifest::manifest))
+        .route("/sw.js", get(handlers::service_worker::service_worker))
         .route("/health", get(handlers::health::health))
         .route("/models", get(handlers::models::list_models))
+        .route("/models/:id/status", get(handlers::model_status::model_status))
         .route(
             "/prove",
-            post(handlers::prove::prove).layer(prove_rate_limit),
+            post(handlers::prove::prove)
+                .layer(middleware::from_fn_with_state(state.clone(), rate_limit::limit_prove))
+                .layer(middleware::from_fn_with_state(state.clone(), api_keys::require_api_key)),
         )
         .route(
             "/prove/batch",
-            post(handlers::batch::batch_prove).layer(batch_rate_limit),
+            post(handlers::batch::batch_prove)
+                .layer(middleware::from_fn_with_state(state.clone(), rate_limit::limit_batch))
+                .layer(middleware::from_fn_with_state(state.clone(), auth::require_auth))
+                .layer(middleware::from_fn_with_state(state.clone(), api_keys::require_api_key)),
         )
         .route("/receipt/:id", get(handlers::receipt::get_receipt))
-        .route("/receipts/recent", get(handlers::receipts_list::recent))
+        .route("/receipt/:id/unlock", post(handlers::receipt_unlock::unlock_receipt))
+        .route("/receipt/:id/events", get(handlers::receipt_events::receipt_events))
+        .route("/receipt/:id/bundle", get(handlers::receipt_bundle::receipt_bundle))
+        .route("/receipt/:id/proof", get(handlers::receipt_proof::download_proof))
+        .route("/receipts/:id/poll", get(handlers::receipt_poll::poll_receipt))
+        .route("/receipts/subscribe", get(handlers::receipt_ws::receipt_ws))
+        .route(
+            "/receipts/recent",
+            get(handlers::receipts_list::recent)
+                .layer(middleware::from_fn_with_state(state.clone(), api_keys::require_api_key)),
+        )
+        .route("/jobs/:id", get(handlers::jobs::get_job))
+        .route("/jobs/model/:id", get(handlers::jobs::get_model_job))
         .route("/verify", post(handlers::verify::verify))
+        .route("/attestation/verify", post(handlers::attestation::verify_attestation))
         .route("/metrics", get(handlers::metrics::metrics))
         .route("/badge/:receipt_id", get(handlers::badge::badge))
+        .route("/badge/:receipt_id/endpoint.json", get(handlers::badge::badge_endpoint))
         .route(
             "/models/upload",
-            post(handlers::upload::upload_model).layer(upload_rate_limit),
+            post(handlers::upload::upload_model)
+                .layer(middleware::from_fn_with_state(state.clone(), rate_limit::limit_upload))
+                .layer(middleware::from_fn_with_state(state.clone(), auth::require_auth)),
+        )
+        .route(
+            "/models/upload/begin",
+            post(handlers::upload_resumable::begin_upload).layer(upload_begin_rate_limit),
+        )
+        .route(
+            "/models/upload/part",
+            post(handlers::upload_resumable::upload_part).layer(upload_part_rate_limit),
+        )
+        .route(
+            "/models/upload/complete",
+            post(handlers::upload_resumable::complete_upload),
         )
         .route(
             "/prove/model",
-            post(handlers::prove_model::prove_model).layer(prove_model_rate_limit),
+            post(handlers::prove_model::prove_model)
+                .layer(middleware::from_fn_with_state(state.clone(), rate_limit::limit_prove_model))
+                .layer(middleware::from_fn_with_state(state.clone(), auth::require_auth)),
         )
         .route("/convert", post(handlers::convert::convert))
         .route("/openapi.json", get(handlers::openapi::openapi_spec))
-        .route("/admin/static/playground", put(handlers::static_update::update_playground))
+        .route("/admin/login", post(admin_auth::login))
+        .route(
+            "/admin/static/playground",
+            put(handlers::static_update::update_playground)
+                .layer(middleware::from_fn_with_state(state.clone(), admin_auth::require_admin)),
+        )
+        .route(
+            "/admin/scrub",
+            post(handlers::scrub::scrub)
+                .layer(middleware::from_fn_with_state(state.clone(), admin_auth::require_admin)),
+        )
+        .route(
+            "/admin/tokens",
+            post(handlers::admin_tokens::issue_token)
+                .layer(middleware::from_fn_with_state(state.clone(), admin_auth::require_admin)),
+        )
+        .route(
+            "/admin/api-keys",
+            post(handlers::admin_api_keys::issue_api_key)
+                .delete(handlers::admin_api_keys::revoke_api_key)
+                .layer(middleware::from_fn_with_state(state.clone(), admin_auth::require_admin)),
+        )
+        .route("/aggregate", post(handlers::aggregate::aggregate))
+        .route("/aggregate/:id", get(handlers::aggregate::get_aggregate))
+        .route("/.well-known/webfinger", get(handlers::activitypub::webfinger))
+        .route(announce::activitypub::ACTOR_PATH, get(handlers::activitypub::actor))
+        .route(announce::activitypub::INBOX_PATH, post(handlers::activitypub::inbox))
+        .route("/did.json", get(handlers::credential::did_document))
+        .route("/verify-credential", post(handlers::credential::verify_credential))
         .layer(cors)
         .with_state(state);
 
@@ -653,11 +652,36 @@ async fn main() -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     info!("[clawproof] Listening on {}", addr);
 
-    axum::serve(listener, app).await?;
+    // `with_connect_info` so `rate_limit`'s middleware can fall back to the
+    // client's IP when a request carries no JWT `sub` to key its bucket on.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
-async fn playground() -> Html<String> {
-    Html(templates::playground::render())
+#[derive(serde::Deserialize, Default)]
+struct PlaygroundQuery {
+    #[serde(default)]
+    lang: Option<String>,
+}
+
+async fn playground(
+    State(state): State<AppState>,
+    Query(query): Query<PlaygroundQuery>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let models = state.registry.read().expect("model registry lock poisoned").list();
+    let default_locale = locale::Locale::from_code(&state.config.default_locale).unwrap_or(locale::Locale::En);
+    let accept_language = headers.get(header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+    let locale = locale::Locale::resolve(query.lang.as_deref(), accept_language, default_locale);
+
+    let ctx = templates::playground::PageContext {
+        models: models.into_iter().cloned().collect(),
+        locale,
+    };
+    Html(templates::playground::render(&ctx))
 }
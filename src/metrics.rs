@@ -0,0 +1,248 @@
+//! Process-lifetime Prometheus counters/histograms for the receipt store.
+//!
+//! `ReceiptStats` (see `receipt.rs`) gives a point-in-time SQL snapshot; this
+//! module accumulates the same data as monotonic counters so `/metrics` can
+//! be scraped for dashboards/alerting instead of polled.
+
+use dashmap::DashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::LazyLock;
+
+use crate::receipt::ReceiptStats;
+
+const PROVE_TIME_BUCKETS_MS: &[f64] = &[250.0, 1000.0, 5000.0, 15000.0, 30000.0, 60000.0];
+const VERIFY_TIME_BUCKETS_MS: &[f64] = &[5.0, 25.0, 100.0, 500.0, 2000.0, 10000.0];
+const INFERENCE_TIME_BUCKETS_MS: &[f64] = &[10.0, 50.0, 200.0, 1000.0, 5000.0, 15000.0];
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+struct Histogram {
+    buckets: &'static [f64],
+    /// One counter per bucket bound plus a trailing +Inf counter; each
+    /// counter is already cumulative (an observation increments every
+    /// bucket whose bound is >= the value), matching Prometheus semantics.
+    counts: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+    total: AtomicU64,
+}
+
+impl Histogram {
+    fn new(buckets: &'static [f64]) -> Self {
+        Self {
+            buckets,
+            counts: (0..=buckets.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_ms: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value_ms: f64) {
+        self.total.fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(value_ms.round() as u64, Ordering::Relaxed);
+        for (i, bound) in self.buckets.iter().enumerate() {
+            if value_ms <= *bound {
+                self.counts[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.counts[self.buckets.len()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        for (i, bound) in self.buckets.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{le=\"{bound}\"}} {}",
+                self.counts[i].load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{le=\"+Inf\"}} {}",
+            self.total.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(out, "{name}_sum {}", self.sum_ms.load(Ordering::Relaxed));
+        let _ = writeln!(out, "{name}_count {}", self.total.load(Ordering::Relaxed));
+    }
+}
+
+pub struct Metrics {
+    proofs_total: DashMap<String, AtomicU64>,
+    proofs_by_model: DashMap<String, AtomicU64>,
+    prove_time: Histogram,
+    verify_time: Histogram,
+    inference_time: Histogram,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    /// Requests into `prove`/`batch_prove`, labeled by model_id and outcome
+    /// ("accepted", "validation_rejected", "model_loading", "inference_panic",
+    /// etc.) — unlike `proofs_total`, which only counts receipts that made it
+    /// past validation, this also captures requests rejected before a receipt
+    /// ever existed.
+    prove_requests: DashMap<(String, String), AtomicU64>,
+    /// Receipts currently in `Proving` status — a gauge, not a counter, so it
+    /// tracks backlog depth rather than lifetime volume.
+    proving_gauge: AtomicI64,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Self {
+            proofs_total: DashMap::new(),
+            proofs_by_model: DashMap::new(),
+            prove_time: Histogram::new(PROVE_TIME_BUCKETS_MS),
+            verify_time: Histogram::new(VERIFY_TIME_BUCKETS_MS),
+            inference_time: Histogram::new(INFERENCE_TIME_BUCKETS_MS),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            prove_requests: DashMap::new(),
+            proving_gauge: AtomicI64::new(0),
+        }
+    }
+
+    /// Seed the counters from a `get_stats()` snapshot at startup so a
+    /// restart doesn't zero the totals a scraper has already seen.
+    pub fn seed_from_stats(&self, stats: &ReceiptStats) {
+        self.proofs_total
+            .entry("verified".to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .store(stats.verified, Ordering::Relaxed);
+        self.proofs_total
+            .entry("failed".to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .store(stats.failed, Ordering::Relaxed);
+        self.proofs_total
+            .entry("proving".to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .store(stats.proving, Ordering::Relaxed);
+
+        for (model_id, count) in &stats.by_model {
+            self.proofs_by_model
+                .entry(model_id.clone())
+                .or_insert_with(|| AtomicU64::new(0))
+                .store(*count, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_status(&self, status: &str) {
+        self.proofs_total
+            .entry(status.to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_completion(&self, model_id: &str, prove_time_ms: Option<u128>, verify_time_ms: Option<u128>) {
+        self.proofs_by_model
+            .entry(model_id.to_string())
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+        if let Some(p) = prove_time_ms {
+            self.prove_time.observe(p as f64);
+        }
+        if let Some(v) = verify_time_ms {
+            self.verify_time.observe(v as f64);
+        }
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one `prove`/`batch_prove` request outcome, labeled by model_id
+    /// and outcome ("accepted", "validation_rejected", "model_loading",
+    /// "inference_panic", ...). Called at every early-return branch of
+    /// `handlers::prove::run_single_prove`, not just the success path.
+    pub fn record_prove_request(&self, model_id: &str, outcome: &str) {
+        self.prove_requests
+            .entry((model_id.to_string(), outcome.to_string()))
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_inference_duration(&self, duration_ms: f64) {
+        self.inference_time.observe(duration_ms);
+    }
+
+    /// Call when a receipt enters `Proving` status.
+    pub fn inc_proving(&self) {
+        self.proving_gauge.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Call when a receipt leaves `Proving` status for a terminal one.
+    pub fn dec_proving(&self) {
+        self.proving_gauge.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP clawproof_proofs_total Total proof receipts by status.");
+        let _ = writeln!(out, "# TYPE clawproof_proofs_total counter");
+        for entry in self.proofs_total.iter() {
+            let _ = writeln!(
+                out,
+                "clawproof_proofs_total{{status=\"{}\"}} {}",
+                entry.key(),
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_proofs_by_model_total Total completed proofs per model.");
+        let _ = writeln!(out, "# TYPE clawproof_proofs_by_model_total counter");
+        for entry in self.proofs_by_model.iter() {
+            let _ = writeln!(
+                out,
+                "clawproof_proofs_by_model_total{{model_id=\"{}\"}} {}",
+                entry.key(),
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_prove_time_ms Proof generation latency.");
+        let _ = writeln!(out, "# TYPE clawproof_prove_time_ms histogram");
+        self.prove_time.render("clawproof_prove_time_ms", &mut out);
+
+        let _ = writeln!(out, "# HELP clawproof_verify_time_ms Proof verification latency.");
+        let _ = writeln!(out, "# TYPE clawproof_verify_time_ms histogram");
+        self.verify_time.render("clawproof_verify_time_ms", &mut out);
+
+        let _ = writeln!(out, "# HELP clawproof_inference_time_ms Forward-pass inference latency.");
+        let _ = writeln!(out, "# TYPE clawproof_inference_time_ms histogram");
+        self.inference_time.render("clawproof_inference_time_ms", &mut out);
+
+        let _ = writeln!(out, "# HELP clawproof_prove_requests_total prove/batch_prove requests by model_id and outcome.");
+        let _ = writeln!(out, "# TYPE clawproof_prove_requests_total counter");
+        for entry in self.prove_requests.iter() {
+            let (model_id, outcome) = entry.key();
+            let _ = writeln!(
+                out,
+                "clawproof_prove_requests_total{{model_id=\"{}\",outcome=\"{}\"}} {}",
+                model_id,
+                outcome,
+                entry.value().load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(out, "# HELP clawproof_receipts_proving Receipts currently in Proving status.");
+        let _ = writeln!(out, "# TYPE clawproof_receipts_proving gauge");
+        let _ = writeln!(out, "clawproof_receipts_proving {}", self.proving_gauge.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP clawproof_receipt_cache_hits_total Receipt store DashMap cache hits.");
+        let _ = writeln!(out, "# TYPE clawproof_receipt_cache_hits_total counter");
+        let _ = writeln!(out, "clawproof_receipt_cache_hits_total {}", self.cache_hits.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP clawproof_receipt_cache_misses_total Receipt store SQLite fallback reads.");
+        let _ = writeln!(out, "# TYPE clawproof_receipt_cache_misses_total counter");
+        let _ = writeln!(out, "clawproof_receipt_cache_misses_total {}", self.cache_misses.load(Ordering::Relaxed));
+
+        out
+    }
+}
@@ -0,0 +1,106 @@
+//! Job-shaped view over the upload-model-and-prove pipeline
+//! (`handlers::prove_model`). Converting and preprocessing an uploaded model
+//! can both run long before a `Receipt` exists to poll (that only appears
+//! once the actual proof is enqueued), so unlike `handlers::jobs::get_job` —
+//! which treats a `Receipt` as the job record — this is a small dedicated
+//! store keyed by an opaque job id, tracking the stages before proving even
+//! starts.
+//!
+//! Once proving is enqueued, the job record is stamped with the resulting
+//! `receipt_id` and flips to `Proving` — that's this store's terminal state;
+//! from there `ReceiptStore`/`ProofQueue` own the rest of the lifecycle
+//! (`GET /jobs/:receipt_id` or `GET /receipt/:receipt_id` report the
+//! eventual `verified`/`failed`), the same way they do for `/prove`.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelJobStatus {
+    Queued,
+    Converting,
+    Preprocessing,
+    Proving,
+    Failed,
+}
+
+impl ModelJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelJobStatus::Queued => "queued",
+            ModelJobStatus::Converting => "converting",
+            ModelJobStatus::Preprocessing => "preprocessing",
+            ModelJobStatus::Proving => "proving",
+            ModelJobStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct ModelJobRecord {
+    pub status: ModelJobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_id: Option<String>,
+    /// Stable tag from `ApiError::code`, mirroring the `code` field on an
+    /// HTTP error response — set alongside `error` so a poller can branch on
+    /// it the same way a synchronous failure's JSON body would let it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// In-memory job table for `/prove/model`. Unlike `ReceiptStore` this isn't
+/// persisted — a restart mid-upload just loses the job, the same way an
+/// in-flight `/models/upload/begin` resumable session would, and the caller
+/// re-uploads.
+#[derive(Clone, Default)]
+pub struct ModelJobStore {
+    jobs: Arc<DashMap<String, ModelJobRecord>>,
+}
+
+impl ModelJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_queued(&self, job_id: &str) {
+        self.jobs.insert(
+            job_id.to_string(),
+            ModelJobRecord {
+                status: ModelJobStatus::Queued,
+                receipt_id: None,
+                code: None,
+                error: None,
+            },
+        );
+    }
+
+    pub fn set_status(&self, job_id: &str, status: ModelJobStatus) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.status = status;
+        }
+    }
+
+    pub fn set_proving(&self, job_id: &str, receipt_id: &str) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.status = ModelJobStatus::Proving;
+            job.receipt_id = Some(receipt_id.to_string());
+        }
+    }
+
+    pub fn fail(&self, job_id: &str, code: &'static str, error: String) {
+        if let Some(mut job) = self.jobs.get_mut(job_id) {
+            job.status = ModelJobStatus::Failed;
+            job.code = Some(code);
+            job.error = Some(error);
+        }
+    }
+
+    pub fn get(&self, job_id: &str) -> Option<ModelJobRecord> {
+        self.jobs.get(job_id).map(|j| j.clone())
+    }
+}
@@ -0,0 +1,210 @@
+//! Pluggable storage for uploaded ONNX models, fronting `upload_model`'s
+//! `network.onnx`/`model.toml` pair the same way `profile_cache` fronts
+//! agent profile lookups: a `LocalModelStore` preserves the original
+//! `uploaded_models_dir` layout (one directory per `model_id`), while an
+//! `S3ModelStore` lets a model written on one node be read and preprocessed
+//! on another — the gap a single ephemeral/multi-instance deployment's
+//! local disk can't close. Selected by `MODEL_STORE_BACKEND`, same as
+//! `profile_cache_backend`.
+//!
+//! Every file is addressed as `{model_id}/{filename}` — e.g. `network.onnx`
+//! or `model.toml` — under whichever prefix the backend uses.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+/// Filenames `delete` removes for a given `model_id`. Kept as an explicit
+/// list (rather than a prefix listing) since S3 has no "delete directory"
+/// operation and the upload layout only ever writes these two files plus an
+/// optional `vocab.json`.
+const MODEL_FILES: &[&str] = &["network.onnx", "model.toml", "vocab.json"];
+
+/// Storage backend for one uploaded model's files. Implementations must
+/// tolerate `get`/`exists` being called before `put` has ever run for that
+/// `model_id` (e.g. a stale registry entry pointing at a deleted model).
+#[async_trait]
+pub trait ModelStore: Send + Sync {
+    async fn put(&self, model_id: &str, filename: &str, bytes: &[u8]) -> anyhow::Result<()>;
+    async fn get(&self, model_id: &str, filename: &str) -> anyhow::Result<Vec<u8>>;
+    async fn exists(&self, model_id: &str, filename: &str) -> bool;
+    /// Removes every file this store knows about for `model_id`.
+    /// Best-effort — used to clean up after a failed upload, so a file that
+    /// was never written isn't an error.
+    async fn delete(&self, model_id: &str);
+    /// A local filesystem path holding `filename`'s current contents, for
+    /// callers that need to hand a `&Path` to `onnx_tracer::model` or
+    /// `ProverBackend::preprocess` — neither can read from an object store
+    /// directly. `LocalModelStore` returns its real path with no copy;
+    /// `S3ModelStore` downloads to a tempfile first.
+    async fn local_path(&self, model_id: &str, filename: &str) -> anyhow::Result<PathBuf>;
+    /// Makes `filename` resolve the same way for `to_model_id` as it
+    /// already does for `from_model_id`, without the caller re-supplying
+    /// the bytes. Used by `upload_model`'s content-addressed dedup: a
+    /// re-uploaded ONNX file that matches an existing `model_hash` is
+    /// aliased onto the existing blob instead of being written a second
+    /// time. `LocalModelStore` hard-links (falling back to a copy across
+    /// filesystems); `S3ModelStore` issues a server-side `CopyObject` so the
+    /// bytes never have to come back down to this process.
+    async fn alias(&self, from_model_id: &str, to_model_id: &str, filename: &str) -> anyhow::Result<()>;
+}
+
+/// Original behavior: one directory per model under `base_dir`, written and
+/// read with plain filesystem calls. Fine for a single instance with
+/// persistent disk; loses every uploaded model on an ephemeral volume or a
+/// second instance that didn't receive the upload.
+pub struct LocalModelStore {
+    base_dir: PathBuf,
+}
+
+impl LocalModelStore {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self { base_dir }
+    }
+
+    fn path(&self, model_id: &str, filename: &str) -> PathBuf {
+        self.base_dir.join(model_id).join(filename)
+    }
+}
+
+#[async_trait]
+impl ModelStore for LocalModelStore {
+    async fn put(&self, model_id: &str, filename: &str, bytes: &[u8]) -> anyhow::Result<()> {
+        let path = self.path(model_id, filename);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, bytes).await?;
+        Ok(())
+    }
+
+    async fn get(&self, model_id: &str, filename: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.path(model_id, filename)).await?)
+    }
+
+    async fn exists(&self, model_id: &str, filename: &str) -> bool {
+        tokio::fs::metadata(self.path(model_id, filename)).await.is_ok()
+    }
+
+    async fn delete(&self, model_id: &str) {
+        let _ = tokio::fs::remove_dir_all(self.base_dir.join(model_id)).await;
+    }
+
+    async fn local_path(&self, model_id: &str, filename: &str) -> anyhow::Result<PathBuf> {
+        Ok(self.path(model_id, filename))
+    }
+
+    async fn alias(&self, from_model_id: &str, to_model_id: &str, filename: &str) -> anyhow::Result<()> {
+        let from = self.path(from_model_id, filename);
+        let to = self.path(to_model_id, filename);
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        if tokio::fs::hard_link(&from, &to).await.is_err() {
+            // Cross-device link, or the backend doesn't support hard links —
+            // fall back to a plain copy rather than failing the dedup path.
+            tokio::fs::copy(&from, &to).await?;
+        }
+        Ok(())
+    }
+}
+
+/// S3-compatible object-storage backend, following the same
+/// `aws_config`/`force_path_style` setup as `profile_cache::S3ProfileCache`
+/// and `proof_archive::ProofArchive`.
+pub struct S3ModelStore {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3ModelStore {
+    pub async fn new(bucket: String, endpoint: Option<String>, region: Option<String>) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new(region.unwrap_or_else(|| "us-east-1".to_string())));
+        if let Some(endpoint) = endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let sdk_config = loader.load().await;
+        // Path-style addressing is what MinIO/R2 expect; real AWS S3 also
+        // accepts it, so there's no deployment-specific branch needed here.
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(true)
+            .build();
+        Self {
+            client: aws_sdk_s3::Client::from_conf(s3_config),
+            bucket,
+        }
+    }
+
+    fn object_key(model_id: &str, filename: &str) -> String {
+        format!("models/{}/{}", model_id, filename)
+    }
+}
+
+#[async_trait]
+impl ModelStore for S3ModelStore {
+    async fn put(&self, model_id: &str, filename: &str, bytes: &[u8]) -> anyhow::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(model_id, filename))
+            .body(bytes.to_vec().into())
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn get(&self, model_id: &str, filename: &str) -> anyhow::Result<Vec<u8>> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(model_id, filename))
+            .send()
+            .await?;
+        Ok(resp.body.collect().await?.into_bytes().to_vec())
+    }
+
+    async fn exists(&self, model_id: &str, filename: &str) -> bool {
+        self.client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(model_id, filename))
+            .send()
+            .await
+            .is_ok()
+    }
+
+    async fn delete(&self, model_id: &str) {
+        for filename in MODEL_FILES {
+            let _ = self
+                .client
+                .delete_object()
+                .bucket(&self.bucket)
+                .key(Self::object_key(model_id, filename))
+                .send()
+                .await;
+        }
+    }
+
+    /// Downloads `filename` to a tempfile under the OS temp dir, named with
+    /// both `model_id` and `filename` so concurrent preprocessing of
+    /// different models (or files) can't collide.
+    async fn local_path(&self, model_id: &str, filename: &str) -> anyhow::Result<PathBuf> {
+        let bytes = self.get(model_id, filename).await?;
+        let path = std::env::temp_dir().join(format!("clawproof-model-{}-{}", model_id, filename));
+        tokio::fs::write(&path, &bytes).await?;
+        Ok(path)
+    }
+
+    async fn alias(&self, from_model_id: &str, to_model_id: &str, filename: &str) -> anyhow::Result<()> {
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(format!("{}/{}", self.bucket, Self::object_key(from_model_id, filename)))
+            .key(Self::object_key(to_model_id, filename))
+            .send()
+            .await?;
+        Ok(())
+    }
+}
@@ -19,6 +19,48 @@ pub struct FieldSchema {
     pub max: usize,
 }
 
+/// Fixed-point quantization applied when turning a model's input into field
+/// elements. Lets an uploaded model's `model.toml` match whatever scale its
+/// ONNX graph was exported with instead of `build_tfidf_vector`,
+/// `build_onehot_vector`, and the raw-input path each assuming their own
+/// hardcoded convention.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuantizationConfig {
+    /// Multiplier applied to each value before rounding to the nearest `i32`.
+    pub scale: f64,
+    /// Offset added after scaling.
+    pub zero_point: i32,
+    /// Inclusive range the quantized value is clamped to.
+    pub clamp_min: i32,
+    pub clamp_max: i32,
+}
+
+impl Default for QuantizationConfig {
+    fn default() -> Self {
+        // Identity transform — unscaled, unshifted, unclamped.
+        Self { scale: 1.0, zero_point: 0, clamp_min: i32::MIN, clamp_max: i32::MAX }
+    }
+}
+
+impl QuantizationConfig {
+    pub fn apply(&self, value: f64) -> i32 {
+        let scaled = (value * self.scale).round() as i64 + self.zero_point as i64;
+        scaled.clamp(self.clamp_min as i64, self.clamp_max as i64) as i32
+    }
+}
+
+/// Whether a model is available for proving. Set by the background
+/// preprocessing loop after its capacity pre-flight: `Unsupported` means the
+/// auto-estimated `trace_length` exceeded `Config::max_trace_length`, so
+/// preprocessing was skipped rather than attempted.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelStatus {
+    Ready,
+    Unsupported,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelDescriptor {
     pub id: String,
@@ -28,9 +70,24 @@ pub struct ModelDescriptor {
     pub input_dim: usize,
     pub input_shape: Vec<usize>,
     pub labels: Vec<String>,
+    /// Starts at whatever `model.toml` declares (or the default), then gets
+    /// overwritten by the capacity pre-flight's auto-estimated value before
+    /// preprocessing actually runs.
     pub trace_length: usize,
+    pub status: ModelStatus,
+    /// Applied to this model's input vector before it's tensorized — see
+    /// `QuantizationConfig`.
+    pub quantization: QuantizationConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<Vec<FieldSchema>>,
+    /// Commitment to the ONNX file, computed once at upload time so `/prove`
+    /// doesn't have to re-hash the model on every request. `None` for models
+    /// registered before this was tracked, in which case `/prove` falls back
+    /// to hashing on demand.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_hash: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -49,6 +106,8 @@ pub struct ModelToml {
     pub trace_length: usize,
     #[serde(default)]
     pub fields: Vec<FieldToml>,
+    #[serde(default)]
+    pub quantization: QuantizationConfig,
 }
 
 fn default_trace_length() -> usize {
@@ -92,6 +151,10 @@ impl ModelRegistry {
                 "DENIED".to_string(),
             ],
             trace_length: 1 << 14,
+            status: ModelStatus::Ready,
+            quantization: QuantizationConfig::default(),
+            unsupported_reason: None,
+            model_hash: None,
             fields: Some(vec![
                 FieldSchema { name: "budget".to_string(), description: "Budget level".to_string(), min: 0, max: 15 },
                 FieldSchema { name: "trust".to_string(), description: "Trust score".to_string(), min: 0, max: 7 },
@@ -118,6 +181,25 @@ impl ModelRegistry {
         self.models.get(id)
     }
 
+    /// Overwrites a registered model's `trace_length` with the capacity
+    /// pre-flight's auto-estimated value. No-op if the model isn't
+    /// registered (e.g. it was removed between listing and estimating).
+    pub fn set_trace_length(&mut self, id: &str, trace_length: usize) {
+        if let Some(model) = self.models.get_mut(id) {
+            model.trace_length = trace_length;
+        }
+    }
+
+    /// Flags a model as `Unsupported` so `/prove` and preprocessing both
+    /// skip it instead of attempting a proof that would blow past
+    /// `max_trace_length`.
+    pub fn mark_unsupported(&mut self, id: &str, reason: String) {
+        if let Some(model) = self.models.get_mut(id) {
+            model.status = ModelStatus::Unsupported;
+            model.unsupported_reason = Some(reason);
+        }
+    }
+
     pub fn list(&self) -> Vec<&ModelDescriptor> {
         self.order
             .iter()
@@ -171,6 +253,10 @@ impl ModelRegistry {
             input_shape,
             labels: toml_model.labels,
             trace_length: toml_model.trace_length,
+            status: ModelStatus::Ready,
+            quantization: toml_model.quantization,
+            unsupported_reason: None,
+            model_hash: None,
             fields,
         })
     }
@@ -201,3 +287,71 @@ impl ModelRegistry {
         }
     }
 }
+
+/// An S3 PostObject-style signed grant for `POST /models/upload`: the form's
+/// `policy` field base64-decodes to this, and must be accompanied by an
+/// `x-amz-signature` field matching `crypto::sign_upload_policy(secret,
+/// policy_b64)` — checked in constant time via `crypto::constant_time_eq`.
+/// Lets an operator hand out scoped, time-limited upload grants instead of
+/// leaving the endpoint open to any unauthenticated POST.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadPolicy {
+    /// Unix timestamp after which the policy is no longer valid.
+    pub expiration: i64,
+    #[serde(default)]
+    pub conditions: Vec<UploadCondition>,
+}
+
+/// One constraint checked against the fields actually submitted alongside
+/// the policy.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "condition", rename_all = "kebab-case")]
+pub enum UploadCondition {
+    /// The uploaded ONNX file's byte length must fall within `[min, max]`.
+    ContentLengthRange { min: u64, max: u64 },
+    /// The submitted `name` field must start with `prefix`.
+    NamePrefix { prefix: String },
+}
+
+/// What `upload_model` actually submitted, checked against an
+/// `UploadPolicy`'s conditions.
+pub struct UploadAttempt<'a> {
+    pub content_length: u64,
+    pub name: &'a str,
+}
+
+impl UploadPolicy {
+    /// Base64-decodes and JSON-parses `policy_b64` — the caller still has to
+    /// check it against the upload's `x-amz-signature` and `check` it
+    /// against the submitted fields before trusting it.
+    pub fn decode(policy_b64: &str) -> anyhow::Result<UploadPolicy> {
+        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, policy_b64)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Rejects an expired policy or any condition `attempt` fails, returning
+    /// the first violation found.
+    pub fn check(&self, attempt: &UploadAttempt) -> Result<(), String> {
+        if self.expiration < chrono::Utc::now().timestamp() {
+            return Err("Upload policy has expired".to_string());
+        }
+        for condition in &self.conditions {
+            match condition {
+                UploadCondition::ContentLengthRange { min, max } => {
+                    if attempt.content_length < *min || attempt.content_length > *max {
+                        return Err(format!(
+                            "Uploaded file size {} is outside the policy's allowed range [{}, {}]",
+                            attempt.content_length, min, max
+                        ));
+                    }
+                }
+                UploadCondition::NamePrefix { prefix } => {
+                    if !attempt.name.starts_with(prefix.as_str()) {
+                        return Err(format!("name must start with '{}'", prefix));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Minimal reader for NumPy's `.npy` array format and `.npz` (a zip archive
+//! of `.npy` entries), used by `handlers::prove_model` to accept a binary
+//! tensor upload instead of requiring callers to flatten their input into a
+//! JSON array by hand. Only the integer/float/bool dtypes NumPy actually
+//! produces are supported, each cast to `i32` the same way `input_raw`'s
+//! JSON array is already expected to be.
+
+use std::io::Read;
+
+#[derive(Debug)]
+pub struct NpyError(String);
+
+impl std::fmt::Display for NpyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct NpyArray {
+    pub shape: Vec<usize>,
+    pub data: Vec<i32>,
+}
+
+/// Parses `bytes` as either a `.npy` array or a `.npz` archive (by magic
+/// number, not filename) and returns its flattened `i32` data and shape.
+/// For `.npz`, only the first entry is read — callers uploading a single
+/// tensor for `/prove/model` only ever have the one array to offer.
+pub fn parse_tensor(bytes: &[u8]) -> Result<NpyArray, NpyError> {
+    if bytes.starts_with(b"PK\x03\x04") {
+        parse_npz(bytes)
+    } else {
+        parse_npy(bytes)
+    }
+}
+
+fn parse_npz(bytes: &[u8]) -> Result<NpyArray, NpyError> {
+    let reader = std::io::Cursor::new(bytes);
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| NpyError(format!("Not a valid .npz archive: {}", e)))?;
+    if archive.is_empty() {
+        return Err(NpyError("Empty .npz archive".to_string()));
+    }
+    let mut entry = archive.by_index(0).map_err(|e| NpyError(format!("Failed to read .npz entry: {}", e)))?;
+    let mut entry_bytes = Vec::new();
+    entry
+        .read_to_end(&mut entry_bytes)
+        .map_err(|e| NpyError(format!("Failed to read .npz entry: {}", e)))?;
+    parse_npy(&entry_bytes)
+}
+
+fn parse_npy(bytes: &[u8]) -> Result<NpyArray, NpyError> {
+    if bytes.len() < 10 || &bytes[0..6] != b"\x93NUMPY" {
+        return Err(NpyError("Not a valid .npy file (bad magic)".to_string()));
+    }
+    let major = bytes[6];
+    let (header_len, header_start) = if major >= 2 {
+        (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12)
+    } else {
+        (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10)
+    };
+    let header_end = header_start + header_len;
+    if bytes.len() < header_end {
+        return Err(NpyError("Truncated .npy header".to_string()));
+    }
+    let header = std::str::from_utf8(&bytes[header_start..header_end]).map_err(|_| NpyError("Non-UTF8 .npy header".to_string()))?;
+
+    let shape = parse_shape(header)?;
+    let descr = parse_descr(header)?;
+    // A 0-dimensional array (a NumPy scalar) still holds exactly one element.
+    let element_count: usize = if shape.is_empty() { 1 } else { shape.iter().product() };
+    let data = decode_elements(&descr, &bytes[header_end..], element_count)?;
+    Ok(NpyArray { shape, data })
+}
+
+/// Pulls the quoted value following `'key':` out of a `.npy` header dict
+/// literal, e.g. `extract_value(header, "descr")` on
+/// `{'descr': '<i4', 'shape': (2, 3), }` returns `'<i4', 'shape': (2, 3), }`.
+fn extract_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let key_pat = format!("'{}'", key);
+    let key_idx = header.find(&key_pat)?;
+    let after_key = &header[key_idx + key_pat.len()..];
+    let colon_idx = after_key.find(':')?;
+    Some(after_key[colon_idx + 1..].trim_start())
+}
+
+fn parse_descr(header: &str) -> Result<String, NpyError> {
+    let rest = extract_value(header, "descr").ok_or_else(|| NpyError("Missing descr in .npy header".to_string()))?;
+    let rest = rest.trim_start_matches(['\'', '"']);
+    let end = rest.find(['\'', '"']).ok_or_else(|| NpyError("Malformed descr in .npy header".to_string()))?;
+    Ok(rest[..end].to_string())
+}
+
+fn parse_shape(header: &str) -> Result<Vec<usize>, NpyError> {
+    let rest = extract_value(header, "shape").ok_or_else(|| NpyError("Missing shape in .npy header".to_string()))?;
+    let open = rest.find('(').ok_or_else(|| NpyError("Malformed shape in .npy header".to_string()))?;
+    let close = rest[open..].find(')').ok_or_else(|| NpyError("Malformed shape in .npy header".to_string()))? + open;
+    rest[open + 1..close]
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().map_err(|_| NpyError(format!("Invalid shape dimension '{}'", s))))
+        .collect()
+}
+
+/// Decodes `count` elements of `descr`'s dtype (e.g. `<i4`, `>f8`, `|u1`)
+/// out of `data`, rounding floats to the nearest integer — the same lossy
+/// cast `input_raw`'s JSON numbers already go through as `i32`.
+fn decode_elements(descr: &str, data: &[u8], count: usize) -> Result<Vec<i32>, NpyError> {
+    let little_endian = !descr.starts_with('>');
+    let kind = descr.trim_start_matches(['<', '>', '|']);
+    let mut chars = kind.chars();
+    let type_char = chars.next().ok_or_else(|| NpyError(format!("Malformed dtype '{}'", descr)))?;
+    let element_size: usize = chars.as_str().parse().unwrap_or(1);
+
+    if data.len() < count * element_size {
+        return Err(NpyError("Truncated .npy data".to_string()));
+    }
+
+    let mut out = Vec::with_capacity(count);
+    for i in 0..count {
+        let chunk = &data[i * element_size..(i + 1) * element_size];
+        let value = match (type_char, element_size) {
+            ('b', 1) => chunk[0] as i32,
+            ('i', 1) => chunk[0] as i8 as i32,
+            ('u', 1) => chunk[0] as i32,
+            ('i', 2) => read_int(chunk, little_endian) as i16 as i32,
+            ('u', 2) => read_int(chunk, little_endian) as u16 as i32,
+            ('i', 4) => read_int(chunk, little_endian) as i32,
+            ('u', 4) => read_int(chunk, little_endian) as u32 as i32,
+            ('i', 8) => read_int(chunk, little_endian) as i64 as i32,
+            ('f', 4) => f32::from_bits(read_int(chunk, little_endian) as u32).round() as i32,
+            ('f', 8) => f64::from_bits(read_int(chunk, little_endian)).round() as i32,
+            _ => return Err(NpyError(format!("Unsupported .npy dtype '{}'", descr))),
+        };
+        out.push(value);
+    }
+    Ok(out)
+}
+
+fn read_int(chunk: &[u8], little_endian: bool) -> u64 {
+    let mut buf = [0u8; 8];
+    if little_endian {
+        buf[..chunk.len()].copy_from_slice(chunk);
+        u64::from_le_bytes(buf)
+    } else {
+        // Right-align big-endian bytes before widening to u64.
+        buf[8 - chunk.len()..].copy_from_slice(chunk);
+        u64::from_be_bytes(buf)
+    }
+}
@@ -0,0 +1,377 @@
+//! Persistent queue for `upload_model`'s background preprocessing step.
+//! That used to fire `prover::backend_for(...).preprocess` via a bare
+//! `tokio::spawn` — a process restart mid-job, or the blocking task
+//! panicking, left the model registered forever in `"preprocessing"` limbo
+//! with no record and no retry. Now `upload_model` enqueues a job row here
+//! (persisted to the same SQLite database as receipts/`ProofQueue`) and
+//! `spawn_dispatcher` drains it with a bounded worker pool, retrying a
+//! failed run with exponential backoff until the job has been attempted
+//! `max_attempts` times in all, then giving up for good.
+
+use crate::model_store::ModelStore;
+use crate::state::{AppState, BackendPreprocessing, ProverBackendKind};
+
+use dashmap::DashMap;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How long the dispatcher sleeps between polls of an empty queue.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreprocessJobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+impl PreprocessJobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PreprocessJobStatus::Queued => "queued",
+            PreprocessJobStatus::Running => "running",
+            PreprocessJobStatus::Done => "done",
+            PreprocessJobStatus::Failed => "failed",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "running" => PreprocessJobStatus::Running,
+            "done" => PreprocessJobStatus::Done,
+            "failed" => PreprocessJobStatus::Failed,
+            _ => PreprocessJobStatus::Queued,
+        }
+    }
+}
+
+/// `GET /models/:id/status`'s view of a model's preprocessing job — the
+/// thing a client polls instead of guessing from the `"preprocessing"`
+/// string `upload_model` returns at submit time.
+#[derive(Serialize)]
+pub struct PreprocessJobRecord {
+    pub model_id: String,
+    pub status: PreprocessJobStatus,
+    pub attempts: u32,
+    pub max_attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+struct QueuedJob {
+    id: i64,
+    model_id: String,
+    model_hash: String,
+    backend: ProverBackendKind,
+    trace_length: usize,
+    attempts: u32,
+    max_attempts: u32,
+}
+
+/// SQLite-backed FIFO of preprocessing jobs, one row per `model_id` — a
+/// retry updates the row in place rather than inserting a new one, so
+/// `status_for_model` always has exactly one record to report.
+pub struct PreprocessQueue {
+    conn: Arc<Mutex<Connection>>,
+    backoff_base_secs: i64,
+}
+
+impl PreprocessQueue {
+    pub fn new(db_path: &Path, backoff_base_secs: u64) -> anyhow::Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")?;
+        let queue = Self { conn: Arc::new(Mutex::new(conn)), backoff_base_secs: backoff_base_secs as i64 };
+        queue.init()?;
+        Ok(queue)
+    }
+
+    fn init(&self) -> anyhow::Result<()> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS preprocess_jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                model_id TEXT NOT NULL UNIQUE,
+                model_hash TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                trace_length INTEGER NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                max_attempts INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                last_error TEXT,
+                next_attempt_at TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_preprocess_jobs_status ON preprocess_jobs(status, next_attempt_at, id);",
+        )?;
+        Ok(())
+    }
+
+    /// Persist a `queued` job for `model_id` and return immediately —
+    /// `spawn_dispatcher`'s workers pick it up. Called from `upload_model`
+    /// right after the ONNX blob is committed to `AppState::store`.
+    pub fn enqueue(
+        &self,
+        model_id: &str,
+        model_hash: &str,
+        backend: ProverBackendKind,
+        trace_length: usize,
+        max_attempts: u32,
+    ) -> anyhow::Result<()> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO preprocess_jobs (model_id, model_hash, backend, trace_length, attempts, max_attempts, status, last_error, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, 0, ?5, ?6, NULL, ?7, ?7)",
+            rusqlite::params![
+                model_id,
+                model_hash,
+                backend.as_str(),
+                trace_length as i64,
+                max_attempts,
+                PreprocessJobStatus::Queued.as_str(),
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Record a model as already `done` with no dispatcher work needed —
+    /// used by `upload_model`'s content-hash dedup path, which shares an
+    /// existing `BackendPreprocessing` synchronously instead of enqueuing a
+    /// fresh preprocessing run, but should still show up as `done` (not
+    /// absent) to `GET /models/:id/status`.
+    pub fn record_done(&self, model_id: &str, model_hash: &str, backend: ProverBackendKind, trace_length: usize) -> anyhow::Result<()> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO preprocess_jobs (model_id, model_hash, backend, trace_length, attempts, max_attempts, status, last_error, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, 0, 1, ?5, NULL, ?6, ?6)",
+            rusqlite::params![
+                model_id,
+                model_hash,
+                backend.as_str(),
+                trace_length as i64,
+                PreprocessJobStatus::Done.as_str(),
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Atomically claim the oldest `queued` job whose backoff has elapsed,
+    /// flipping it to `running` in the same lock.
+    fn claim_next(&self) -> Option<QueuedJob> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        let now = chrono::Utc::now().to_rfc3339();
+        let claimed: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM preprocess_jobs WHERE status = ?1 AND next_attempt_at <= ?2 ORDER BY id LIMIT 1",
+                rusqlite::params![PreprocessJobStatus::Queued.as_str(), now],
+                |row| row.get(0),
+            )
+            .ok();
+        let id = claimed?;
+        if let Err(e) = conn.execute(
+            "UPDATE preprocess_jobs SET status = ?1 WHERE id = ?2 AND status = ?3",
+            rusqlite::params![PreprocessJobStatus::Running.as_str(), id, PreprocessJobStatus::Queued.as_str()],
+        ) {
+            error!("[clawproof] preprocess_jobs claim update failed: {:?}", e);
+            return None;
+        }
+
+        conn.query_row(
+            "SELECT id, model_id, model_hash, backend, trace_length, attempts, max_attempts FROM preprocess_jobs WHERE id = ?1",
+            rusqlite::params![id],
+            |row| {
+                let backend_str: String = row.get(3)?;
+                let trace_length: i64 = row.get(4)?;
+                let attempts: i64 = row.get(5)?;
+                let max_attempts: i64 = row.get(6)?;
+                Ok(QueuedJob {
+                    id: row.get(0)?,
+                    model_id: row.get(1)?,
+                    model_hash: row.get(2)?,
+                    backend: ProverBackendKind::from_str(&backend_str).unwrap_or_default(),
+                    trace_length: trace_length as usize,
+                    attempts: attempts as u32,
+                    max_attempts: max_attempts as u32,
+                })
+            },
+        )
+        .ok()
+    }
+
+    fn mark_done(&self, job_id: i64) {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        if let Err(e) = conn.execute(
+            "UPDATE preprocess_jobs SET status = ?1, last_error = NULL WHERE id = ?2",
+            rusqlite::params![PreprocessJobStatus::Done.as_str(), job_id],
+        ) {
+            error!("[clawproof] preprocess_jobs done update failed for job {}: {:?}", job_id, e);
+        }
+    }
+
+    /// Requeue with exponential backoff if `attempts` hasn't exhausted
+    /// `max_attempts` yet, otherwise mark the job permanently `failed`.
+    fn mark_failed(&self, job: &QueuedJob, error_message: &str) {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        let attempts = job.attempts + 1;
+        if attempts < job.max_attempts {
+            let backoff_secs = self.backoff_base_secs * (1i64 << job.attempts.min(16));
+            let next_attempt_at = (chrono::Utc::now() + chrono::Duration::seconds(backoff_secs)).to_rfc3339();
+            if let Err(e) = conn.execute(
+                "UPDATE preprocess_jobs SET status = ?1, attempts = ?2, last_error = ?3, next_attempt_at = ?4 WHERE id = ?5",
+                rusqlite::params![PreprocessJobStatus::Queued.as_str(), attempts, error_message, next_attempt_at, job.id],
+            ) {
+                error!("[clawproof] preprocess_jobs retry update failed for job {}: {:?}", job.id, e);
+            }
+        } else if let Err(e) = conn.execute(
+            "UPDATE preprocess_jobs SET status = ?1, attempts = ?2, last_error = ?3 WHERE id = ?4",
+            rusqlite::params![PreprocessJobStatus::Failed.as_str(), attempts, error_message, job.id],
+        ) {
+            error!("[clawproof] preprocess_jobs failure update failed for job {}: {:?}", job.id, e);
+        }
+    }
+
+    /// Flip every `running` job back to `queued` — called once at startup,
+    /// since a `running` row only means that on the *previous* process;
+    /// nothing is still holding it.
+    fn requeue_stuck(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        let now = chrono::Utc::now().to_rfc3339();
+        let count = conn.execute(
+            "UPDATE preprocess_jobs SET status = ?1, next_attempt_at = ?2 WHERE status = ?3",
+            rusqlite::params![PreprocessJobStatus::Queued.as_str(), now, PreprocessJobStatus::Running.as_str()],
+        )?;
+        Ok(count)
+    }
+
+    /// Latest job record for `model_id`, if one has ever been enqueued —
+    /// backs `GET /models/:id/status`.
+    pub fn status_for_model(&self, model_id: &str) -> Option<PreprocessJobRecord> {
+        let conn = self.conn.lock().expect("preprocess_jobs connection lock poisoned");
+        conn.query_row(
+            "SELECT status, attempts, max_attempts, last_error FROM preprocess_jobs WHERE model_id = ?1",
+            rusqlite::params![model_id],
+            |row| {
+                let status_str: String = row.get(0)?;
+                let attempts: i64 = row.get(1)?;
+                let max_attempts: i64 = row.get(2)?;
+                Ok(PreprocessJobRecord {
+                    model_id: model_id.to_string(),
+                    status: PreprocessJobStatus::from_str(&status_str),
+                    attempts: attempts as u32,
+                    max_attempts: max_attempts as u32,
+                    last_error: row.get(3)?,
+                })
+            },
+        )
+        .ok()
+    }
+}
+
+/// The pieces of `AppState` a worker needs to actually run a claimed job.
+#[derive(Clone)]
+pub struct PreprocessDispatcherContext {
+    pub store: Arc<dyn crate::model_store::ModelStore>,
+    pub preprocessing: Arc<DashMap<(String, ProverBackendKind), Arc<BackendPreprocessing>>>,
+    pub model_hash_index: Arc<DashMap<String, String>>,
+}
+
+impl PreprocessDispatcherContext {
+    pub fn from_state(state: &AppState) -> Self {
+        Self {
+            store: state.store.clone(),
+            preprocessing: state.preprocessing.clone(),
+            model_hash_index: state.model_hash_index.clone(),
+        }
+    }
+}
+
+/// Requeue anything left `running` from a previous run, then spawn the
+/// dispatcher loop: claim the oldest due job, acquire a semaphore permit
+/// (bounding how many `prover_preprocess` runs are ever in flight at once
+/// to `concurrency`), and run it, holding the permit until the blocking
+/// preprocessing work actually finishes.
+pub fn spawn_dispatcher(queue: Arc<PreprocessQueue>, concurrency: usize, ctx: PreprocessDispatcherContext) {
+    match queue.requeue_stuck() {
+        Ok(0) => {}
+        Ok(n) => info!("[clawproof] Requeued {} preprocess job(s) left mid-run by a previous process", n),
+        Err(e) => error!("[clawproof] Failed to requeue stuck preprocess jobs: {:?}", e),
+    }
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+    info!("[clawproof] Preprocess dispatcher started (concurrency: {})", concurrency);
+
+    tokio::spawn(async move {
+        loop {
+            let Some(job) = queue.claim_next() else {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            };
+
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("preprocess dispatcher semaphore is never closed");
+            let queue = queue.clone();
+            let ctx = ctx.clone();
+
+            tokio::spawn(async move {
+                let _permit = permit;
+
+                let onnx_path = match ctx.store.local_path(&job.model_id, "network.onnx").await {
+                    Ok(path) => path,
+                    Err(e) => {
+                        error!("[clawproof] Failed to materialize {} for preprocessing: {:?}", job.model_id, e);
+                        queue.mark_failed(&job, &format!("failed to materialize model: {:?}", e));
+                        return;
+                    }
+                };
+
+                info!("[clawproof] Preprocessing uploaded model {} (attempt {}/{})", job.model_id, job.attempts + 1, job.max_attempts);
+                let trace_length = job.trace_length;
+                let backend = job.backend;
+                let result = tokio::task::spawn_blocking(move || {
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        crate::prover::backend_for(backend).preprocess(&onnx_path, trace_length)
+                    }))
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(Ok(preprocessing))) => {
+                        ctx.preprocessing.insert((job.model_id.clone(), job.backend), Arc::new(preprocessing));
+                        ctx.model_hash_index
+                            .entry(job.model_hash.clone())
+                            .or_insert_with(|| job.model_id.clone());
+                        info!("[clawproof] Uploaded model {} preprocessed successfully", job.model_id);
+                        queue.mark_done(job.id);
+                    }
+                    Ok(Ok(Err(e))) => {
+                        warn!("[clawproof] Preprocessing failed for uploaded model {}: {:?}", job.model_id, e);
+                        queue.mark_failed(&job, &format!("{:?}", e));
+                    }
+                    Ok(Err(_)) => {
+                        error!("[clawproof] Preprocessing panicked for uploaded model {}", job.model_id);
+                        queue.mark_failed(&job, "preprocessing task panicked");
+                    }
+                    Err(e) => {
+                        error!("[clawproof] Failed to preprocess uploaded model {}: {:?}", job.model_id, e);
+                        queue.mark_failed(&job, &format!("{:?}", e));
+                    }
+                }
+            });
+        }
+    });
+}
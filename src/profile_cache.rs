@@ -0,0 +1,183 @@
+//! Cache layer sitting in front of every `TrustSource`, so repeated
+//! `agent_lookup` calls for the same agent don't hammer the upstream API.
+//! Mirrors Plume's pluggable replicated-media backend: an in-memory backend
+//! for single-instance deployments, or an S3-compatible one so a fleet of
+//! instances shares a warm cache.
+//!
+//! Results are keyed on `(scheme, agent_name)` and served from cache while
+//! within `ProfileCache`'s TTL. When the TTL has lapsed and the upstream
+//! fetch then fails, the last cached copy is served anyway (marked stale)
+//! rather than failing the whole lookup.
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::trust_source::{RawAgentData, SourceError, TrustSource};
+
+/// A cached `RawAgentData` snapshot plus when it was fetched, serialized as
+/// plain Unix seconds (rather than `SystemTime` directly) so it round-trips
+/// through any backend, including ones like S3 that store raw bytes.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CachedProfile {
+    pub data: RawAgentData,
+    pub fetched_at_unix_secs: u64,
+}
+
+impl CachedProfile {
+    fn fresh(data: RawAgentData) -> Self {
+        Self {
+            data,
+            fetched_at_unix_secs: unix_now(),
+        }
+    }
+
+    fn age_secs(&self) -> u64 {
+        unix_now().saturating_sub(self.fetched_at_unix_secs)
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A storage backend for cached profiles, keyed on `(scheme, agent_name)`.
+#[async_trait]
+pub trait ProfileCacheBackend: Send + Sync {
+    async fn get(&self, scheme: &str, agent: &str) -> Option<CachedProfile>;
+    async fn put(&self, scheme: &str, agent: &str, entry: CachedProfile);
+}
+
+/// Single-process backend — fine for one instance, but cold on every
+/// restart and not shared across a fleet.
+#[derive(Default)]
+pub struct InMemoryProfileCache {
+    entries: dashmap::DashMap<(String, String), CachedProfile>,
+}
+
+impl InMemoryProfileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ProfileCacheBackend for InMemoryProfileCache {
+    async fn get(&self, scheme: &str, agent: &str) -> Option<CachedProfile> {
+        self.entries
+            .get(&(scheme.to_string(), agent.to_string()))
+            .map(|e| e.clone())
+    }
+
+    async fn put(&self, scheme: &str, agent: &str, entry: CachedProfile) {
+        self.entries.insert((scheme.to_string(), agent.to_string()), entry);
+    }
+}
+
+/// S3-compatible backend (AWS S3, MinIO, R2, ...) so multiple instances
+/// share one warm cache instead of each cold-starting its own.
+pub struct S3ProfileCache {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3ProfileCache {
+    pub async fn new(bucket: String, endpoint: Option<String>, region: Option<String>) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new(region.unwrap_or_else(|| "us-east-1".to_string())));
+        if let Some(endpoint) = endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let sdk_config = loader.load().await;
+        // Path-style addressing is what MinIO/R2 expect; real AWS S3 also
+        // accepts it, so there's no deployment-specific branch needed here.
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(true)
+            .build();
+        Self {
+            client: aws_sdk_s3::Client::from_conf(s3_config),
+            bucket,
+        }
+    }
+
+    fn object_key(scheme: &str, agent: &str) -> String {
+        format!("profile-cache/{}/{}.json", scheme, agent)
+    }
+}
+
+#[async_trait]
+impl ProfileCacheBackend for S3ProfileCache {
+    async fn get(&self, scheme: &str, agent: &str) -> Option<CachedProfile> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(scheme, agent))
+            .send()
+            .await
+            .ok()?;
+        let bytes = resp.body.collect().await.ok()?.into_bytes();
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    async fn put(&self, scheme: &str, agent: &str, entry: CachedProfile) {
+        let Ok(bytes) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        let _ = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(scheme, agent))
+            .body(bytes.into())
+            .content_type("application/json")
+            .send()
+            .await;
+    }
+}
+
+/// Fronts a `TrustSource` lookup with the configured backend and TTL.
+pub struct ProfileCache {
+    backend: Arc<dyn ProfileCacheBackend>,
+    ttl_secs: u64,
+}
+
+impl ProfileCache {
+    pub fn new(backend: Arc<dyn ProfileCacheBackend>, ttl_secs: u64) -> Self {
+        Self { backend, ttl_secs }
+    }
+
+    /// Serve `agent` for `scheme` from cache when within the TTL; otherwise
+    /// fetch from `source`, cache the result, and return it. If the fetch
+    /// fails, falls back to the last cached copy — flagged stale — instead
+    /// of failing the lookup outright. Returns `(data, stale)`.
+    pub async fn get_or_fetch(
+        &self,
+        scheme: &str,
+        agent: &str,
+        source: &Arc<dyn TrustSource>,
+    ) -> Result<(RawAgentData, bool), SourceError> {
+        let cached = self.backend.get(scheme, agent).await;
+        if let Some(entry) = &cached {
+            if entry.age_secs() < self.ttl_secs {
+                return Ok((entry.data.clone(), false));
+            }
+        }
+
+        match source.fetch(agent).await {
+            Ok(data) => {
+                self.backend.put(scheme, agent, CachedProfile::fresh(data.clone())).await;
+                Ok((data, false))
+            }
+            Err(e) => match cached {
+                Some(entry) => Ok((entry.data, true)),
+                None => Err(e),
+            },
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Durable off-box home for serialized proof artifacts, fronting the
+//! `proofs_dir` filesystem the same way `profile_cache`'s S3 backend fronts
+//! a `TrustSource` lookup. `proofs_dir` is just a local directory — a lost
+//! disk or wiped volume takes every `.proof`/`.io.json` file with it, and
+//! unlike receipt metadata (durable in SQLite, hot-cached in DashMap) the
+//! proof bytes themselves have no second copy. When `PROOF_ARCHIVE_S3_BUCKET`
+//! is set, every finalized proof is also archived here under a key derived
+//! from its `proof_hash`, alongside a checksum so a corrupted or truncated
+//! download is caught before the bytes are handed to `ark-serialize`.
+//!
+//! Entirely optional: with no bucket configured, `archive`/`fetch` are never
+//! called, so local runs stay filesystem-only.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto;
+
+#[derive(Serialize, Deserialize)]
+struct ArchivedProof {
+    proof_base64: String,
+    program_io_json: String,
+    checksum: String,
+}
+
+pub struct ProofArchive {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl ProofArchive {
+    pub async fn new(bucket: String, endpoint: Option<String>, region: Option<String>) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new(region.unwrap_or_else(|| "us-east-1".to_string())));
+        if let Some(endpoint) = endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let sdk_config = loader.load().await;
+        // Path-style addressing is what MinIO/R2 expect; real AWS S3 also
+        // accepts it, so there's no deployment-specific branch needed here.
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(true)
+            .build();
+        Self {
+            client: aws_sdk_s3::Client::from_conf(s3_config),
+            bucket,
+        }
+    }
+
+    fn object_key(proof_hash: &str) -> String {
+        format!("proof-archive/{}.json", proof_hash)
+    }
+
+    /// Archives `proof_bytes`/`program_io_json` under `proof_hash`, alongside
+    /// a checksum of the proof bytes so `fetch` can detect corruption before
+    /// the caller tries to deserialize a bad SNARK. Best-effort: failures are
+    /// swallowed since the local `proofs_dir` copy is already the source of
+    /// truth at the moment this is called.
+    pub async fn archive(&self, proof_hash: &str, proof_bytes: &[u8], program_io_json: &str) {
+        let entry = ArchivedProof {
+            proof_base64: base64::engine::general_purpose::STANDARD.encode(proof_bytes),
+            program_io_json: program_io_json.to_string(),
+            checksum: crypto::keccak256(proof_bytes),
+        };
+        let Ok(bytes) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        if let Err(e) = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(proof_hash))
+            .body(bytes.into())
+            .content_type("application/json")
+            .send()
+            .await
+        {
+            tracing::warn!("[clawproof] Failed to archive proof {} to object storage: {:?}", proof_hash, e);
+        }
+    }
+
+    /// Fetches the archived `(proof_bytes, program_io_json)` pair for
+    /// `proof_hash`, validating the stored checksum against the downloaded
+    /// proof bytes before returning it. Returns `None` on any failure —
+    /// missing object, decode error, or checksum mismatch — so callers treat
+    /// it the same as "not archived" rather than needing a separate error path.
+    pub async fn fetch(&self, proof_hash: &str) -> Option<(Vec<u8>, String)> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(proof_hash))
+            .send()
+            .await
+            .ok()?;
+        let body = resp.body.collect().await.ok()?.into_bytes();
+        let entry: ArchivedProof = serde_json::from_slice(&body).ok()?;
+        let proof_bytes = base64::engine::general_purpose::STANDARD
+            .decode(&entry.proof_base64)
+            .ok()?;
+        if crypto::keccak256(&proof_bytes) != entry.checksum {
+            tracing::warn!("[clawproof] Proof archive checksum mismatch for {}, discarding download", proof_hash);
+            return None;
+        }
+        Some((proof_bytes, entry.program_io_json))
+    }
+}
+
+/// Reads `{id}.proof`/`{id}.io.json` from `proofs_dir`, falling back to
+/// `archive` (keyed by the receipt's `proof_hash`) when the local copy is
+/// missing — e.g. an evicted disk, or a fresh instance that never produced
+/// this proof locally. A successful fallback is written back to `proofs_dir`
+/// so later reads don't round-trip to the object store again.
+pub async fn load_proof_artifacts(
+    proofs_dir: &std::path::Path,
+    archive: Option<&ProofArchive>,
+    id: &str,
+    proof_hash: Option<&str>,
+) -> Option<(Vec<u8>, String)> {
+    let proof_path = proofs_dir.join(format!("{}.proof", id));
+    let io_path = proofs_dir.join(format!("{}.io.json", id));
+
+    if let (Ok(proof_bytes), Ok(io_json)) = (
+        tokio::fs::read(&proof_path).await,
+        tokio::fs::read_to_string(&io_path).await,
+    ) {
+        return Some((proof_bytes, io_json));
+    }
+
+    let archive = archive?;
+    let proof_hash = proof_hash?;
+    let (proof_bytes, io_json) = archive.fetch(proof_hash).await?;
+
+    if let Err(e) = tokio::fs::write(&proof_path, &proof_bytes).await {
+        tracing::warn!("[clawproof] Failed to cache archived proof {} locally: {:?}", id, e);
+    }
+    if let Err(e) = tokio::fs::write(&io_path, &io_json).await {
+        tracing::warn!("[clawproof] Failed to cache archived program IO {} locally: {:?}", id, e);
+    }
+
+    Some((proof_bytes, io_json))
+}
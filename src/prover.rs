@@ -1,45 +1,152 @@
 use crate::crypto;
+use crate::proof_archive::ProofArchive;
 use crate::receipt::{ReceiptStatus, ReceiptStore};
-use crate::state::PreprocessingCache;
+use crate::state::{BackendPreprocessing, PreprocessingCache, ProgressBroadcaster, ProofProgress, ProofStage, ProverBackendKind, Snark};
 
-use ark_bn254::Fr;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use dashmap::DashMap;
-use jolt_core::poly::commitment::dory::DoryCommitmentScheme;
-use jolt_core::transcripts::KeccakTranscript;
+use k256::ecdsa::SigningKey;
 use onnx_tracer::{model, tensor::Tensor, ProgramIO};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::{error, info};
-use zkml_jolt_core::jolt::JoltSNARK;
+use tracing::{error, info, warn};
 
-#[allow(clippy::upper_case_acronyms)]
-type PCS = DoryCommitmentScheme;
-type Snark = JoltSNARK<Fr, PCS, KeccakTranscript>;
+/// A pluggable proving implementation. `JoltAtlasBackend` wraps the real
+/// `Snark`; `MockBackend` skips proving entirely and is meant for local
+/// development and CI, where minutes of real proving time would otherwise be
+/// spent on every test run. Both produce the same `(proof_bytes,
+/// program_io_json)` shape so `prove_and_verify` doesn't need to branch past
+/// the point where it picks a backend.
+pub trait ProverBackend: Send + Sync {
+    fn kind(&self) -> ProverBackendKind;
+
+    fn preprocess(&self, model_path: &std::path::Path, trace_length: usize) -> anyhow::Result<BackendPreprocessing>;
+
+    fn prove(&self, preprocessing: &BackendPreprocessing, model_path: &std::path::Path, input: &Tensor<i32>) -> anyhow::Result<(Vec<u8>, String)>;
+
+    fn verify(&self, preprocessing: &BackendPreprocessing, proof_bytes: &[u8], program_io_json: &str) -> anyhow::Result<()>;
+}
+
+pub struct JoltAtlasBackend;
+
+impl ProverBackend for JoltAtlasBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::JoltAtlas
+    }
+
+    fn preprocess(&self, model_path: &std::path::Path, trace_length: usize) -> anyhow::Result<BackendPreprocessing> {
+        let model_path = model_path.to_path_buf();
+        let model_fn = || model(&model_path);
+        let prover = Snark::prover_preprocess(model_fn, trace_length);
+        let verifier = (&prover).into();
+        Ok(BackendPreprocessing::JoltAtlas(PreprocessingCache { prover, verifier }))
+    }
+
+    fn prove(&self, preprocessing: &BackendPreprocessing, model_path: &std::path::Path, input: &Tensor<i32>) -> anyhow::Result<(Vec<u8>, String)> {
+        let cache = match preprocessing {
+            BackendPreprocessing::JoltAtlas(cache) => cache,
+            BackendPreprocessing::Mock => anyhow::bail!("JoltAtlasBackend given Mock preprocessing"),
+        };
+        let model_path = model_path.to_path_buf();
+        let prove_fn = || model(&model_path);
+        let (snark, program_io, _debug_info) = Snark::prove(&cache.prover, prove_fn, input);
+
+        let mut proof_bytes = Vec::new();
+        snark.serialize_compressed(&mut proof_bytes)?;
+        let program_io_json = serde_json::to_string(&program_io)?;
+        Ok((proof_bytes, program_io_json))
+    }
+
+    fn verify(&self, preprocessing: &BackendPreprocessing, proof_bytes: &[u8], program_io_json: &str) -> anyhow::Result<()> {
+        let cache = match preprocessing {
+            BackendPreprocessing::JoltAtlas(cache) => cache,
+            BackendPreprocessing::Mock => anyhow::bail!("JoltAtlasBackend given Mock preprocessing"),
+        };
+        let snark = Snark::deserialize_compressed(proof_bytes)?;
+        let program_io: ProgramIO = serde_json::from_str(program_io_json)?;
+        snark
+            .verify(&cache.verifier, program_io, None)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+}
+
+/// Skips the SNARK entirely — "proves" by hashing the model and input
+/// together, so the same input against the same model always round-trips
+/// through `verify`, but nothing here is actually sound. Only meant for
+/// local development and CI.
+pub struct MockBackend;
+
+impl ProverBackend for MockBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::Mock
+    }
+
+    fn preprocess(&self, _model_path: &std::path::Path, _trace_length: usize) -> anyhow::Result<BackendPreprocessing> {
+        Ok(BackendPreprocessing::Mock)
+    }
+
+    fn prove(&self, _preprocessing: &BackendPreprocessing, model_path: &std::path::Path, input: &Tensor<i32>) -> anyhow::Result<(Vec<u8>, String)> {
+        let model_bytes = std::fs::read(model_path)?;
+        let mut stub = model_bytes;
+        for value in input.data() {
+            stub.extend_from_slice(&value.to_le_bytes());
+        }
+        let proof_bytes = crypto::keccak256(&stub).into_bytes();
+        let program_io_json = serde_json::to_string(&serde_json::json!({
+            "mock": true,
+            "digest": crypto::keccak256(&proof_bytes),
+        }))?;
+        Ok((proof_bytes, program_io_json))
+    }
+
+    fn verify(&self, _preprocessing: &BackendPreprocessing, proof_bytes: &[u8], program_io_json: &str) -> anyhow::Result<()> {
+        let parsed: serde_json::Value = serde_json::from_str(program_io_json)?;
+        let expected = crypto::keccak256(proof_bytes);
+        if parsed["digest"].as_str() != Some(expected.as_str()) {
+            anyhow::bail!("mock proof digest mismatch");
+        }
+        Ok(())
+    }
+}
+
+pub fn backend_for(kind: ProverBackendKind) -> Box<dyn ProverBackend> {
+    match kind {
+        ProverBackendKind::JoltAtlas => Box::new(JoltAtlasBackend),
+        ProverBackendKind::Mock => Box::new(MockBackend),
+    }
+}
 
 pub fn prove_and_verify(
     receipt_id: String,
     receipt_store: ReceiptStore,
-    preprocessing_map: Arc<DashMap<String, PreprocessingCache>>,
+    progress: ProgressBroadcaster,
+    preprocessing_map: Arc<DashMap<(String, ProverBackendKind), Arc<BackendPreprocessing>>>,
     model_id: String,
+    backend_kind: ProverBackendKind,
     models_dir: PathBuf,
     uploaded_models_dir: PathBuf,
+    proofs_dir: PathBuf,
     input_tensor: Tensor<i32>,
     webhook_url: Option<String>,
-) {
+    webhook_signing_secret: Option<String>,
+    attestation_key: Option<Arc<SigningKey>>,
+    proof_archive: Option<Arc<ProofArchive>>,
+) -> tokio::task::JoinHandle<()> {
     tokio::task::spawn_blocking(move || {
         let total_start = Instant::now();
+        let backend = backend_for(backend_kind);
 
-        let preprocessing_ref = match preprocessing_map.get(&model_id) {
+        let preprocessing_ref = match preprocessing_map.get(&(model_id.clone(), backend_kind)) {
             Some(p) => p,
             None => {
-                error!("[clawproof] No preprocessing found for model {}", model_id);
+                error!("[clawproof] No preprocessing found for model {} backend {:?}", model_id, backend_kind);
                 receipt_store.update(&receipt_id, |r| {
                     r.status = ReceiptStatus::Failed;
                     r.error = Some("No preprocessing available".to_string());
                     r.completed_at = Some(chrono::Utc::now());
                 });
+                progress.publish(&receipt_id, ProofProgress::failed(None, "No preprocessing available".to_string()));
                 return;
             }
         };
@@ -49,6 +156,10 @@ pub fn prove_and_verify(
             "[clawproof] Starting proof generation for receipt {}",
             receipt_id
         );
+        progress.publish(
+            &receipt_id,
+            ProofProgress::new(ProofStage::WitnessGeneration, Some(10), Some(total_start.elapsed().as_millis())),
+        );
         let prove_start = Instant::now();
 
         let model_path = {
@@ -59,92 +170,55 @@ pub fn prove_and_verify(
                 uploaded_models_dir.join(&model_id).join("network.onnx")
             }
         };
-        let model_path_for_prove = model_path.clone();
-        let prove_fn = || model(&model_path_for_prove);
 
-        let (snark, program_io, _debug_info) =
-            Snark::prove(&preprocessing_ref.prover, prove_fn, &input_tensor);
-
-        let prove_time = prove_start.elapsed();
-        info!(
-            "[clawproof] Proof generated in {}ms for receipt {}",
-            prove_time.as_millis(),
-            receipt_id
+        // Proving and witness generation happen in one backend call, so
+        // neither stage is timed on its own: `witness_generation` above and
+        // `proving` here are both published before that call starts.
+        progress.publish(
+            &receipt_id,
+            ProofProgress::new(ProofStage::Proving, Some(30), Some(total_start.elapsed().as_millis())),
         );
-
-        // --- Serialize proof ---
-        let mut proof_bytes = Vec::new();
-        if let Err(e) = snark.serialize_compressed(&mut proof_bytes) {
-            error!("[clawproof] Proof serialization failed: {:?}", e);
-            receipt_store.update(&receipt_id, |r| {
-                r.status = ReceiptStatus::Failed;
-                r.error = Some("Proof generation failed".to_string());
-                r.completed_at = Some(chrono::Utc::now());
-            });
-            return;
-        }
-
-        let proof_hash = crypto::keccak256(&proof_bytes);
-        let proof_size = proof_bytes.len();
-
-        info!(
-            "[clawproof] Proof serialized: {} bytes, hash: {}...",
-            proof_size,
-            &proof_hash[..10]
-        );
-
-        // --- Serialize ProgramIO for verification ---
-        let program_io_json = match serde_json::to_string(&program_io) {
-            Ok(j) => j,
+        let (proof_bytes, program_io_json) = match backend.prove(&preprocessing_ref, &model_path, &input_tensor) {
+            Ok(result) => result,
             Err(e) => {
-                error!("[clawproof] ProgramIO serialization failed: {:?}", e);
+                error!("[clawproof] Proof generation failed: {:?}", e);
                 receipt_store.update(&receipt_id, |r| {
                     r.status = ReceiptStatus::Failed;
                     r.error = Some("Proof generation failed".to_string());
                     r.completed_at = Some(chrono::Utc::now());
                 });
+                progress.publish(
+                    &receipt_id,
+                    ProofProgress::failed(Some(total_start.elapsed().as_millis()), "Proof generation failed".to_string()),
+                );
                 return;
             }
         };
 
+        let prove_time = prove_start.elapsed();
+        let proof_hash = crypto::keccak256(&proof_bytes);
+        let proof_size = proof_bytes.len();
+
+        info!(
+            "[clawproof] Proof generated in {}ms for receipt {}: {} bytes, hash: {}...",
+            prove_time.as_millis(),
+            receipt_id,
+            proof_size,
+            &proof_hash[..10]
+        );
+
         // --- Verify ---
         info!(
             "[clawproof] Starting verification for receipt {}",
             receipt_id
         );
+        progress.publish(
+            &receipt_id,
+            ProofProgress::new(ProofStage::Verifying, Some(70), Some(total_start.elapsed().as_millis())),
+        );
         let verify_start = Instant::now();
 
-        let deserialized_snark: Snark =
-            match Snark::deserialize_compressed(proof_bytes.as_slice()) {
-                Ok(s) => s,
-                Err(e) => {
-                    error!(
-                        "[clawproof] Proof deserialization failed: {:?}",
-                        e
-                    );
-                    receipt_store.update(&receipt_id, |r| {
-                        r.status = ReceiptStatus::Failed;
-                        r.error = Some("Proof verification failed".to_string());
-                        r.completed_at = Some(chrono::Utc::now());
-                    });
-                    return;
-                }
-            };
-
-        let deserialized_io: ProgramIO = match serde_json::from_str(&program_io_json) {
-            Ok(io) => io,
-            Err(e) => {
-                error!("[clawproof] ProgramIO deserialization failed: {:?}", e);
-                receipt_store.update(&receipt_id, |r| {
-                    r.status = ReceiptStatus::Failed;
-                    r.error = Some("Proof verification failed".to_string());
-                    r.completed_at = Some(chrono::Utc::now());
-                });
-                return;
-            }
-        };
-
-        match deserialized_snark.verify(&preprocessing_ref.verifier, deserialized_io, None) {
+        match backend.verify(&preprocessing_ref, &proof_bytes, &program_io_json) {
             Ok(()) => {
                 let verify_time = verify_start.elapsed();
                 info!(
@@ -154,18 +228,71 @@ pub fn prove_and_verify(
                     total_start.elapsed().as_millis()
                 );
 
+                let program_io_hash = crypto::keccak256(program_io_json.as_bytes());
+
+                // Persist the raw proof bytes and program I/O alongside the
+                // receipt so `GET /receipt/:id/bundle` can hand a third
+                // party everything needed to re-verify offline — the
+                // receipt itself only stores hashes, not the proof blob.
+                if let Err(e) = std::fs::create_dir_all(&proofs_dir) {
+                    warn!("[clawproof] Failed to create proofs dir: {:?}", e);
+                } else {
+                    if let Err(e) = std::fs::write(proofs_dir.join(format!("{}.proof", receipt_id)), &proof_bytes) {
+                        warn!("[clawproof] Failed to persist proof bytes for {}: {:?}", receipt_id, e);
+                    }
+                    if let Err(e) = std::fs::write(proofs_dir.join(format!("{}.io.json", receipt_id)), &program_io_json) {
+                        warn!("[clawproof] Failed to persist program IO for {}: {:?}", receipt_id, e);
+                    }
+                }
+
+                // Best-effort durable copy — `proofs_dir` above is already
+                // the source of truth the moment this finishes, so archival
+                // runs fire-and-forget the same way `fire_webhook` does.
+                if let Some(archive) = proof_archive.clone() {
+                    let archive_proof_hash = proof_hash.clone();
+                    let archive_proof_bytes = proof_bytes.clone();
+                    let archive_program_io_json = program_io_json.clone();
+                    tokio::runtime::Handle::current().spawn(async move {
+                        archive.archive(&archive_proof_hash, &archive_proof_bytes, &archive_program_io_json).await;
+                    });
+                }
+
+                let attestation = match (attestation_key.as_deref(), receipt_store.get(&receipt_id)) {
+                    (Some(key), Some(receipt)) => crypto::sign_attestation(
+                        key,
+                        &receipt.model_hash,
+                        &receipt.input_hash,
+                        &proof_hash,
+                        &program_io_hash,
+                    )
+                    .map_err(|e| warn!("[clawproof] attestation signing failed: {:?}", e))
+                    .ok(),
+                    _ => None,
+                };
+
                 receipt_store.update(&receipt_id, |r| {
                     r.status = ReceiptStatus::Verified;
-                    r.proof_hash = Some(proof_hash);
+                    r.proof_hash = Some(proof_hash.clone());
                     r.proof_size = Some(proof_size);
                     r.prove_time_ms = Some(prove_time.as_millis());
                     r.verify_time_ms = Some(verify_time.as_millis());
                     r.completed_at = Some(chrono::Utc::now());
+                    r.attestation = attestation;
                 });
+                progress.publish(
+                    &receipt_id,
+                    ProofProgress::done(
+                        Some(total_start.elapsed().as_millis()),
+                        proof_hash,
+                        proof_size,
+                        prove_time.as_millis(),
+                        verify_time.as_millis(),
+                    ),
+                );
 
                 // Fire webhook if provided
-                if let Some(url) = webhook_url {
-                    fire_webhook(&receipt_store, &receipt_id, &url);
+                if let Some(url) = webhook_url.clone() {
+                    fire_webhook(&receipt_store, &receipt_id, &url, webhook_signing_secret.clone());
                 }
             }
             Err(e) => {
@@ -175,39 +302,168 @@ pub fn prove_and_verify(
                     r.error = Some("Proof verification failed".to_string());
                     r.completed_at = Some(chrono::Utc::now());
                 });
+                progress.publish(
+                    &receipt_id,
+                    ProofProgress::failed(
+                        Some(total_start.elapsed().as_millis()),
+                        "Proof verification failed".to_string(),
+                    ),
+                );
 
                 // Fire webhook on failure too
                 if let Some(url) = webhook_url {
-                    fire_webhook(&receipt_store, &receipt_id, &url);
+                    fire_webhook(&receipt_store, &receipt_id, &url, webhook_signing_secret);
                 }
             }
         }
     });
 }
 
-fn fire_webhook(receipt_store: &ReceiptStore, receipt_id: &str, url: &str) {
+/// Result of a successful `aggregate_proofs` call.
+pub struct AggregateOutcome {
+    pub merkle_root: String,
+    pub leaf_hashes: Vec<String>,
+}
+
+/// Re-verifies each member receipt's persisted proof and folds its
+/// `(model_hash, input_hash, output_hash)` leaf into a Merkle root. Runs
+/// synchronously (SNARK verification is CPU-bound) — callers should invoke
+/// this from `spawn_blocking`, the same way `prove_and_verify` does its own
+/// proving work. Every member must already be `Verified` and must still have
+/// its `.proof`/`.io.json` artifacts on disk; the first member that fails
+/// either check aborts the whole aggregate rather than silently dropping it.
+pub fn aggregate_proofs(
+    receipt_ids: &[String],
+    receipt_store: &ReceiptStore,
+    preprocessing_map: &DashMap<(String, ProverBackendKind), Arc<BackendPreprocessing>>,
+    proofs_dir: &std::path::Path,
+) -> Result<AggregateOutcome, String> {
+    let mut leaf_hashes = Vec::with_capacity(receipt_ids.len());
+    for id in receipt_ids {
+        let receipt = receipt_store
+            .get(id)
+            .ok_or_else(|| format!("receipt {} not found", id))?;
+        if receipt.status != ReceiptStatus::Verified {
+            return Err(format!("receipt {} has not been verified", id));
+        }
+
+        let backend_kind = ProverBackendKind::from_str(&receipt.backend).unwrap_or_default();
+        let preprocessing_ref = preprocessing_map
+            .get(&(receipt.model_id.clone(), backend_kind))
+            .ok_or_else(|| format!("no preprocessing available for model {}", receipt.model_id))?;
+
+        let proof_bytes = std::fs::read(proofs_dir.join(format!("{}.proof", id)))
+            .map_err(|e| format!("failed to read proof artifact for {}: {:?}", id, e))?;
+        let io_json = std::fs::read_to_string(proofs_dir.join(format!("{}.io.json", id)))
+            .map_err(|e| format!("failed to read program IO for {}: {:?}", id, e))?;
+
+        let backend = backend_for(backend_kind);
+        backend
+            .verify(&preprocessing_ref, &proof_bytes, &io_json)
+            .map_err(|e| format!("member proof {} failed re-verification: {:?}", id, e))?;
+
+        leaf_hashes.push(crypto::keccak256(
+            format!("{}{}{}", receipt.model_hash, receipt.input_hash, receipt.output_hash).as_bytes(),
+        ));
+    }
+
+    let merkle_root = merkle_root(&leaf_hashes);
+    Ok(AggregateOutcome { merkle_root, leaf_hashes })
+}
+
+/// Folds a list of leaf hashes into a single root by repeatedly hashing
+/// adjacent pairs, duplicating the last leaf at each level that has an odd
+/// count — the standard unbalanced-tree convention.
+fn merkle_root(leaves: &[String]) -> String {
+    if leaves.is_empty() {
+        return crypto::keccak256(&[]);
+    }
+    let mut level: Vec<String> = leaves.to_vec();
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        for pair in level.chunks(2) {
+            let combined = if pair.len() == 2 {
+                format!("{}{}", pair[0], pair[1])
+            } else {
+                format!("{}{}", pair[0], pair[0])
+            };
+            next.push(crypto::keccak256(combined.as_bytes()));
+        }
+        level = next;
+    }
+    level.into_iter().next().expect("non-empty level always has a root")
+}
+
+fn fire_webhook(receipt_store: &ReceiptStore, receipt_id: &str, url: &str, signing_secret: Option<String>) {
     if let Some(receipt) = receipt_store.get(receipt_id) {
-        let url = url.to_string();
-        let handle = tokio::runtime::Handle::current();
-        handle.spawn(async move {
-            let client = reqwest::Client::new();
-            let result = client.post(&url).json(&receipt).send().await;
-            match result {
-                Ok(resp) => {
-                    info!(
-                        "[clawproof] Webhook sent to {}, status: {}",
-                        url,
-                        resp.status()
-                    );
-                }
-                Err(e) => {
-                    error!("[clawproof] Webhook failed: {:?}, retrying in 5s", e);
-                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                    if let Err(e2) = client.post(&url).json(&receipt).send().await {
-                        error!("[clawproof] Webhook retry failed: {:?}", e2);
-                    }
-                }
-            }
+        let receipt_id = receipt_id.to_string();
+        let receipt_store = receipt_store.clone();
+        fire_webhook_payload(url, &receipt, signing_secret, move |message| {
+            receipt_store.set_webhook_error(&receipt_id, message);
         });
     }
 }
+
+/// Fire-and-forget an HMAC-signed webhook POST of `payload`, retrying with
+/// `retry::retry_send` and routing the connection through
+/// `ssrf::guarded_client()` the same way `fire_webhook` does. `on_error` is
+/// called with a diagnostic message if delivery ultimately fails, so a
+/// caller that has somewhere durable to record it (a receipt, a job) can —
+/// unlike `fire_webhook`, this isn't tied to `ReceiptStore` at all, for
+/// callers (`model_jobs`) that fire before a receipt exists yet.
+pub fn fire_webhook_payload<T, F>(url: &str, payload: &T, signing_secret: Option<String>, on_error: F)
+where
+    T: serde::Serialize,
+    F: FnOnce(String) + Send + 'static,
+{
+    let url = url.to_string();
+    let body = match serde_json::to_vec(payload) {
+        Ok(body) => body,
+        Err(e) => {
+            error!("[clawproof] Failed to serialize webhook payload for {}: {:?}", url, e);
+            return;
+        }
+    };
+    let handle = tokio::runtime::Handle::current();
+    handle.spawn(async move {
+        // Signed once up front — the request has to be rebuilt on every
+        // retry attempt (reqwest's builder isn't reusable), but the
+        // signature over the same body bytes doesn't change between
+        // attempts.
+        let signature = signing_secret
+            .as_deref()
+            .map(|secret| crypto::hmac_sha256_hex(secret, &body));
+
+        let client = crate::ssrf::guarded_client();
+        let result = crate::retry::retry_send(crate::retry::DEFAULT_MAX_RETRIES, || {
+            let mut req = client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(sig) = &signature {
+                req = req.header("X-Clawproof-Signature", format!("sha256={}", sig));
+            }
+            req.send()
+        })
+        .await;
+        match result {
+            Ok(resp) => {
+                info!(
+                    "[clawproof] Webhook sent to {}, status: {}",
+                    url,
+                    resp.status()
+                );
+            }
+            Err(crate::retry::RetryError::Exhausted) => {
+                let message = format!("Webhook delivery to {} exhausted retries", url);
+                error!("[clawproof] {}", message);
+                on_error(message);
+            }
+            Err(crate::retry::RetryError::Terminal(e)) => {
+                let message = format!("Webhook delivery to {} failed: {:?}", url, e);
+                error!("[clawproof] {}", message);
+                on_error(message);
+            }
+        }
+    });
+}
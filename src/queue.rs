@@ -0,0 +1,273 @@
+//! Persistent proof queue. `handlers::prove`/`handlers::batch` used to fire
+//! `prover::prove_and_verify` as an unbounded background task per request —
+//! a burst could spawn unboundedly many concurrent SNARK proving runs, and
+//! anything still in flight at a restart was simply lost. Now they enqueue a
+//! job row here (persisted to the same SQLite database as receipts) and
+//! return immediately; `spawn_dispatcher` runs a pool of workers bounded by
+//! a `tokio::sync::Semaphore` that drains it, sized by `PROVE_CONCURRENCY`.
+
+use crate::proof_archive::ProofArchive;
+use crate::prover;
+use crate::receipt::ReceiptStore;
+use crate::state::{BackendPreprocessing, ProgressBroadcaster, ProverBackendKind};
+
+use dashmap::DashMap;
+use k256::ecdsa::SigningKey;
+use onnx_tracer::tensor::Tensor;
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How long the dispatcher sleeps between polls of an empty queue.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum JobStatus {
+    Queued,
+    Proving,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Proving => "proving",
+        }
+    }
+}
+
+/// A queued proving run, with enough of `ProveRequest` persisted to
+/// reconstruct the `Tensor<i32>` `prover::prove_and_verify` needs.
+struct QueuedJob {
+    id: i64,
+    receipt_id: String,
+    model_id: String,
+    backend: ProverBackendKind,
+    input_shape: Vec<usize>,
+    input_data: Vec<i32>,
+    webhook_url: Option<String>,
+}
+
+/// SQLite-backed FIFO of proving jobs, stored in the same database file as
+/// `ReceiptStore` (its own connection — SQLite's WAL mode is built for
+/// exactly this, multiple connections to one file).
+pub struct ProofQueue {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ProofQueue {
+    pub fn new(db_path: &Path) -> anyhow::Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")?;
+        let queue = Self { conn: Arc::new(Mutex::new(conn)) };
+        queue.init()?;
+        Ok(queue)
+    }
+
+    fn init(&self) -> anyhow::Result<()> {
+        let conn = self.conn.lock().expect("proof_jobs connection lock poisoned");
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS proof_jobs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                receipt_id TEXT NOT NULL,
+                model_id TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                input_shape_json TEXT NOT NULL,
+                input_data_json TEXT NOT NULL,
+                webhook_url TEXT,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_proof_jobs_status ON proof_jobs(status, id);",
+        )?;
+        Ok(())
+    }
+
+    /// Persist a job and return immediately — `spawn_dispatcher`'s workers
+    /// pick it up. Called from `handlers::prove`/`handlers::batch` after the
+    /// receipt itself has already been inserted as `Proving`. `input_shape`/
+    /// `input_data` are the same pair `Tensor::new` was built from, so
+    /// `claim_next` can rebuild the identical tensor without needing to know
+    /// anything about `onnx_tracer::tensor::Tensor`'s internals.
+    pub fn enqueue(
+        &self,
+        receipt_id: &str,
+        model_id: &str,
+        backend: ProverBackendKind,
+        input_shape: &[usize],
+        input_data: &[i32],
+        webhook_url: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let input_shape_json = serde_json::to_string(input_shape)?;
+        let input_data_json = serde_json::to_string(input_data)?;
+        let conn = self.conn.lock().expect("proof_jobs connection lock poisoned");
+        conn.execute(
+            "INSERT INTO proof_jobs (receipt_id, model_id, backend, input_shape_json, input_data_json, webhook_url, status, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                receipt_id,
+                model_id,
+                backend.as_str(),
+                input_shape_json,
+                input_data_json,
+                webhook_url,
+                JobStatus::Queued.as_str(),
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Atomically claim the oldest `queued` job, flipping it to `proving` in
+    /// the same lock so a second dispatcher (this server only ever runs
+    /// one, but the invariant should hold regardless) can't claim it too.
+    fn claim_next(&self) -> Option<QueuedJob> {
+        let conn = self.conn.lock().expect("proof_jobs connection lock poisoned");
+        let claimed: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM proof_jobs WHERE status = ?1 ORDER BY id LIMIT 1",
+                rusqlite::params![JobStatus::Queued.as_str()],
+                |row| row.get(0),
+            )
+            .ok();
+        let id = claimed?;
+        if let Err(e) = conn.execute(
+            "UPDATE proof_jobs SET status = ?1 WHERE id = ?2 AND status = ?3",
+            rusqlite::params![JobStatus::Proving.as_str(), id, JobStatus::Queued.as_str()],
+        ) {
+            error!("[clawproof] proof_jobs claim update failed: {:?}", e);
+            return None;
+        }
+
+        conn.query_row(
+            "SELECT id, receipt_id, model_id, backend, input_shape_json, input_data_json, webhook_url FROM proof_jobs WHERE id = ?1",
+            rusqlite::params![id],
+            |row| {
+                let backend_str: String = row.get(3)?;
+                let input_shape_json: String = row.get(4)?;
+                let input_data_json: String = row.get(5)?;
+                Ok(QueuedJob {
+                    id: row.get(0)?,
+                    receipt_id: row.get(1)?,
+                    model_id: row.get(2)?,
+                    backend: ProverBackendKind::from_str(&backend_str).unwrap_or_default(),
+                    input_shape: serde_json::from_str(&input_shape_json).unwrap_or_default(),
+                    input_data: serde_json::from_str(&input_data_json).unwrap_or_default(),
+                    webhook_url: row.get(6)?,
+                })
+            },
+        )
+        .ok()
+    }
+
+    /// Drop a finished job's row — the receipt it drove already carries the
+    /// final `Verified`/`Failed` state, so nothing is lost by not keeping a
+    /// record here too.
+    fn mark_done(&self, job_id: i64) {
+        let conn = self.conn.lock().expect("proof_jobs connection lock poisoned");
+        if let Err(e) = conn.execute("DELETE FROM proof_jobs WHERE id = ?1", rusqlite::params![job_id]) {
+            error!("[clawproof] proof_jobs delete failed for job {}: {:?}", job_id, e);
+        }
+    }
+
+    /// Flip every `proving` job back to `queued` — called once at startup,
+    /// since a `proving` row only means that on the *previous* run; nothing
+    /// is still holding it.
+    fn requeue_stuck(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.lock().expect("proof_jobs connection lock poisoned");
+        let count = conn.execute(
+            "UPDATE proof_jobs SET status = ?1 WHERE status = ?2",
+            rusqlite::params![JobStatus::Queued.as_str(), JobStatus::Proving.as_str()],
+        )?;
+        Ok(count)
+    }
+}
+
+/// Everything a worker needs to actually run a claimed job — the pieces of
+/// `AppState`/`Config` that don't change per job, bundled so
+/// `spawn_dispatcher` doesn't take a dozen positional arguments.
+#[derive(Clone)]
+pub struct DispatcherContext {
+    pub receipt_store: ReceiptStore,
+    pub progress: ProgressBroadcaster,
+    pub preprocessing: Arc<DashMap<(String, ProverBackendKind), Arc<BackendPreprocessing>>>,
+    pub models_dir: PathBuf,
+    pub uploaded_models_dir: PathBuf,
+    pub proofs_dir: PathBuf,
+    pub webhook_signing_secret: Option<String>,
+    pub attestation_key: Option<Arc<SigningKey>>,
+    pub proof_archive: Option<Arc<ProofArchive>>,
+}
+
+/// Requeue anything left `proving` from a previous run, then spawn the
+/// dispatcher loop: claim the oldest queued job, acquire a semaphore permit
+/// (bounding how many proving runs are ever in flight at once to
+/// `concurrency`), and hand it to `prover::prove_and_verify`, holding the
+/// permit until that job's blocking proof/verify work actually finishes.
+pub fn spawn_dispatcher(queue: Arc<ProofQueue>, concurrency: usize, ctx: DispatcherContext) {
+    match queue.requeue_stuck() {
+        Ok(0) => {}
+        Ok(n) => info!("[clawproof] Requeued {} proof job(s) left mid-run by a previous process", n),
+        Err(e) => error!("[clawproof] Failed to requeue stuck proof jobs: {:?}", e),
+    }
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+    info!("[clawproof] Proof dispatcher started (concurrency: {})", concurrency);
+
+    tokio::spawn(async move {
+        loop {
+            let Some(job) = queue.claim_next() else {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            };
+
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("proof dispatcher semaphore is never closed");
+            let queue = queue.clone();
+            let ctx = ctx.clone();
+
+            tokio::spawn(async move {
+                let _permit = permit;
+                let input_tensor = match Tensor::new(Some(&job.input_data), &job.input_shape) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        warn!(
+                            "[clawproof] Dropping proof job {} for receipt {}: failed to rebuild input tensor: {:?}",
+                            job.id, job.receipt_id, e
+                        );
+                        queue.mark_done(job.id);
+                        return;
+                    }
+                };
+
+                let handle = prover::prove_and_verify(
+                    job.receipt_id.clone(),
+                    ctx.receipt_store,
+                    ctx.progress,
+                    ctx.preprocessing,
+                    job.model_id,
+                    job.backend,
+                    ctx.models_dir,
+                    ctx.uploaded_models_dir,
+                    ctx.proofs_dir,
+                    input_tensor,
+                    job.webhook_url,
+                    ctx.webhook_signing_secret,
+                    ctx.attestation_key,
+                    ctx.proof_archive,
+                );
+                if let Err(e) = handle.await {
+                    error!("[clawproof] Proof task panicked for receipt {}: {:?}", job.receipt_id, e);
+                }
+                queue.mark_done(job.id);
+            });
+        }
+    });
+}
@@ -0,0 +1,183 @@
+//! Per-subject token-bucket rate limiting for `/prove`, `/prove/batch`,
+//! `/models/upload`, and `/prove/model` — replacing the global
+//! `tower::limit::RateLimitLayer` those routes used before this existed,
+//! which counted every caller against one shared ceiling and let a single
+//! noisy client starve everyone else.
+//!
+//! Each route gets its own [`RateLimiter`] (a `DashMap<String, Bucket>`) so
+//! exhausting one route's bucket doesn't touch another's. A bucket is keyed
+//! on the caller's JWT `sub` claim when [`auth::require_auth`](crate::auth)
+//! ran first and stashed [`Claims`] in request extensions; otherwise it
+//! falls back to the client's IP, bucketed at the `free` tier. Capacity and
+//! refill rate per tier come from [`RateLimitConfig`].
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use dashmap::DashMap;
+
+use crate::auth::Claims;
+use crate::config::RateLimitConfig;
+use crate::handlers::prove::ErrorResponse;
+use crate::state::AppState;
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A single route's buckets, one per subject key. Cloning shares the
+/// underlying map (cheap `Arc` clone), same as `AppState`'s other shared
+/// caches.
+#[derive(Clone)]
+pub struct RateLimiter {
+    buckets: Arc<DashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self {
+            buckets: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Refills `key`'s bucket for elapsed time, then tries to deduct one
+    /// token. `Ok(())` means the request may proceed; `Err(retry_after_secs)`
+    /// carries how long until a token will next be available.
+    fn check(&self, key: &str, capacity: f64, refill_per_sec: f64) -> Result<(), f64> {
+        let now = Instant::now();
+        let mut bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| Bucket {
+                tokens: capacity,
+                last_refill: now,
+            });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if refill_per_sec > 0.0 {
+            Err((1.0 - bucket.tokens) / refill_per_sec)
+        } else {
+            Err(f64::MAX)
+        }
+    }
+
+    /// Spawn the background task that evicts buckets idle for longer than
+    /// `ttl` on `interval` — same role `ReceiptStore::spawn_scrub_task` and
+    /// `upload_resumable::spawn_pending_upload_reaper` play for their own
+    /// unbounded maps. A bucket keyed on a free-tier client IP is otherwise
+    /// never removed, so a large number of distinct source IPs (trivial with
+    /// IPv6) would grow `buckets` forever. Evicting an idle bucket is safe:
+    /// `check` just recreates it at full capacity on the subject's next
+    /// request, identical to its first-ever request.
+    pub fn spawn_reaper(&self, interval: std::time::Duration, ttl: std::time::Duration) {
+        let buckets = self.buckets.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let now = Instant::now();
+                buckets.retain(|_, bucket| now.duration_since(bucket.last_refill) <= ttl);
+            }
+        });
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `(key, tier)` for the current request — the `sub`/`tier` from `Claims`
+/// left by `auth::require_auth`, or the client IP at the `free` tier when
+/// no claims are present (anonymous caller, or auth disabled entirely).
+fn subject_key(req: &Request, addr: Option<SocketAddr>) -> (String, String) {
+    match req.extensions().get::<Claims>() {
+        Some(claims) => (claims.sub.clone(), claims.tier.clone()),
+        None => (
+            addr.map(|a| a.ip().to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            "free".to_string(),
+        ),
+    }
+}
+
+fn too_many_requests(retry_after_secs: f64) -> Response {
+    let retry_after = retry_after_secs.ceil().max(1.0) as u64;
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ErrorResponse {
+            error: "Too Many Requests".to_string(),
+            hint: Some(format!("Retry after {} second(s)", retry_after)),
+        }),
+    )
+        .into_response();
+    if let Ok(value) = axum::http::HeaderValue::from_str(&retry_after.to_string()) {
+        response
+            .headers_mut()
+            .insert(axum::http::header::RETRY_AFTER, value);
+    }
+    response
+}
+
+async fn limit(
+    limiter: &RateLimiter,
+    limits: &RateLimitConfig,
+    addr: Option<SocketAddr>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let (key, tier) = subject_key(&req, addr);
+    let (capacity, refill_per_sec) = limits.for_tier(&tier);
+
+    match limiter.check(&key, capacity, refill_per_sec) {
+        Ok(()) => next.run(req).await,
+        Err(retry_after_secs) => too_many_requests(retry_after_secs),
+    }
+}
+
+fn peer_addr(req: &Request) -> Option<SocketAddr> {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr)
+}
+
+pub async fn limit_prove(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let addr = peer_addr(&req);
+    limit(&state.prove_limiter, &state.config.prove_rate_limit, addr, req, next).await
+}
+
+pub async fn limit_batch(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let addr = peer_addr(&req);
+    limit(&state.batch_limiter, &state.config.batch_rate_limit, addr, req, next).await
+}
+
+pub async fn limit_upload(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let addr = peer_addr(&req);
+    limit(&state.upload_limiter, &state.config.upload_rate_limit, addr, req, next).await
+}
+
+pub async fn limit_prove_model(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let addr = peer_addr(&req);
+    limit(
+        &state.prove_model_limiter,
+        &state.config.prove_model_rate_limit,
+        addr,
+        req,
+        next,
+    )
+    .await
+}
@@ -65,6 +65,88 @@ pub struct Receipt {
 
     // Error (if failed)
     pub error: Option<String>,
+
+    // Set when webhook delivery exhausts its retries; distinct from `error`
+    // since a receipt can verify fine but still fail to notify its webhook.
+    #[serde(default)]
+    pub webhook_error: Option<String>,
+
+    // Ethereum-style attestation binding this receipt to the prover's
+    // identity, set when the server has a signing key configured and the
+    // receipt reaches `Verified`.
+    #[serde(default)]
+    pub attestation: Option<crate::crypto::Attestation>,
+
+    // Optional sharing controls, analogous to Firefox Send: a receipt can
+    // auto-expire, cap how many times it's viewed, and require a passphrase.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub max_views: Option<u32>,
+    #[serde(default)]
+    pub view_count: u32,
+    // Argon2 hash of the sharing passphrase; the plaintext is never stored.
+    #[serde(default)]
+    pub passphrase_hash: Option<String>,
+
+    // Which `ProverBackendKind` produced this proof, stored as its `as_str()`
+    // so older rows (all JOLT-Atlas, before backends were pluggable) still
+    // deserialize via the default.
+    #[serde(default = "default_backend")]
+    pub backend: String,
+
+    // `api_keys::ApiKeyIdentity::label` of the caller that created this
+    // receipt, if `api_keys::require_api_key` authenticated one — lets
+    // `GET /receipts/recent` filter receipts down to a single caller's own.
+    #[serde(default)]
+    pub api_key_label: Option<String>,
+}
+
+fn default_backend() -> String {
+    "jolt_atlas".to_string()
+}
+
+/// Why a receipt's sharing controls are currently blocking access.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccessDenial {
+    Expired,
+    ViewLimitReached,
+}
+
+impl Receipt {
+    /// Returns `Some(reason)` if this receipt's expiration or view-limit
+    /// sharing controls currently block access, `None` if it's viewable.
+    pub fn access_denial(&self) -> Option<AccessDenial> {
+        if let Some(expires_at) = self.expires_at {
+            if Utc::now() > expires_at {
+                return Some(AccessDenial::Expired);
+            }
+        }
+        if let Some(max_views) = self.max_views {
+            if self.view_count >= max_views {
+                return Some(AccessDenial::ViewLimitReached);
+            }
+        }
+        None
+    }
+
+    pub fn is_passphrase_protected(&self) -> bool {
+        self.passphrase_hash.is_some()
+    }
+}
+
+/// A single receipt aggregated into a batch. Unlike `Receipt`, this carries
+/// no inference output or proof bytes of its own — it commits to a set of
+/// already-verified member receipts via a Merkle root over each member's
+/// `(model_hash, input_hash, output_hash)` leaf, so the aggregate can be
+/// handed to a third party without re-shipping every member's full proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregateReceipt {
+    pub id: String,
+    pub member_ids: Vec<String>,
+    pub merkle_root: String,
+    pub leaf_hashes: Vec<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 pub struct SqliteStore {
@@ -103,20 +185,50 @@ impl SqliteStore {
                 proof_size INTEGER,
                 prove_time_ms INTEGER,
                 verify_time_ms INTEGER,
-                error TEXT
+                error TEXT,
+                webhook_error TEXT,
+                attestation_json TEXT,
+                expires_at TEXT,
+                max_views INTEGER,
+                view_count INTEGER NOT NULL DEFAULT 0,
+                passphrase_hash TEXT,
+                backend TEXT NOT NULL DEFAULT 'jolt_atlas',
+                api_key_label TEXT
             );
             CREATE INDEX IF NOT EXISTS idx_receipts_status ON receipts(status);
             CREATE INDEX IF NOT EXISTS idx_receipts_model_id ON receipts(model_id);
-            CREATE INDEX IF NOT EXISTS idx_receipts_created_at ON receipts(created_at DESC);"
+            CREATE INDEX IF NOT EXISTS idx_receipts_created_at ON receipts(created_at DESC);
+            CREATE TABLE IF NOT EXISTS aggregate_receipts (
+                id TEXT PRIMARY KEY,
+                member_ids_json TEXT NOT NULL,
+                merkle_root TEXT NOT NULL,
+                leaf_hashes_json TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );"
         )?;
+        // Databases created before webhook retry / attestation / sharing-
+        // control support won't have these columns; adding them is a no-op
+        // (ignored) on a fresh table.
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN webhook_error TEXT", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN attestation_json TEXT", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN expires_at TEXT", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN max_views INTEGER", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN view_count INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN passphrase_hash TEXT", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN backend TEXT NOT NULL DEFAULT 'jolt_atlas'", []);
+        let _ = conn.execute("ALTER TABLE receipts ADD COLUMN api_key_label TEXT", []);
         Ok(())
     }
 
     pub fn insert(&self, receipt: &Receipt) {
         let conn = self.conn.lock().expect("SQLite connection lock poisoned");
         let output_json = serde_json::to_string(&receipt.output).unwrap_or_default();
+        let attestation_json = receipt
+            .attestation
+            .as_ref()
+            .map(|a| serde_json::to_string(a).unwrap_or_default());
         if let Err(e) = conn.execute(
-            "INSERT OR REPLACE INTO receipts (id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            "INSERT OR REPLACE INTO receipts (id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error, webhook_error, attestation_json, expires_at, max_views, view_count, passphrase_hash, backend, api_key_label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
             rusqlite::params![
                 receipt.id,
                 receipt.model_id,
@@ -133,16 +245,216 @@ impl SqliteStore {
                 receipt.prove_time_ms.map(|t| t as i64),
                 receipt.verify_time_ms.map(|t| t as i64),
                 receipt.error,
+                receipt.webhook_error,
+                attestation_json,
+                receipt.expires_at.map(|t| t.to_rfc3339()),
+                receipt.max_views,
+                receipt.view_count,
+                receipt.passphrase_hash,
+                receipt.backend,
+                receipt.api_key_label,
             ],
         ) {
             error!("[clawproof] SQLite insert failed: {:?}", e);
         }
     }
 
+    /// Write many receipts in a single transaction instead of one commit per
+    /// row. Opens one transaction, prepares the `INSERT OR REPLACE` once, and
+    /// reuses it for every row — sharply cuts WAL fsync pressure versus
+    /// `insert()` called in a loop under burst proving traffic.
+    pub fn insert_batch(&self, receipts: &[Receipt]) {
+        if receipts.is_empty() {
+            return;
+        }
+        let mut conn = self.conn.lock().expect("SQLite connection lock poisoned");
+        let tx = match conn.transaction() {
+            Ok(tx) => tx,
+            Err(e) => {
+                error!("[clawproof] insert_batch transaction open failed: {:?}", e);
+                return;
+            }
+        };
+        {
+            let mut stmt = match tx.prepare(
+                "INSERT OR REPLACE INTO receipts (id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error, webhook_error, attestation_json, expires_at, max_views, view_count, passphrase_hash, backend, api_key_label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+            ) {
+                Ok(s) => s,
+                Err(e) => {
+                    error!("[clawproof] insert_batch prepare failed: {:?}", e);
+                    return;
+                }
+            };
+            for receipt in receipts {
+                let output_json = serde_json::to_string(&receipt.output).unwrap_or_default();
+                let attestation_json = receipt
+                    .attestation
+                    .as_ref()
+                    .map(|a| serde_json::to_string(a).unwrap_or_default());
+                if let Err(e) = stmt.execute(rusqlite::params![
+                    receipt.id,
+                    receipt.model_id,
+                    receipt.model_name,
+                    receipt.status.as_str(),
+                    receipt.created_at.to_rfc3339(),
+                    receipt.completed_at.map(|t| t.to_rfc3339()),
+                    receipt.model_hash,
+                    receipt.input_hash,
+                    receipt.output_hash,
+                    output_json,
+                    receipt.proof_hash,
+                    receipt.proof_size.map(|s| s as i64),
+                    receipt.prove_time_ms.map(|t| t as i64),
+                    receipt.verify_time_ms.map(|t| t as i64),
+                    receipt.error,
+                    receipt.webhook_error,
+                    attestation_json,
+                    receipt.expires_at.map(|t| t.to_rfc3339()),
+                    receipt.max_views,
+                    receipt.view_count,
+                    receipt.passphrase_hash,
+                    receipt.backend,
+                    receipt.api_key_label,
+                ]) {
+                    error!("[clawproof] insert_batch row failed for {}: {:?}", receipt.id, e);
+                }
+            }
+        }
+        if let Err(e) = tx.commit() {
+            error!("[clawproof] insert_batch commit failed: {:?}", e);
+        }
+    }
+
+    /// Scan every row in bounded batches, recomputing `output_hash` and
+    /// checking terminal-status invariants, repairing whatever fails by
+    /// flipping the row to `Failed` with a diagnostic `error`.
+    pub fn scrub(&self, batch_size: u64) -> ScrubReport {
+        let mut report = ScrubReport::default();
+        let mut offset = 0u64;
+        loop {
+            let batch = self.scrub_batch(batch_size, offset);
+            if batch.is_empty() {
+                break;
+            }
+            report.scanned += batch.len() as u64;
+            for receipt in batch {
+                if let Some(diagnostic) = Self::check_invariants(&receipt) {
+                    report.mismatches += 1;
+                    let mut repaired = receipt.clone();
+                    repaired.status = ReceiptStatus::Failed;
+                    repaired.error = Some(diagnostic.clone());
+                    self.insert(&repaired);
+                    report.repaired += 1;
+                    report.issues.push(ScrubIssue {
+                        id: receipt.id,
+                        diagnostic,
+                    });
+                }
+            }
+            offset += batch_size;
+        }
+        report
+    }
+
+    fn scrub_batch(&self, limit: u64, offset: u64) -> Vec<Receipt> {
+        let conn = self.conn.lock().expect("SQLite connection lock poisoned");
+        let mut stmt = match conn.prepare(
+            "SELECT id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error, webhook_error, attestation_json, expires_at, max_views, view_count, passphrase_hash, backend, api_key_label FROM receipts ORDER BY id LIMIT ?1 OFFSET ?2",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("[clawproof] scrub_batch prepare failed: {:?}", e);
+                return vec![];
+            }
+        };
+        let rows = stmt.query_map(rusqlite::params![limit as i64, offset as i64], |row| {
+            let status_str: String = row.get(3)?;
+            let created_str: String = row.get(4)?;
+            let completed_str: Option<String> = row.get(5)?;
+            let output_json: String = row.get(9)?;
+            let proof_size: Option<i64> = row.get(11)?;
+            let prove_time: Option<i64> = row.get(12)?;
+            let verify_time: Option<i64> = row.get(13)?;
+            let attestation_json: Option<String> = row.get(16)?;
+            let expires_str: Option<String> = row.get(17)?;
+            let max_views: Option<i64> = row.get(18)?;
+            let view_count: i64 = row.get(19)?;
+
+            Ok(Receipt {
+                id: row.get(0)?,
+                model_id: row.get(1)?,
+                model_name: row.get(2)?,
+                status: ReceiptStatus::from_str(&status_str),
+                created_at: DateTime::parse_from_rfc3339(&created_str)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                completed_at: completed_str.and_then(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .ok()
+                }),
+                model_hash: row.get(6)?,
+                input_hash: row.get(7)?,
+                output_hash: row.get(8)?,
+                output: serde_json::from_str(&output_json).unwrap_or(InferenceOutput {
+                    raw_output: vec![],
+                    predicted_class: 0,
+                    label: "unknown".to_string(),
+                    confidence: 0.0,
+                }),
+                proof_hash: row.get(10)?,
+                proof_size: proof_size.map(|s| s as usize),
+                prove_time_ms: prove_time.map(|t| t as u128),
+                verify_time_ms: verify_time.map(|t| t as u128),
+                error: row.get(14)?,
+                webhook_error: row.get(15)?,
+                attestation: attestation_json.and_then(|j| serde_json::from_str(&j).ok()),
+                expires_at: expires_str.and_then(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .ok()
+                }),
+                max_views: max_views.map(|v| v as u32),
+                view_count: view_count as u32,
+                passphrase_hash: row.get(20)?,
+                backend: row.get(21)?,
+                api_key_label: row.get(22)?,
+            })
+        });
+        match rows {
+            Ok(iter) => iter.flatten().collect(),
+            Err(e) => {
+                error!("[clawproof] scrub_batch rows failed: {:?}", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Returns `Some(diagnostic)` if `receipt` violates an integrity
+    /// invariant, `None` if it's healthy.
+    fn check_invariants(receipt: &Receipt) -> Option<String> {
+        let expected_hash = crate::crypto::hash_tensor(&receipt.output.raw_output);
+        if expected_hash != receipt.output_hash {
+            return Some(format!(
+                "output_hash mismatch: stored {} recomputed {}",
+                receipt.output_hash, expected_hash
+            ));
+        }
+        if receipt.status == ReceiptStatus::Verified
+            && (receipt.proof_hash.is_none() || receipt.proof_size.is_none())
+        {
+            return Some("status=verified but missing proof_hash/proof_size".to_string());
+        }
+        if receipt.status == ReceiptStatus::Failed && receipt.error.is_none() {
+            return Some("status=failed but missing error".to_string());
+        }
+        None
+    }
+
     pub fn get(&self, id: &str) -> Option<Receipt> {
         let conn = self.conn.lock().expect("SQLite connection lock poisoned");
         conn.query_row(
-            "SELECT id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error FROM receipts WHERE id = ?1",
+            "SELECT id, model_id, model_name, status, created_at, completed_at, model_hash, input_hash, output_hash, output_json, proof_hash, proof_size, prove_time_ms, verify_time_ms, error, webhook_error, attestation_json, expires_at, max_views, view_count, passphrase_hash, backend, api_key_label FROM receipts WHERE id = ?1",
             rusqlite::params![id],
             |row| {
                 let status_str: String = row.get(3)?;
@@ -152,6 +464,10 @@ impl SqliteStore {
                 let proof_size: Option<i64> = row.get(11)?;
                 let prove_time: Option<i64> = row.get(12)?;
                 let verify_time: Option<i64> = row.get(13)?;
+                let attestation_json: Option<String> = row.get(16)?;
+                let expires_str: Option<String> = row.get(17)?;
+                let max_views: Option<i64> = row.get(18)?;
+                let view_count: i64 = row.get(19)?;
 
                 Ok(Receipt {
                     id: row.get(0)?,
@@ -180,6 +496,59 @@ impl SqliteStore {
                     prove_time_ms: prove_time.map(|t| t as u128),
                     verify_time_ms: verify_time.map(|t| t as u128),
                     error: row.get(14)?,
+                    webhook_error: row.get(15)?,
+                    attestation: attestation_json.and_then(|j| serde_json::from_str(&j).ok()),
+                    expires_at: expires_str.and_then(|s| {
+                        DateTime::parse_from_rfc3339(&s)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .ok()
+                    }),
+                    max_views: max_views.map(|v| v as u32),
+                    view_count: view_count as u32,
+                    passphrase_hash: row.get(20)?,
+                    backend: row.get(21)?,
+                    api_key_label: row.get(22)?,
+                })
+            },
+        )
+        .ok()
+    }
+
+    pub fn insert_aggregate(&self, aggregate: &AggregateReceipt) {
+        let conn = self.conn.lock().expect("SQLite connection lock poisoned");
+        let member_ids_json = serde_json::to_string(&aggregate.member_ids).unwrap_or_default();
+        let leaf_hashes_json = serde_json::to_string(&aggregate.leaf_hashes).unwrap_or_default();
+        if let Err(e) = conn.execute(
+            "INSERT OR REPLACE INTO aggregate_receipts (id, member_ids_json, merkle_root, leaf_hashes_json, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                aggregate.id,
+                member_ids_json,
+                aggregate.merkle_root,
+                leaf_hashes_json,
+                aggregate.created_at.to_rfc3339(),
+            ],
+        ) {
+            error!("[clawproof] SQLite aggregate insert failed: {:?}", e);
+        }
+    }
+
+    pub fn get_aggregate(&self, id: &str) -> Option<AggregateReceipt> {
+        let conn = self.conn.lock().expect("SQLite connection lock poisoned");
+        conn.query_row(
+            "SELECT id, member_ids_json, merkle_root, leaf_hashes_json, created_at FROM aggregate_receipts WHERE id = ?1",
+            rusqlite::params![id],
+            |row| {
+                let member_ids_json: String = row.get(1)?;
+                let leaf_hashes_json: String = row.get(3)?;
+                let created_str: String = row.get(4)?;
+                Ok(AggregateReceipt {
+                    id: row.get(0)?,
+                    member_ids: serde_json::from_str(&member_ids_json).unwrap_or_default(),
+                    merkle_root: row.get(2)?,
+                    leaf_hashes: serde_json::from_str(&leaf_hashes_json).unwrap_or_default(),
+                    created_at: DateTime::parse_from_rfc3339(&created_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
                 })
             },
         )
@@ -228,24 +597,57 @@ impl SqliteStore {
         stats
     }
 
-    pub fn list_recent(&self, limit: u64) -> Vec<ReceiptSummary> {
+    /// Keyset-paginated, filterable recent-receipts query.
+    ///
+    /// `cursor` is the opaque `(created_at, id)` token returned as
+    /// `next_cursor` from a previous page; ordering on the compound key
+    /// `(created_at DESC, id DESC)` avoids the skipped/duplicated rows a
+    /// plain `OFFSET` suffers when new receipts arrive mid-scroll.
+    pub fn list_recent(&self, limit: u64, cursor: Option<&str>, filter: &ReceiptFilter) -> ReceiptPage {
         let conn = self.conn.lock().expect("SQLite connection lock poisoned");
-        let mut stmt = match conn.prepare(
-            "SELECT id, model_id, model_name, status, created_at, output_json, prove_time_ms, verify_time_ms FROM receipts ORDER BY created_at DESC LIMIT ?1",
-        ) {
+
+        let mut sql = String::from(
+            "SELECT id, model_id, model_name, status, created_at, output_json, prove_time_ms, verify_time_ms, backend FROM receipts WHERE 1=1",
+        );
+        let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(status) = &filter.status {
+            sql.push_str(" AND status = ?");
+            params.push(Box::new(status.clone()));
+        }
+        if let Some(model_id) = &filter.model_id {
+            sql.push_str(" AND model_id = ?");
+            params.push(Box::new(model_id.clone()));
+        }
+        if let Some(api_key_label) = &filter.api_key_label {
+            sql.push_str(" AND api_key_label = ?");
+            params.push(Box::new(api_key_label.clone()));
+        }
+        if let Some((cursor_time, cursor_id)) = cursor.and_then(decode_cursor) {
+            sql.push_str(" AND (created_at, id) < (?, ?)");
+            params.push(Box::new(cursor_time.to_rfc3339()));
+            params.push(Box::new(cursor_id));
+        }
+        sql.push_str(" ORDER BY created_at DESC, id DESC LIMIT ?");
+        // Fetch one extra row to know whether another page exists.
+        params.push(Box::new((limit + 1) as i64));
+
+        let mut stmt = match conn.prepare(&sql) {
             Ok(s) => s,
             Err(e) => {
                 error!("[clawproof] list_recent query failed: {:?}", e);
-                return vec![];
+                return ReceiptPage::empty();
             }
         };
 
-        let rows = stmt.query_map(rusqlite::params![limit as i64], |row| {
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt.query_map(param_refs.as_slice(), |row| {
             let status_str: String = row.get(3)?;
             let created_str: String = row.get(4)?;
             let output_json: String = row.get(5)?;
             let prove_time: Option<i64> = row.get(6)?;
             let verify_time: Option<i64> = row.get(7)?;
+            let backend: String = row.get(8)?;
 
             let output: InferenceOutput = serde_json::from_str(&output_json).unwrap_or(InferenceOutput {
                 raw_output: vec![],
@@ -266,19 +668,78 @@ impl SqliteStore {
                 created_at: DateTime::parse_from_rfc3339(&created_str)
                     .map(|dt| dt.with_timezone(&Utc))
                     .unwrap_or_else(|_| Utc::now()),
+                backend,
             })
         });
 
-        match rows {
+        let mut receipts: Vec<ReceiptSummary> = match rows {
             Ok(iter) => iter.flatten().collect(),
             Err(e) => {
                 error!("[clawproof] list_recent rows failed: {:?}", e);
-                vec![]
+                return ReceiptPage::empty();
             }
+        };
+
+        let has_more = receipts.len() as u64 > limit;
+        if has_more {
+            receipts.truncate(limit as usize);
+        }
+        let next_cursor = if has_more {
+            receipts.last().map(|r| encode_cursor(&r.created_at, &r.id))
+        } else {
+            None
+        };
+
+        ReceiptPage {
+            receipts,
+            next_cursor,
+            has_more,
+        }
+    }
+}
+
+/// Constraints a `list_recent` page may be filtered by.
+#[derive(Clone, Debug, Default)]
+pub struct ReceiptFilter {
+    pub status: Option<String>,
+    pub model_id: Option<String>,
+    pub api_key_label: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ReceiptPage {
+    pub receipts: Vec<ReceiptSummary>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl ReceiptPage {
+    fn empty() -> Self {
+        Self {
+            receipts: vec![],
+            next_cursor: None,
+            has_more: false,
         }
     }
 }
 
+fn encode_cursor(created_at: &DateTime<Utc>, id: &str) -> String {
+    use base64::Engine;
+    let raw = format!("{}|{}", created_at.to_rfc3339(), id);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(raw)
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, String)> {
+    use base64::Engine;
+    let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()?;
+    let raw = String::from_utf8(raw).ok()?;
+    let (ts, id) = raw.split_once('|')?;
+    let dt = DateTime::parse_from_rfc3339(ts).ok()?.with_timezone(&Utc);
+    Some((dt, id.to_string()))
+}
+
 impl Clone for SqliteStore {
     fn clone(&self) -> Self {
         Self {
@@ -298,6 +759,23 @@ pub struct ReceiptSummary {
     pub prove_time_ms: Option<u128>,
     pub verify_time_ms: Option<u128>,
     pub created_at: DateTime<Utc>,
+    pub backend: String,
+}
+
+/// One receipt that failed an integrity check during `scrub()`.
+#[derive(Clone, Debug, Serialize)]
+pub struct ScrubIssue {
+    pub id: String,
+    pub diagnostic: String,
+}
+
+/// Summary returned by a `scrub()` pass.
+#[derive(Clone, Debug, Serialize, Default)]
+pub struct ScrubReport {
+    pub scanned: u64,
+    pub mismatches: u64,
+    pub repaired: u64,
+    pub issues: Vec<ScrubIssue>,
 }
 
 #[derive(Clone, Debug, Serialize, Default)]
@@ -311,60 +789,333 @@ pub struct ReceiptStats {
     pub avg_verify_time_ms: Option<f64>,
 }
 
+/// Fields pushed to `receipt/update` subscribers as a receipt transitions —
+/// a subset of the full `Receipt` a webhook payload carries.
+#[derive(Clone, Debug, Serialize)]
+pub struct ReceiptUpdateEvent {
+    pub receipt_id: String,
+    pub status: String,
+    pub proof_hash: Option<String>,
+    pub proof_size: Option<usize>,
+    pub prove_time_ms: Option<u128>,
+    pub verify_time_ms: Option<u128>,
+}
+
+impl ReceiptUpdateEvent {
+    fn from_receipt(receipt: &Receipt) -> Self {
+        Self {
+            receipt_id: receipt.id.clone(),
+            status: receipt.status.as_str().to_string(),
+            proof_hash: receipt.proof_hash.clone(),
+            proof_size: receipt.proof_size,
+            prove_time_ms: receipt.prove_time_ms,
+            verify_time_ms: receipt.verify_time_ms,
+        }
+    }
+}
+
+/// Capacity of each per-receipt broadcast channel. A slow subscriber that
+/// falls behind this many updates starts lagging rather than blocking
+/// publishers — acceptable since a receipt only ever has a handful of
+/// transitions in its lifetime.
+const SUBSCRIBER_CHANNEL_CAPACITY: usize = 16;
+
+/// Flush a debounced batch of dirty receipts once this many are buffered,
+/// even if the debounce interval hasn't elapsed yet.
+const WRITE_BUFFER_THRESHOLD: usize = 64;
+
+/// Row count per SELECT during `scrub()`, to bound memory on large tables.
+const SCRUB_BATCH_SIZE: u64 = 500;
+
+/// A cached receipt plus a logical access tick used for LRU eviction. The
+/// tick is a monotonic counter rather than a wall-clock timestamp so
+/// ordering accesses doesn't need a syscall on every cache hit.
+struct CacheEntry {
+    receipt: Receipt,
+    last_access: std::sync::atomic::AtomicU64,
+}
+
+impl CacheEntry {
+    fn new(receipt: Receipt, tick: u64) -> Self {
+        Self {
+            receipt,
+            last_access: std::sync::atomic::AtomicU64::new(tick),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ReceiptStore {
-    cache: Arc<DashMap<String, Receipt>>,
+    cache: Arc<DashMap<String, CacheEntry>>,
     db: SqliteStore,
+    waiters: Arc<DashMap<String, Arc<tokio::sync::Notify>>>,
+    /// Receipts written since the last flush, keyed by id so repeated writes
+    /// to the same receipt (e.g. insert then update) coalesce into one row.
+    dirty: Arc<DashMap<String, Receipt>>,
+    max_cache_entries: usize,
+    access_tick: Arc<std::sync::atomic::AtomicU64>,
+    /// Per-receipt pubsub channels for `receipt/subscribe` over the
+    /// WebSocket transport, keyed by receipt id like `waiters`.
+    subscribers: Arc<DashMap<String, tokio::sync::broadcast::Sender<ReceiptUpdateEvent>>>,
 }
 
 impl ReceiptStore {
-    pub fn new(db_path: &Path) -> anyhow::Result<Self> {
+    pub fn new(db_path: &Path, max_cache_entries: usize) -> anyhow::Result<Self> {
         let db = SqliteStore::new(db_path)?;
         Ok(Self {
             cache: Arc::new(DashMap::new()),
             db,
+            waiters: Arc::new(DashMap::new()),
+            dirty: Arc::new(DashMap::new()),
+            max_cache_entries,
+            access_tick: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            subscribers: Arc::new(DashMap::new()),
         })
     }
 
-    pub fn insert(&self, receipt: Receipt) {
-        self.cache.insert(receipt.id.clone(), receipt.clone());
+    /// Register interest in `id`'s status transitions. The returned
+    /// receiver gets a `ReceiptUpdateEvent` on every `update()` call for
+    /// that receipt until it reaches a terminal status.
+    pub fn subscribe(&self, id: &str) -> tokio::sync::broadcast::Receiver<ReceiptUpdateEvent> {
+        self.subscribers
+            .entry(id.to_string())
+            .or_insert_with(|| tokio::sync::broadcast::channel(SUBSCRIBER_CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publish a transition to `id`'s subscribers, if any, and drop the
+    /// channel registration once the receipt reaches a terminal status —
+    /// no further updates will ever be published for it.
+    fn publish_update(&self, receipt: &Receipt) {
+        if let Some(sender) = self.subscribers.get(&receipt.id) {
+            let _ = sender.send(ReceiptUpdateEvent::from_receipt(receipt));
+        }
+        if matches!(receipt.status, ReceiptStatus::Verified | ReceiptStatus::Failed) {
+            self.subscribers.remove(&receipt.id);
+        }
+    }
+
+    fn next_tick(&self) -> u64 {
+        self.access_tick.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Evict the least-recently-used entries until the cache is back at or
+    /// under `max_cache_entries`. Called after inserts that can grow it.
+    fn evict_lru(&self) {
+        if self.cache.len() <= self.max_cache_entries {
+            return;
+        }
+        let mut by_tick: Vec<(String, u64)> = self
+            .cache
+            .iter()
+            .map(|e| (e.key().clone(), e.value().last_access.load(std::sync::atomic::Ordering::Relaxed)))
+            .collect();
+        by_tick.sort_by_key(|(_, tick)| *tick);
+        let excess = self.cache.len().saturating_sub(self.max_cache_entries);
+        for (id, _) in by_tick.into_iter().take(excess) {
+            self.cache.remove(&id);
+        }
+    }
+
+    /// Spawn the background task that flushes buffered writes on a debounce
+    /// interval. Call once at startup; must run inside a Tokio runtime.
+    pub fn spawn_write_coalescer(&self, debounce: std::time::Duration) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(debounce);
+            loop {
+                interval.tick().await;
+                store.flush_dirty();
+            }
+        });
+    }
+
+    fn mark_dirty(&self, receipt: &Receipt) {
+        self.dirty.insert(receipt.id.clone(), receipt.clone());
+        if self.dirty.len() >= WRITE_BUFFER_THRESHOLD {
+            self.flush_dirty();
+        }
+    }
+
+    /// Drain the dirty buffer and write it in a single SQLite transaction.
+    fn flush_dirty(&self) {
+        if self.dirty.is_empty() {
+            return;
+        }
+        let batch: Vec<Receipt> = self
+            .dirty
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        for receipt in &batch {
+            self.dirty.remove(&receipt.id);
+        }
+        if batch.is_empty() {
+            return;
+        }
         let db = self.db.clone();
         let _ = tokio::task::spawn_blocking(move || {
-            db.insert(&receipt);
+            db.insert_batch(&batch);
         });
     }
 
+    pub fn insert(&self, receipt: Receipt) {
+        crate::metrics::metrics().record_status(receipt.status.as_str());
+        if receipt.status == ReceiptStatus::Proving {
+            crate::metrics::metrics().inc_proving();
+        }
+        let tick = self.next_tick();
+        self.cache.insert(receipt.id.clone(), CacheEntry::new(receipt.clone(), tick));
+        self.mark_dirty(&receipt);
+        self.evict_lru();
+    }
+
+    fn record_terminal_metrics(&self, receipt: &Receipt) {
+        if matches!(receipt.status, ReceiptStatus::Verified | ReceiptStatus::Failed) {
+            crate::metrics::metrics().record_status(receipt.status.as_str());
+            crate::metrics::metrics().record_completion(
+                &receipt.model_id,
+                receipt.prove_time_ms,
+                receipt.verify_time_ms,
+            );
+            crate::metrics::metrics().dec_proving();
+        }
+    }
+
+    fn notify_waiters(&self, id: &str, status: &ReceiptStatus) {
+        if let Some(notify) = self.waiters.get(id) {
+            notify.notify_waiters();
+        }
+        // Terminal statuses won't change again — drop the entry so the
+        // waiter map doesn't grow unbounded across the receipt's lifetime.
+        if matches!(status, ReceiptStatus::Verified | ReceiptStatus::Failed) {
+            self.waiters.remove(id);
+        }
+    }
+
+    /// Block until the receipt at `id` leaves the `since` status or `timeout`
+    /// elapses, whichever comes first. Returns the current receipt in either
+    /// case, or `None` if the receipt doesn't exist.
+    pub async fn wait_for_status_change(
+        &self,
+        id: &str,
+        since: &str,
+        timeout: std::time::Duration,
+    ) -> Option<Receipt> {
+        let current = self.get(id)?;
+        if current.status.as_str() != since {
+            return Some(current);
+        }
+
+        let notify = self
+            .waiters
+            .entry(id.to_string())
+            .or_insert_with(|| Arc::new(tokio::sync::Notify::new()))
+            .clone();
+
+        // Re-check after registering interest in case the status flipped
+        // between the first read and the notify registration.
+        if let Some(r) = self.get(id) {
+            if r.status.as_str() != since {
+                return Some(r);
+            }
+        }
+
+        let _ = tokio::time::timeout(timeout, notify.notified()).await;
+        self.get(id)
+    }
+
     pub fn get(&self, id: &str) -> Option<Receipt> {
         // DashMap first (hot cache)
-        if let Some(r) = self.cache.get(id) {
-            return Some(r.value().clone());
+        if let Some(entry) = self.cache.get(id) {
+            crate::metrics::metrics().record_cache_hit();
+            entry
+                .last_access
+                .store(self.next_tick(), std::sync::atomic::Ordering::Relaxed);
+            return Some(entry.receipt.clone());
         }
         // SQLite fallback
+        crate::metrics::metrics().record_cache_miss();
         let receipt = self.db.get(id)?;
         // Populate cache for future reads
-        self.cache.insert(receipt.id.clone(), receipt.clone());
+        let tick = self.next_tick();
+        self.cache.insert(receipt.id.clone(), CacheEntry::new(receipt.clone(), tick));
+        self.evict_lru();
         Some(receipt)
     }
 
+    /// Record a webhook-delivery failure without re-triggering terminal
+    /// metrics or waiter notification — unlike `update()`, this doesn't
+    /// represent a status transition, since the receipt already reached
+    /// its terminal status before the webhook describing it fired.
+    pub fn set_webhook_error(&self, id: &str, message: String) {
+        if let Some(mut entry) = self.cache.get_mut(id) {
+            entry.value_mut().receipt.webhook_error = Some(message);
+            let receipt = entry.value().receipt.clone();
+            drop(entry);
+            self.mark_dirty(&receipt);
+        } else if let Some(mut receipt) = self.db.get(id) {
+            receipt.webhook_error = Some(message);
+            let tick = self.next_tick();
+            self.cache.insert(receipt.id.clone(), CacheEntry::new(receipt.clone(), tick));
+            self.evict_lru();
+            self.mark_dirty(&receipt);
+        }
+    }
+
+    /// Record a successful authorized view of a shared receipt, incrementing
+    /// `view_count` without re-triggering terminal metrics or waiter
+    /// notification — like `set_webhook_error`, this isn't a status
+    /// transition, just bookkeeping for the sharing-control view limit.
+    /// Returns the updated receipt so the caller can re-check
+    /// `access_denial()` against the new count.
+    pub fn record_view(&self, id: &str) -> Option<Receipt> {
+        if let Some(mut entry) = self.cache.get_mut(id) {
+            entry.value_mut().receipt.view_count += 1;
+            let receipt = entry.value().receipt.clone();
+            drop(entry);
+            self.mark_dirty(&receipt);
+            Some(receipt)
+        } else if let Some(mut receipt) = self.db.get(id) {
+            receipt.view_count += 1;
+            let tick = self.next_tick();
+            self.cache.insert(receipt.id.clone(), CacheEntry::new(receipt.clone(), tick));
+            self.evict_lru();
+            self.mark_dirty(&receipt);
+            Some(receipt)
+        } else {
+            None
+        }
+    }
+
     pub fn update<F>(&self, id: &str, f: F)
     where
         F: FnOnce(&mut Receipt),
     {
         if let Some(mut entry) = self.cache.get_mut(id) {
-            f(entry.value_mut());
-            let receipt = entry.value().clone();
-            let db = self.db.clone();
-            let _ = tokio::task::spawn_blocking(move || {
-                db.insert(&receipt);
-            });
+            f(&mut entry.value_mut().receipt);
+            entry
+                .value()
+                .last_access
+                .store(self.next_tick(), std::sync::atomic::Ordering::Relaxed);
+            let receipt = entry.value().receipt.clone();
+            drop(entry);
+            self.record_terminal_metrics(&receipt);
+            let status = receipt.status.clone();
+            self.mark_dirty(&receipt);
+            self.notify_waiters(id, &status);
+            self.publish_update(&receipt);
         } else if let Some(mut receipt) = self.db.get(id) {
             // Receipt was evicted from cache — load from SQLite, apply mutation, write back
             f(&mut receipt);
-            self.cache.insert(receipt.id.clone(), receipt.clone());
-            let db = self.db.clone();
-            let _ = tokio::task::spawn_blocking(move || {
-                db.insert(&receipt);
-            });
+            let tick = self.next_tick();
+            self.cache.insert(receipt.id.clone(), CacheEntry::new(receipt.clone(), tick));
+            self.evict_lru();
+            self.record_terminal_metrics(&receipt);
+            let status = receipt.status.clone();
+            self.mark_dirty(&receipt);
+            self.notify_waiters(id, &status);
+            self.publish_update(&receipt);
         } else {
             warn!("[clawproof] update called for unknown receipt {}", id);
         }
@@ -373,7 +1124,7 @@ impl ReceiptStore {
     pub fn cleanup_cache(&self, max_age: std::time::Duration) {
         let cutoff = Utc::now() - chrono::Duration::from_std(max_age).unwrap();
         let before = self.cache.len();
-        self.cache.retain(|_, receipt| receipt.created_at > cutoff);
+        self.cache.retain(|_, entry| entry.receipt.created_at > cutoff);
         let removed = before - self.cache.len();
         if removed > 0 {
             info!("[clawproof] Evicted {} receipts from cache", removed);
@@ -384,7 +1135,52 @@ impl ReceiptStore {
         self.db.get_stats()
     }
 
-    pub fn list_recent(&self, limit: u64) -> Vec<ReceiptSummary> {
-        self.db.list_recent(limit)
+    pub fn list_recent(&self, limit: u64, cursor: Option<&str>, filter: &ReceiptFilter) -> ReceiptPage {
+        self.db.list_recent(limit, cursor, filter)
+    }
+
+    /// Aggregates are append-only and looked up far less often than
+    /// receipts, so unlike `insert`/`get` they skip the DashMap cache and go
+    /// straight to SQLite.
+    pub fn insert_aggregate(&self, aggregate: &AggregateReceipt) {
+        self.db.insert_aggregate(aggregate);
+    }
+
+    pub fn get_aggregate(&self, id: &str) -> Option<AggregateReceipt> {
+        self.db.get_aggregate(id)
+    }
+
+    /// Run an integrity scrub over every row in SQLite, repairing whatever
+    /// fails a check. Safe to call on demand or on a timer.
+    pub async fn scrub(&self) -> ScrubReport {
+        let db = self.db.clone();
+        let report = tokio::task::spawn_blocking(move || db.scrub(SCRUB_BATCH_SIZE))
+            .await
+            .unwrap_or_default();
+        // Repaired rows were written straight to SQLite; drop any cached
+        // copy so the next get() reloads the corrected version.
+        for issue in &report.issues {
+            self.cache.remove(&issue.id);
+        }
+        report
+    }
+
+    /// Spawn the background task that scrubs on `interval`. Call once at
+    /// startup; must run inside a Tokio runtime.
+    pub fn spawn_scrub_task(&self, interval: std::time::Duration) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let report = store.scrub().await;
+                if report.mismatches > 0 {
+                    warn!(
+                        "[clawproof] scrub: {} scanned, {} mismatches, {} repaired",
+                        report.scanned, report.mismatches, report.repaired
+                    );
+                }
+            }
+        });
     }
 }
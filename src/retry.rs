@@ -0,0 +1,85 @@
+//! Retry helper for outbound HTTP calls (webhooks, converter proxy).
+//!
+//! Classifies a failed attempt as retriable — HTTP 5xx, connect errors,
+//! timeouts, and other errors while sending the request — or terminal —
+//! request-construction, redirect, body and decode errors — and backs off
+//! exponentially with jitter between retriable attempts. Any non-5xx
+//! response, 4xx included, is returned as-is without a retry.
+
+use std::time::Duration;
+use tracing::warn;
+
+/// Default retry budget for a single delivery attempt chain.
+pub const DEFAULT_MAX_RETRIES: u32 = 4;
+
+const BASE_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+pub enum RetryError {
+    /// Every attempt hit a retriable failure and the retry budget ran out.
+    Exhausted,
+    /// A non-retriable error — one that isn't a timeout, a connect failure
+    /// or an error while sending the request (e.g. a request-construction
+    /// error). A 4xx response is not an error here: it comes back as `Ok`.
+    Terminal(reqwest::Error),
+}
+
+fn is_retriable_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+/// Exponential backoff (base 500ms, doubling, capped at 30s) with jitter so
+/// concurrent retries of the same endpoint don't all wake up in lockstep.
+fn backoff_with_jitter(attempt: u32) -> Duration {
+    let exp_ms = BASE_BACKOFF_MS
+        .saturating_mul(1u64 << attempt.min(10))
+        .min(MAX_BACKOFF_MS);
+    let jitter_ns = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    let half = exp_ms / 2;
+    Duration::from_millis(half + jitter_ns % (half + 1))
+}
+
+/// Send a request via `send`, retrying retriable failures up to
+/// `max_retries` times with exponential backoff. `send` is called again on
+/// each retry so the caller can rebuild the request (reqwest's
+/// `RequestBuilder`/`Form` aren't reusable across attempts).
+pub async fn retry_send<F, Fut>(max_retries: u32, mut send: F) -> Result<reqwest::Response, RetryError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
+{
+    for attempt in 0..=max_retries {
+        match send().await {
+            Ok(resp) if resp.status().is_server_error() => {
+                if attempt == max_retries {
+                    return Err(RetryError::Exhausted);
+                }
+                warn!(
+                    "[clawproof] retriable {} response, attempt {}/{}",
+                    resp.status(),
+                    attempt + 1,
+                    max_retries + 1
+                );
+                tokio::time::sleep(backoff_with_jitter(attempt)).await;
+            }
+            Ok(resp) => return Ok(resp),
+            Err(e) if is_retriable_error(&e) => {
+                if attempt == max_retries {
+                    return Err(RetryError::Exhausted);
+                }
+                warn!(
+                    "[clawproof] retriable transport error, attempt {}/{}: {:?}",
+                    attempt + 1,
+                    max_retries + 1,
+                    e
+                );
+                tokio::time::sleep(backoff_with_jitter(attempt)).await;
+            }
+            Err(e) => return Err(RetryError::Terminal(e)),
+        }
+    }
+    Err(RetryError::Exhausted)
+}
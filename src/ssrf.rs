@@ -0,0 +1,94 @@
+//! Guards `webhook_url` against pointing at the server's own network —
+//! cloud metadata endpoints (`169.254.169.254`), loopback, or other RFC
+//! 1918/4193 addresses a receipt-holder shouldn't be able to reach through
+//! this service. Checking `Url::host_str()` alone isn't enough: the name
+//! could resolve to a private address at connect time even if it looks
+//! public at submission time (DNS rebinding), so this also wires into
+//! `reqwest` as a custom [`Resolve`] that re-checks every resolved address
+//! before a delivery attempt connects to it.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+/// True if `ip` is loopback, link-local (including the `169.254.169.254`
+/// cloud metadata address), a private (RFC 1918) IPv4 range, or a unique
+/// local (RFC 4193, `fc00::/7`) IPv6 range.
+pub fn is_disallowed_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_link_local() || v4.is_private() || v4.is_unspecified(),
+        IpAddr::V6(v6) => {
+            // An IPv4-mapped address (`::ffff:a.b.c.d`) connects to the
+            // underlying IPv4 target, so it must be judged by the V4 rules
+            // above — otherwise `::ffff:169.254.169.254` sails through every
+            // check here despite reaching the same socket as the bare IPv4
+            // metadata address.
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_disallowed_ip(&IpAddr::V4(v4));
+            }
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00 // fc00::/7 unique local
+                || (v6.segments()[0] & 0xffc0) == 0xfe80 // fe80::/10 link-local
+        }
+    }
+}
+
+/// Resolve `host` and return an error if any resolved address is
+/// disallowed. Used at submission time (`handlers::prove`) so a bad
+/// `webhook_url` is rejected with `400` before a receipt is ever created.
+pub async fn check_host(host: &str) -> Result<(), String> {
+    let addrs = tokio::net::lookup_host((host, 0))
+        .await
+        .map_err(|e| format!("could not resolve webhook host: {}", e))?;
+    let mut saw_any = false;
+    for addr in addrs {
+        saw_any = true;
+        if is_disallowed_ip(&addr.ip()) {
+            return Err(format!(
+                "webhook host resolves to a disallowed address ({})",
+                addr.ip()
+            ));
+        }
+    }
+    if !saw_any {
+        return Err("webhook host did not resolve to any address".to_string());
+    }
+    Ok(())
+}
+
+/// A [`reqwest::dns::Resolve`] that delegates to the system resolver and
+/// then drops any resolved address `is_disallowed_ip` rejects, so a webhook
+/// host that re-resolves to an internal address between submission and
+/// delivery (DNS rebinding) still can't be reached.
+#[derive(Clone, Default)]
+pub struct SsrfGuardResolver;
+
+impl Resolve for SsrfGuardResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+            let allowed: Vec<SocketAddr> = addrs.filter(|a| !is_disallowed_ip(&a.ip())).collect();
+            if allowed.is_empty() {
+                return Err(Box::<dyn std::error::Error + Send + Sync>::from(
+                    "webhook host has no non-internal resolved addresses",
+                ));
+            }
+            Ok(Box::new(allowed.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// A [`reqwest::Client`] built with [`SsrfGuardResolver`] — used for webhook
+/// delivery instead of `reqwest::Client::new()` so every attempt (including
+/// retries, each of which re-resolves) is protected, not just the
+/// submission-time `check_host` call.
+pub fn guarded_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(SsrfGuardResolver))
+        .build()
+        .expect("reqwest client with a custom resolver should always build")
+}
@@ -1,15 +1,68 @@
 use crate::config::Config;
 use crate::input::{OneHotVocab, TfIdfVocab, TokenIndexVocab};
+use crate::model_store::ModelStore;
 use crate::models::ModelRegistry;
+use crate::profile_cache::ProfileCache;
 use crate::receipt::ReceiptStore;
+use crate::trust_source::TrustSourceRegistry;
 
 use ark_bn254::Fr;
 use jolt_core::poly::commitment::dory::DoryCommitmentScheme;
+use jolt_core::transcripts::KeccakTranscript;
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use zkml_jolt_core::jolt::{JoltProverPreprocessing, JoltVerifierPreprocessing};
+use zkml_jolt_core::jolt::{JoltProverPreprocessing, JoltSNARK, JoltVerifierPreprocessing};
 
+#[allow(clippy::upper_case_acronyms)]
 type PCS = DoryCommitmentScheme;
+pub type Snark = JoltSNARK<Fr, PCS, KeccakTranscript>;
+
+/// Which proving implementation a `/prove` request (or preprocessing pass)
+/// should use. `JoltAtlas` is the real SNARK and the default; `Mock` skips
+/// proving entirely and is meant for local development and CI where minutes
+/// of real proving time would otherwise be wasted on every test run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProverBackendKind {
+    JoltAtlas,
+    Mock,
+}
+
+impl Default for ProverBackendKind {
+    fn default() -> Self {
+        ProverBackendKind::JoltAtlas
+    }
+}
+
+impl ProverBackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProverBackendKind::JoltAtlas => "jolt_atlas",
+            ProverBackendKind::Mock => "mock",
+        }
+    }
+
+    /// Human-readable name surfaced on receipts and in Moltbook posts —
+    /// the reason this lives next to `as_str` instead of just using it
+    /// directly is that `as_str` is a stable wire identifier while this is
+    /// free to read nicely in prose.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ProverBackendKind::JoltAtlas => "JOLT-Atlas on BN254",
+            ProverBackendKind::Mock => "Mock (no real proving)",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "jolt_atlas" => Some(ProverBackendKind::JoltAtlas),
+            "mock" => Some(ProverBackendKind::Mock),
+            _ => None,
+        }
+    }
+}
 
 pub struct PreprocessingCache {
     pub prover: JoltProverPreprocessing<Fr, PCS>,
@@ -22,17 +75,254 @@ pub struct PreprocessingCache {
 unsafe impl Send for PreprocessingCache {}
 unsafe impl Sync for PreprocessingCache {}
 
+/// Preprocessing artifacts for whichever backend produced them. `Mock` has
+/// no artifacts to preprocess — it's a unit variant purely so the cache can
+/// still record that the model is "ready" under that backend.
+pub enum BackendPreprocessing {
+    JoltAtlas(PreprocessingCache),
+    Mock,
+}
+
 pub enum VocabData {
     TfIdf(TfIdfVocab),
     OneHot(OneHotVocab),
     TokenIndex(TokenIndexVocab),
 }
 
+/// A coarse-grained stage within a single proving run, reported to SSE
+/// subscribers as the prover moves through it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofStage {
+    Queued,
+    WitnessGeneration,
+    Proving,
+    Verifying,
+    Done,
+    Failed,
+}
+
+impl ProofStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofStage::Queued => "queued",
+            ProofStage::WitnessGeneration => "witness_generation",
+            ProofStage::Proving => "proving",
+            ProofStage::Verifying => "verifying",
+            ProofStage::Done => "done",
+            ProofStage::Failed => "failed",
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self, ProofStage::Done | ProofStage::Failed)
+    }
+}
+
+/// One stage transition pushed to a receipt's SSE subscribers.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProofProgress {
+    pub stage: ProofStage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u128>,
+    /// Set only on the terminal `Done` event, so a subscriber can render the
+    /// finished receipt straight off the stream instead of following up with
+    /// a `GET /receipt/:id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prove_time_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_time_ms: Option<u128>,
+    /// Set only on the terminal `Failed` event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProofProgress {
+    pub fn new(stage: ProofStage, percent: Option<u8>, elapsed_ms: Option<u128>) -> Self {
+        Self {
+            stage,
+            percent,
+            elapsed_ms,
+            proof_hash: None,
+            proof_size: None,
+            prove_time_ms: None,
+            verify_time_ms: None,
+            error: None,
+        }
+    }
+
+    /// The terminal `Done` event, carrying the fields a subscriber needs to
+    /// render the finished receipt without polling `GET /receipt/:id`.
+    pub fn done(
+        elapsed_ms: Option<u128>,
+        proof_hash: String,
+        proof_size: usize,
+        prove_time_ms: u128,
+        verify_time_ms: u128,
+    ) -> Self {
+        Self {
+            stage: ProofStage::Done,
+            percent: Some(100),
+            elapsed_ms,
+            proof_hash: Some(proof_hash),
+            proof_size: Some(proof_size),
+            prove_time_ms: Some(prove_time_ms),
+            verify_time_ms: Some(verify_time_ms),
+            error: None,
+        }
+    }
+
+    /// The terminal `Failed` event, carrying the error message the receipt
+    /// itself was updated with.
+    pub fn failed(elapsed_ms: Option<u128>, error: String) -> Self {
+        Self {
+            stage: ProofStage::Failed,
+            percent: Some(100),
+            elapsed_ms,
+            proof_hash: None,
+            proof_size: None,
+            prove_time_ms: None,
+            verify_time_ms: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Capacity of each per-receipt progress broadcast channel — generous
+/// relative to the handful of stages a single proving run emits.
+const PROGRESS_CHANNEL_CAPACITY: usize = 16;
+
+/// Per-receipt `ProofProgress` broadcast channels backing the
+/// `GET /receipt/:id/events` SSE endpoint. Lives on `AppState` (rather than
+/// `ReceiptStore`) since it's transient proving-run telemetry, not part of
+/// the receipt's persisted state.
+#[derive(Clone, Default)]
+pub struct ProgressBroadcaster {
+    channels: Arc<dashmap::DashMap<String, tokio::sync::broadcast::Sender<ProofProgress>>>,
+}
+
+impl ProgressBroadcaster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register interest in `receipt_id`'s proving-stage updates.
+    pub fn subscribe(&self, receipt_id: &str) -> tokio::sync::broadcast::Receiver<ProofProgress> {
+        self.channels
+            .entry(receipt_id.to_string())
+            .or_insert_with(|| tokio::sync::broadcast::channel(PROGRESS_CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publish a stage transition, dropping the channel once it reaches a
+    /// terminal stage — no further events will ever be published for it.
+    pub fn publish(&self, receipt_id: &str, event: ProofProgress) {
+        if let Some(sender) = self.channels.get(receipt_id) {
+            let _ = sender.send(event.clone());
+        }
+        if event.stage.is_terminal() {
+            self.channels.remove(receipt_id);
+        }
+    }
+}
+
+/// Bookkeeping for an in-progress chunked model upload (`handlers::
+/// upload_resumable`), keyed by `upload_id`. Parts are appended to
+/// `onnx_path` in order; `next_part` is the part number the client must
+/// send next, so an interrupted upload can always resume by asking the
+/// server what it's still waiting for instead of restarting from scratch.
+/// `model_dir`/`onnx_path` are always a local staging scratch file — random-
+/// access part appends don't map onto an object store — committed to
+/// `AppState::store` (local or S3) only once `complete_upload` has every
+/// byte.
+pub struct UploadSession {
+    pub model_id: String,
+    pub model_dir: std::path::PathBuf,
+    pub onnx_path: std::path::PathBuf,
+    pub name: String,
+    pub description: String,
+    pub input_dim: usize,
+    pub labels: Vec<String>,
+    pub trace_length: usize,
+    pub total_size: u64,
+    pub bytes_received: u64,
+    pub next_part: u32,
+    /// When this session was created — `upload_resumable::spawn_pending_upload_reaper`
+    /// deletes the staging file and forgets the session once this is older
+    /// than `Config::pending_upload_ttl_secs`, so a client that starts an
+    /// upload and abandons it doesn't leak disk forever.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub config: Config,
     pub receipts: ReceiptStore,
     pub registry: Arc<RwLock<ModelRegistry>>,
     pub vocabs: Arc<HashMap<String, VocabData>>,
-    pub preprocessing: Arc<dashmap::DashMap<String, PreprocessingCache>>,
+    /// Wrapped in `Arc` (rather than owned per key) so a content-addressed
+    /// dedup hit (see `model_hash_index`) can register a second model_id
+    /// against the same already-preprocessed `BackendPreprocessing` without
+    /// cloning the underlying SNARK preprocessing artifacts.
+    pub preprocessing: Arc<dashmap::DashMap<(String, ProverBackendKind), Arc<BackendPreprocessing>>>,
+    pub attestation_key: Option<Arc<SigningKey>>,
+    pub proof_progress: ProgressBroadcaster,
+    pub trust_sources: Arc<TrustSourceRegistry>,
+    pub profile_cache: Arc<ProfileCache>,
+    pub pending_uploads: Arc<dashmap::DashMap<String, UploadSession>>,
+    /// `None` when `PROOF_ARCHIVE_S3_BUCKET` is unset — the whole feature is
+    /// gated on this, so local runs never touch an object store.
+    pub proof_archive: Option<Arc<crate::proof_archive::ProofArchive>>,
+    /// Per-subject token buckets backing `rate_limit::limit_prove` et al.,
+    /// one map per route so exhausting one doesn't affect the others.
+    pub prove_limiter: crate::rate_limit::RateLimiter,
+    pub batch_limiter: crate::rate_limit::RateLimiter,
+    pub upload_limiter: crate::rate_limit::RateLimiter,
+    pub prove_model_limiter: crate::rate_limit::RateLimiter,
+    /// Every `announce::Announcer` backend enabled via `ANNOUNCE_BACKENDS`,
+    /// broadcast to on the announce heartbeat in `main.rs`.
+    pub announcers: crate::announce::AnnouncerSet,
+    /// `Some` when the `activitypub` backend is enabled and signing-key
+    /// config is valid — `handlers::activitypub` 404s its routes otherwise.
+    pub activitypub: Option<std::sync::Arc<crate::announce::activitypub::ActivityPubAnnouncer>>,
+    /// Ed25519 `did:key` identity signing `?format=vc` receipts — always
+    /// present (generated on first run if `signing_key_path` doesn't exist
+    /// yet), unlike `attestation_key`, since `/did.json` needs to resolve
+    /// unconditionally for any deployment.
+    pub credential_key: Arc<crate::crypto::CredentialSigningKey>,
+    /// Persistent queue `handlers::prove`/`handlers::batch` enqueue proving
+    /// jobs into, drained by the bounded worker pool `queue::spawn_dispatcher`
+    /// starts at boot. Replaces firing `prover::prove_and_verify` directly.
+    pub prove_queue: Arc<crate::queue::ProofQueue>,
+    /// Opaque bearer tokens backing `api_keys::require_api_key`, seeded from
+    /// `Config::api_keys` at boot and grown via the admin issue endpoint.
+    pub api_keys: crate::api_keys::ApiKeyStore,
+    /// Job-shaped status tracking for `handlers::prove_model`'s
+    /// upload→convert→preprocess→prove pipeline, polled via
+    /// `GET /jobs/model/:id`.
+    pub model_jobs: crate::model_jobs::ModelJobStore,
+    /// Where `upload_model`'s `network.onnx`/`model.toml` pair lives —
+    /// local disk by default, or an S3-compatible object store when
+    /// `MODEL_STORE_BACKEND=s3`, so a model uploaded to one instance can
+    /// still be read and preprocessed on another.
+    pub store: Arc<dyn ModelStore>,
+    /// Maps a model's ONNX content hash (`crypto::keccak256` of the raw
+    /// bytes) to the `model_id` that first uploaded it. `upload_model`
+    /// checks this before writing a new blob: re-uploading an
+    /// already-registered network skips the multi-second
+    /// `Snark::prover_preprocess` and aliases the new model_id onto the
+    /// existing one's preprocessing instead.
+    pub model_hash_index: Arc<dashmap::DashMap<String, String>>,
+    /// Persistent queue `upload_model` enqueues its background preprocessing
+    /// step into, drained by the bounded worker pool
+    /// `preprocess_queue::spawn_dispatcher` starts at boot — replaces firing
+    /// `prover::backend_for(...).preprocess` directly off a bare `tokio::spawn`.
+    /// `GET /models/:id/status` polls it for retry/failure state.
+    pub preprocess_queue: Arc<crate::preprocess_queue::PreprocessQueue>,
 }
@@ -1,6 +1,42 @@
-use crate::receipt::{Receipt, ReceiptStatus};
+use crate::config::BrandConfig;
+use crate::locale::{messages, Locale};
+use crate::receipt::{AccessDenial, Receipt, ReceiptStatus};
+
+pub fn render(receipt: &Receipt, base_url: &str, locale: &Locale, brand: &BrandConfig) -> String {
+    let t = messages(*locale);
+
+    let wordmark_html = match &brand.logo_url {
+        Some(logo) => format!(
+            r#"<img src="{logo}" alt="{wordmark}" style="height: 20px; width: auto;">"#,
+            logo = logo,
+            wordmark = brand.wordmark,
+        ),
+        None => brand.wordmark.clone(),
+    };
+    let favicon_tag = brand
+        .favicon_url
+        .as_deref()
+        .map(|url| format!(r#"<link rel="icon" href="{}">"#, url))
+        .unwrap_or_default();
+    let footer_html = brand.footer_html.clone().unwrap_or_else(|| {
+        format!(
+            r#"<a href="/">{wordmark}</a> &middot; <a href="https://github.com/ICME-Lab/jolt-atlas" target="_blank">JOLT-Atlas</a> &middot; {footer_open_source}"#,
+            wordmark = brand.wordmark,
+            footer_open_source = t.footer_open_source,
+        )
+    });
+
+    // Only the solid semantic colors are brand-overridable; the derived
+    // *-bg/*-border tints stay theme-native since deriving them from an
+    // arbitrary override hex isn't worth the complexity here.
+    let accent = brand.accent.clone().unwrap_or_else(|| "#f0883e".to_string());
+    let green_dark = brand.green.clone().unwrap_or_else(|| "#3fb950".to_string());
+    let green_light = brand.green.clone().unwrap_or_else(|| "#16a34a".to_string());
+    let amber_dark = brand.amber.clone().unwrap_or_else(|| "#d29922".to_string());
+    let amber_light = brand.amber.clone().unwrap_or_else(|| "#d97706".to_string());
+    let red_dark = brand.red.clone().unwrap_or_else(|| "#f85149".to_string());
+    let red_light = brand.red.clone().unwrap_or_else(|| "#dc2626".to_string());
 
-pub fn render(receipt: &Receipt, base_url: &str) -> String {
     let status_class = match receipt.status {
         ReceiptStatus::Proving => "proving",
         ReceiptStatus::Verified => "verified",
@@ -8,91 +44,146 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
     };
 
     let status_label = match receipt.status {
-        ReceiptStatus::Proving => "Proving",
-        ReceiptStatus::Verified => "Verified",
-        ReceiptStatus::Failed => "Failed",
+        ReceiptStatus::Proving => t.status_proving,
+        ReceiptStatus::Verified => t.status_verified,
+        ReceiptStatus::Failed => t.status_failed,
     };
 
+    // With JS, the page holds open an SSE connection to /receipt/:id/events
+    // and updates the spinner text in place instead of reloading; the
+    // meta-refresh only fires for clients with JS disabled.
     let auto_refresh = if receipt.status == ReceiptStatus::Proving {
-        r#"<meta http-equiv="refresh" content="3">"#
+        r#"<noscript><meta http-equiv="refresh" content="3"></noscript>"#
     } else {
         ""
     };
+    let sse_script = if receipt.status == ReceiptStatus::Proving {
+        format!(
+            r#"
+    var es = new EventSource('/receipt/{receipt_id}/events');
+    es.onmessage = function(e) {{
+        try {{
+            var data = JSON.parse(e.data);
+            var el = document.getElementById('proving-stage-text');
+            if (el) {{ el.textContent = data.stage.replace(/_/g, ' '); }}
+            if (data.stage === 'done' || data.stage === 'failed') {{
+                es.close();
+                location.reload();
+            }}
+        }} catch (err) {{}}
+    }};
+    es.onerror = function() {{ es.close(); }};"#,
+            receipt_id = receipt.id,
+        )
+    } else {
+        String::new()
+    };
+
+    // A bundle only exists once the prover has persisted the proof bytes
+    // and program I/O to `proofs_dir`, which happens on verify success.
+    let download_bundle_button = if receipt.status == ReceiptStatus::Verified {
+        format!(
+            r#"<a class="share-btn" href="/receipt/{receipt_id}/bundle">
+                <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4"/><polyline points="7 10 12 15 17 10"/><line x1="12" y1="15" x2="12" y2="3"/></svg>
+                {download_bundle}
+            </a>"#,
+            receipt_id = receipt.id,
+            download_bundle = t.download_bundle,
+        )
+    } else {
+        String::new()
+    };
 
     let proof_section = match receipt.status {
         ReceiptStatus::Verified => {
             format!(
                 r#"<div class="card">
-                    <div class="card-header">Proof</div>
-                    <div class="row"><span class="row-label">Proof hash</span><span class="row-value mono">{}</span></div>
-                    <div class="row"><span class="row-label">Size</span><span class="row-value">{} bytes</span></div>
-                    <div class="row"><span class="row-label">Prove time</span><span class="row-value">{} ms</span></div>
-                    <div class="row last"><span class="row-label">Verify time</span><span class="row-value">{} ms</span></div>
+                    <div class="card-header">{card_proof}</div>
+                    <div class="row"><span class="row-label">{row_proof_hash}</span><span class="row-value mono">{proof_hash}</span></div>
+                    <div class="row"><span class="row-label">{row_size}</span><span class="row-value">{proof_size} {unit_bytes}</span></div>
+                    <div class="row"><span class="row-label">{row_prove_time}</span><span class="row-value">{prove_time_ms} {unit_ms}</span></div>
+                    <div class="row last"><span class="row-label">{row_verify_time}</span><span class="row-value">{verify_time_ms} {unit_ms}</span></div>
                 </div>"#,
-                receipt.proof_hash.as_deref().unwrap_or("\u{2014}"),
-                receipt.proof_size.map(|s| s.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
-                receipt.prove_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
-                receipt.verify_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
+                card_proof = t.card_proof,
+                row_proof_hash = t.row_proof_hash,
+                row_size = t.row_size,
+                row_prove_time = t.row_prove_time,
+                row_verify_time = t.row_verify_time,
+                unit_bytes = t.unit_bytes,
+                unit_ms = t.unit_ms,
+                proof_hash = receipt.proof_hash.as_deref().unwrap_or("\u{2014}"),
+                proof_size = receipt.proof_size.map(|s| s.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
+                prove_time_ms = receipt.prove_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
+                verify_time_ms = receipt.verify_time_ms.map(|t| t.to_string()).unwrap_or_else(|| "\u{2014}".to_string()),
             )
         }
         ReceiptStatus::Proving => {
-            r#"<div class="card">
-                <div class="card-header">Proof</div>
+            format!(
+                r#"<div class="card">
+                <div class="card-header">{card_proof}</div>
                 <div class="proving-notice" role="status">
                     <div class="spinner"></div>
-                    <span>Generating SNARK proof. This page refreshes automatically.</span>
+                    <span id="proving-stage-text">{proving_notice}</span>
                 </div>
-            </div>"#
-                .to_string()
+            </div>"#,
+                card_proof = t.card_proof,
+                proving_notice = t.proving_notice,
+            )
         }
         ReceiptStatus::Failed => {
             format!(
                 r#"<div class="card">
-                    <div class="card-header">Error</div>
-                    <div class="error-notice">{}</div>
+                    <div class="card-header">{card_error}</div>
+                    <div class="error-notice">{error}</div>
                 </div>"#,
-                receipt.error.as_deref().unwrap_or("Unknown error")
+                card_error = t.card_error,
+                error = receipt.error.as_deref().unwrap_or(t.unknown_error),
             )
         }
     };
 
     let completed_at = receipt
         .completed_at
-        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .map(|dt| locale.format_datetime(&dt))
         .unwrap_or_else(|| "\u{2014}".to_string());
 
     let receipt_url = format!("{}/receipt/{}", base_url, receipt.id);
     let badge_url = format!("{}/badge/{}", base_url, receipt.id);
     let proof_string = format!("clawproof:{}:{}:{}", receipt.id, receipt.output.label, receipt.status.as_str());
 
+    let confidence_str = locale.format_confidence(receipt.output.confidence);
+
     // OG description
     let og_description = format!(
-        "Cryptographically verified ML inference. Model: {}. Result: {} ({:.1}% confidence). Status: {}.",
+        "Cryptographically verified ML inference. Model: {}. Result: {} ({} {}). Status: {}.",
         receipt.model_name,
         receipt.output.label,
-        receipt.output.confidence * 100.0,
+        confidence_str,
+        t.confidence_suffix,
         status_label,
     );
     let og_title = format!(
-        "ClawProof \u{2014} {} ({:.1}%)",
+        "ClawProof \u{2014} {} ({})",
         receipt.output.label,
-        receipt.output.confidence * 100.0,
+        confidence_str,
     );
 
     // Pre-formatted share texts (escaped for JS strings)
     let verify_me_text = format!(
-        "I made this decision: {} ({:.1}% confidence) \u{2014} ML inference cryptographically verified with a @novanet_zkp zkML proof. Don\\'t trust me, verify it: {}",
+        "I made this decision: {} ({} {}) \u{2014} ML inference cryptographically verified with a @novanet_zkp zkML proof. Don\\'t trust me, verify it: {}",
         receipt.output.label,
-        receipt.output.confidence * 100.0,
+        confidence_str,
+        t.confidence_suffix,
         receipt_url,
     );
 
     format!(
         r#"<!DOCTYPE html>
-<html lang="en" data-theme="dark">
+<html lang="en" data-theme="{default_theme}">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    {favicon_tag}
     {auto_refresh}
     <title>{og_title}</title>
 
@@ -102,7 +193,7 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
     <meta property="og:description" content="{og_description}" />
     <meta property="og:url" content="{receipt_url}" />
     <meta property="og:image" content="{badge_url}" />
-    <meta property="og:site_name" content="ClawProof" />
+    <meta property="og:site_name" content="{brand_wordmark}" />
 
     <!-- Twitter Card -->
     <meta name="twitter:card" content="summary" />
@@ -123,14 +214,14 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
             --text-primary: #c9d1d9;
             --text-secondary: #8b949e;
             --text-tertiary: #484f58;
-            --accent: #f0883e;
-            --green: #3fb950;
+            --accent: {accent};
+            --green: {green_dark};
             --green-bg: rgba(63,185,80,0.1);
             --green-border: rgba(63,185,80,0.3);
-            --amber: #d29922;
+            --amber: {amber_dark};
             --amber-bg: rgba(210,153,34,0.1);
             --amber-border: rgba(210,153,34,0.3);
-            --red: #f85149;
+            --red: {red_dark};
             --red-bg: rgba(248,81,73,0.1);
             --red-border: rgba(248,81,73,0.3);
             --link: #58a6ff;
@@ -147,19 +238,46 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
             --text-primary: #111827;
             --text-secondary: #4b5563;
             --text-tertiary: #9ca3af;
-            --accent: #f0883e;
-            --green: #16a34a;
+            --accent: {accent};
+            --green: {green_light};
             --green-bg: #f0fdf4;
             --green-border: #bbf7d0;
-            --amber: #d97706;
+            --amber: {amber_light};
             --amber-bg: #fffbeb;
             --amber-border: #fde68a;
-            --red: #dc2626;
+            --red: {red_light};
             --red-bg: #fef2f2;
             --red-border: #fecaca;
             --link: #2563eb;
         }}
 
+        /* "auto" follows the OS preference until the visitor toggles
+           explicitly; the dark palette above already covers :root, so only
+           a light override is needed here (GitHub's data-color-mode model). */
+        @media (prefers-color-scheme: light) {{
+            [data-theme="auto"] {{
+                --bg: #ffffff;
+                --bg-secondary: #f7f8fa;
+                --bg-tertiary: #eef0f4;
+                --border: #d8dce3;
+                --border-light: #e8ebf0;
+                --text-primary: #111827;
+                --text-secondary: #4b5563;
+                --text-tertiary: #9ca3af;
+                --accent: {accent};
+                --green: {green_light};
+                --green-bg: #f0fdf4;
+                --green-border: #bbf7d0;
+                --amber: {amber_light};
+                --amber-bg: #fffbeb;
+                --amber-border: #fde68a;
+                --red: {red_light};
+                --red-bg: #fef2f2;
+                --red-border: #fecaca;
+                --link: #2563eb;
+            }}
+        }}
+
         body {{
             font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', 'Inter', system-ui, sans-serif;
             background: var(--bg); color: var(--text-primary); min-height: 100vh;
@@ -332,110 +450,111 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
     <div class="page">
         <div class="page-header">
             <div class="header-left">
-                <a class="wordmark" href="/">ClawProof <span>/ receipt</span></a>
+                <a class="wordmark" href="/">{wordmark_html} <span>/ receipt</span></a>
             </div>
             <div class="header-right">
                 <span class="status-badge {status_class}" aria-label="Proof status: {status_label}">
                     <span class="status-dot"></span>
                     {status_label}
                 </span>
-                <button class="theme-toggle" id="theme-toggle" onclick="toggleTheme()" title="Toggle dark/light mode"></button>
+                <button class="theme-toggle" id="theme-toggle" onclick="toggleTheme()" title="Cycle auto/light/dark mode"></button>
             </div>
         </div>
 
         <div class="prediction-card">
             <div class="prediction-label">{label}</div>
-            <div class="prediction-confidence">{confidence:.1}% confidence</div>
+            <div class="prediction-confidence">{confidence_str} {confidence_suffix}</div>
         </div>
 
         <div class="proof-string-bar">
-            <span class="proof-string-label">Proof ID</span>
+            <span class="proof-string-label">{proof_id_label}</span>
             <span class="proof-string-value" id="proof-string">{proof_string}</span>
-            <button class="copy-btn" onclick="copyText(document.getElementById('proof-string').textContent, 'Proof string copied')">Copy</button>
+            <button class="copy-btn" onclick="copyText(document.getElementById('proof-string').textContent, '{toast_proof_string_copied}')">{copy_button}</button>
         </div>
 
         <div class="card">
-            <div class="card-header">Model</div>
-            <div class="row"><span class="row-label">Name</span><span class="row-value">{model_name}</span></div>
-            <div class="row"><span class="row-label">ID</span><span class="row-value mono">{model_id}</span></div>
-            <div class="row last"><span class="row-label">Hash</span><span class="row-value mono">{model_hash}</span></div>
+            <div class="card-header">{card_model}</div>
+            <div class="row"><span class="row-label">{row_name}</span><span class="row-value">{model_name}</span></div>
+            <div class="row"><span class="row-label">{row_id}</span><span class="row-value mono">{model_id}</span></div>
+            <div class="row last"><span class="row-label">{row_hash}</span><span class="row-value mono">{model_hash}</span></div>
         </div>
 
         <div class="card">
-            <div class="card-header">Hashes</div>
-            <div class="row"><span class="row-label">Input</span><span class="row-value mono">{input_hash}</span></div>
-            <div class="row last"><span class="row-label">Output</span><span class="row-value mono">{output_hash}</span></div>
+            <div class="card-header">{card_hashes}</div>
+            <div class="row"><span class="row-label">{row_input}</span><span class="row-value mono">{input_hash}</span></div>
+            <div class="row last"><span class="row-label">{row_output}</span><span class="row-value mono">{output_hash}</span></div>
         </div>
 
         {proof_section}
 
         <div class="card">
-            <div class="card-header">Metadata</div>
-            <div class="row"><span class="row-label">Receipt ID</span><span class="row-value mono">{receipt_id}</span></div>
-            <div class="row"><span class="row-label">Created</span><span class="row-value">{created_at}</span></div>
-            <div class="row last"><span class="row-label">Completed</span><span class="row-value">{completed_at}</span></div>
+            <div class="card-header">{card_metadata}</div>
+            <div class="row"><span class="row-label">{row_receipt_id}</span><span class="row-value mono">{receipt_id}</span></div>
+            <div class="row"><span class="row-label">{row_created}</span><span class="row-value">{created_at}</span></div>
+            <div class="row last"><span class="row-label">{row_completed}</span><span class="row-value">{completed_at}</span></div>
         </div>
 
         <!-- Share section -->
         <div class="share-section">
-            <div class="share-section-header">Share this proof</div>
+            <div class="share-section-header">{share_section_header}</div>
             <div class="share-url-bar">
                 <span class="share-url" id="share-url">{receipt_url}</span>
-                <button class="copy-btn" onclick="copyText(document.getElementById('share-url').textContent, 'Link copied')">Copy</button>
+                <button class="copy-btn" onclick="copyText(document.getElementById('share-url').textContent, '{toast_link_copied}')">{copy_button}</button>
             </div>
             <div class="share-buttons">
                 <a class="share-btn primary" id="x-share-btn" href="https://x.com/intent/tweet?text={x_share_text_encoded}" target="_blank" rel="noopener">
                     <svg viewBox="0 0 24 24" fill="currentColor"><path d="M18.244 2.25h3.308l-7.227 8.26 8.502 11.24H16.17l-5.214-6.817L4.99 21.75H1.68l7.73-8.835L1.254 2.25H8.08l4.713 6.231zm-1.161 17.52h1.833L7.084 4.126H5.117z"/></svg>
-                    Share on X
+                    {share_on_x}
                 </a>
                 <button class="share-btn" onclick="copyVerifyMe()">
                     <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M12 22s8-4 8-10V5l-8-3-8 3v7c0 6 8 10 8 10z"/><path d="m9 12 2 2 4-4"/></svg>
-                    Copy "Verify me"
+                    {copy_verify_me}
                 </button>
-                <button class="share-btn" onclick="copyText(document.getElementById('proof-string').textContent, 'Proof string copied')">
+                <button class="share-btn" onclick="copyText(document.getElementById('proof-string').textContent, '{toast_proof_string_copied}')">
                     <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><rect x="9" y="9" width="13" height="13" rx="2" ry="2"/><path d="M5 15H4a2 2 0 0 1-2-2V4a2 2 0 0 1 2-2h9a2 2 0 0 1 2 2v1"/></svg>
-                    Copy proof string
+                    {copy_proof_string}
                 </button>
+                {download_bundle_button}
             </div>
         </div>
 
         <div class="footer">
-            <a href="/">ClawProof</a> &middot;
-            <a href="https://github.com/ICME-Lab/jolt-atlas" target="_blank">JOLT-Atlas</a> &middot;
-            Open source (MIT)
+            {footer_html}
         </div>
     </div>
 
     <div class="toast" id="toast"></div>
 
     <script>
+    var THEME_ORDER = ['auto', 'light', 'dark'];
+    var THEME_ICONS = {{ auto: '◐', light: '☀', dark: '☾' }};
     function initTheme() {{
         var saved = localStorage.getItem('cp-theme');
-        var theme = saved || 'dark';
+        var theme = saved || '{default_theme}';
         document.documentElement.setAttribute('data-theme', theme);
         updateToggleIcon(theme);
     }}
     function toggleTheme() {{
-        var current = document.documentElement.getAttribute('data-theme');
-        var next = current === 'dark' ? 'light' : 'dark';
+        var current = document.documentElement.getAttribute('data-theme') || 'auto';
+        var next = THEME_ORDER[(THEME_ORDER.indexOf(current) + 1) % THEME_ORDER.length];
         document.documentElement.setAttribute('data-theme', next);
         localStorage.setItem('cp-theme', next);
         updateToggleIcon(next);
     }}
     function updateToggleIcon(theme) {{
-        document.getElementById('theme-toggle').textContent = theme === 'dark' ? '\u2600' : '\u263E';
+        document.getElementById('theme-toggle').textContent = THEME_ICONS[theme] || THEME_ICONS.auto;
     }}
 
     function copyText(text, msg) {{
         navigator.clipboard.writeText(text).then(function() {{
-            showToast(msg || 'Copied');
+            showToast(msg || '{copy_button}');
         }});
     }}
 
     function copyVerifyMe() {{
         var text = '{verify_me_text}';
         navigator.clipboard.writeText(text).then(function() {{
-            showToast('"Verify me" message copied');
+            showToast('{toast_verify_me_copied}');
         }});
     }}
 
@@ -447,15 +566,29 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
     }}
 
     initTheme();
+    {sse_script}
     </script>
 </body>
 </html>"#,
+        default_theme = brand.default_theme,
+        favicon_tag = favicon_tag,
+        brand_wordmark = brand.wordmark,
+        wordmark_html = wordmark_html,
+        footer_html = footer_html,
+        accent = accent,
+        green_dark = green_dark,
+        green_light = green_light,
+        amber_dark = amber_dark,
+        amber_light = amber_light,
+        red_dark = red_dark,
+        red_light = red_light,
         auto_refresh = auto_refresh,
         receipt_id = receipt.id,
         status_class = status_class,
         status_label = status_label,
         label = receipt.output.label,
-        confidence = receipt.output.confidence * 100.0,
+        confidence_str = confidence_str,
+        confidence_suffix = t.confidence_suffix,
         model_name = receipt.model_name,
         model_id = receipt.model_id,
         model_hash = receipt.model_hash,
@@ -463,18 +596,138 @@ pub fn render(receipt: &Receipt, base_url: &str) -> String {
         output_hash = receipt.output_hash,
         proof_section = proof_section,
         proof_string = proof_string,
-        created_at = receipt.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        created_at = locale.format_datetime(&receipt.created_at),
         completed_at = completed_at,
         receipt_url = receipt_url,
         badge_url = badge_url,
         og_title = og_title,
         og_description = og_description,
+        card_model = t.card_model,
+        card_hashes = t.card_hashes,
+        card_metadata = t.card_metadata,
+        row_name = t.row_name,
+        row_id = t.row_id,
+        row_hash = t.row_hash,
+        row_input = t.row_input,
+        row_output = t.row_output,
+        row_receipt_id = t.row_receipt_id,
+        row_created = t.row_created,
+        row_completed = t.row_completed,
+        proof_id_label = t.proof_id_label,
+        copy_button = t.copy_button,
+        share_section_header = t.share_section_header,
+        share_on_x = t.share_on_x,
+        copy_verify_me = t.copy_verify_me,
+        copy_proof_string = t.copy_proof_string,
+        download_bundle_button = download_bundle_button,
+        toast_link_copied = t.toast_link_copied,
+        toast_proof_string_copied = t.toast_proof_string_copied,
+        toast_verify_me_copied = t.toast_verify_me_copied,
         x_share_text_encoded = urlencoding::encode(&format!(
-            "My agent classified this as {} ({:.1}% confidence) \u{2014} ML inference cryptographically verified with a @novanet_zkp zkML proof.\n\nDon't trust me, verify it:\n{}",
+            "My agent classified this as {} ({} {}) \u{2014} ML inference cryptographically verified with a @novanet_zkp zkML proof.\n\nDon't trust me, verify it:\n{}",
             receipt.output.label,
-            receipt.output.confidence * 100.0,
+            confidence_str,
+            t.confidence_suffix,
             receipt_url,
         )),
         verify_me_text = verify_me_text,
+        sse_script = sse_script,
     )
 }
+
+/// A shared page chrome (dark-themed, matching `render()`) for the small
+/// single-message pages below — "no longer available" and "enter
+/// passphrase" don't need the full receipt layout.
+fn render_notice_page(title: &str, heading: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en" data-theme="dark">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{title}</title>
+    <style>
+        *, *::before, *::after {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        :root, [data-theme="dark"] {{
+            --bg: #0d1117; --bg-secondary: #161b22; --border: #30363d;
+            --text-primary: #c9d1d9; --text-secondary: #8b949e;
+            --accent: #f0883e; --red: #f85149;
+        }}
+        body {{
+            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', 'Inter', system-ui, sans-serif;
+            background: var(--bg); color: var(--text-primary); min-height: 100vh;
+            -webkit-font-smoothing: antialiased;
+        }}
+        .page {{ max-width: 420px; margin: 0 auto; padding: 4rem 1.25rem; text-align: center; }}
+        .wordmark {{ font-size: 1rem; font-weight: 600; color: var(--text-primary); text-decoration: none; }}
+        .card {{
+            margin-top: 2rem; padding: 1.5rem; border: 1px solid var(--border);
+            border-radius: 8px; background: var(--bg-secondary); text-align: left;
+        }}
+        h1 {{ font-size: 1.125rem; font-weight: 600; margin-top: 1.5rem; }}
+        p {{ font-size: 0.875rem; color: var(--text-secondary); margin-top: 0.5rem; }}
+        label {{ display: block; font-size: 0.8125rem; color: var(--text-secondary); margin-bottom: 0.375rem; }}
+        input[type="password"] {{
+            width: 100%; padding: 0.5rem 0.625rem; font-size: 0.875rem;
+            background: var(--bg); border: 1px solid var(--border); border-radius: 6px;
+            color: var(--text-primary); margin-bottom: 0.75rem;
+        }}
+        button {{
+            width: 100%; padding: 0.5rem; font-size: 0.875rem; font-weight: 500;
+            background: var(--accent); color: #fff; border: none; border-radius: 6px; cursor: pointer;
+        }}
+        button:hover {{ opacity: 0.9; }}
+        .error {{ color: var(--red); font-size: 0.8125rem; margin-bottom: 0.75rem; }}
+    </style>
+</head>
+<body>
+    <div class="page">
+        <a class="wordmark" href="/">ClawProof</a>
+        <h1>{heading}</h1>
+        {body}
+    </div>
+</body>
+</html>"#,
+        title = title,
+        heading = heading,
+        body = body,
+    )
+}
+
+/// Rendered in place of the proof page when `receipt.access_denial()` is
+/// `Some` — the sharing link expired or hit its view limit.
+pub fn render_unavailable(denial: &AccessDenial, locale: &Locale) -> String {
+    let t = messages(*locale);
+    let reason = match denial {
+        AccessDenial::Expired => t.unavailable_expired,
+        AccessDenial::ViewLimitReached => t.unavailable_view_limit,
+    };
+    render_notice_page(t.unavailable_title, t.unavailable_title, &format!("<p>{}</p>", reason))
+}
+
+/// Rendered for a passphrase-protected receipt until the passphrase is
+/// verified via `POST /receipt/:id/unlock`. `error` is set after a failed
+/// unlock attempt to show `locked_incorrect` above the form.
+pub fn render_locked(receipt_id: &str, locale: &Locale, error: bool) -> String {
+    let t = messages(*locale);
+    let error_html = if error {
+        format!(r#"<p class="error">{}</p>"#, t.locked_incorrect)
+    } else {
+        String::new()
+    };
+    let body = format!(
+        r#"<p>{prompt}</p>
+        <form class="card" method="post" action="/receipt/{id}/unlock">
+            {error_html}
+            <label for="passphrase">{label}</label>
+            <input type="password" id="passphrase" name="passphrase" autofocus>
+            <button type="submit">{unlock}</button>
+        </form>"#,
+        prompt = t.locked_prompt,
+        id = receipt_id,
+        error_html = error_html,
+        label = t.locked_passphrase_label,
+        unlock = t.locked_unlock_button,
+    );
+    render_notice_page(t.locked_title, t.locked_title, &body)
+}
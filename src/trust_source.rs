@@ -0,0 +1,80 @@
+//! Pluggable trust-data sources for agent lookups.
+//!
+//! `handlers::agent_lookup` used to bake the Moltbook REST call directly
+//! into the handler. Instead, each platform implements `TrustSource`,
+//! returning a normalized `RawAgentData` regardless of what shape the
+//! upstream API actually uses — the bucketing functions in
+//! `handlers::agent_lookup` only ever see this normalized shape. Adding a
+//! new platform is "implement the trait + register it in
+//! `TrustSourceRegistry`", not forking the handler.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub mod fediverse;
+pub mod moltbook;
+
+/// Platform-agnostic snapshot of an agent's trust signals, post-normalization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawAgentData {
+    pub karma: i64,
+    pub follower_count: i64,
+    pub following_count: Option<i64>,
+    pub posts: i64,
+    pub comments: i64,
+    pub days_old: f64,
+    pub is_claimed: bool,
+    pub x_verified: bool,
+    /// Recent post/comment bodies (titles folded in where applicable), used
+    /// by `compute_spam_score` for content-similarity bucketing.
+    pub recent_texts: Vec<String>,
+}
+
+/// Failure fetching or normalizing an agent's trust data from a source.
+pub enum SourceError {
+    /// The source has no record of this agent.
+    NotFound,
+    /// The source requires configuration (e.g. an API key) that isn't set.
+    NotConfigured,
+    /// The source reached the upstream but it errored or returned something
+    /// we couldn't parse.
+    Upstream(String),
+    /// The source requires a signed request (e.g. ActivityPub "authorized
+    /// fetch") and producing the signature itself failed — distinct from
+    /// `Upstream` since the upstream was never actually reached.
+    SigningFailed(String),
+}
+
+/// A platform that can be looked up for agent trust signals, keyed by the
+/// scheme string passed to `TrustSourceRegistry::register` (e.g. "moltbook").
+#[async_trait]
+pub trait TrustSource: Send + Sync {
+    /// Fetch and normalize trust data for `agent` (a platform-specific
+    /// username, already stripped of any URL wrapper by the caller).
+    async fn fetch(&self, agent: &str) -> Result<RawAgentData, SourceError>;
+}
+
+/// Registry of `TrustSource` implementations keyed by scheme prefix, held on
+/// `AppState` so `agent_lookup` can dispatch without knowing which
+/// platforms exist. Mirrors the `ModelRegistry` pattern in `models.rs`.
+#[derive(Clone, Default)]
+pub struct TrustSourceRegistry {
+    sources: HashMap<String, Arc<dyn TrustSource>>,
+}
+
+impl TrustSourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, scheme: &str, source: Arc<dyn TrustSource>) {
+        self.sources.insert(scheme.to_string(), source);
+    }
+
+    pub fn get(&self, scheme: &str) -> Option<Arc<dyn TrustSource>> {
+        self.sources.get(scheme).cloned()
+    }
+}
@@ -0,0 +1,369 @@
+//! Fediverse `TrustSource` implementation — resolves a WebFinger handle
+//! (`user@instance`) to an ActivityPub actor and normalizes its public
+//! collections/attachments into `RawAgentData`.
+//!
+//! No karma-equivalent signal exists in ActivityPub, so `karma` is always 0;
+//! `is_claimed` is always `true` since a resolvable actor is inherently an
+//! owned identity (there's no "unclaimed" concept like Moltbook's).
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::crypto::HttpSignatureKey;
+
+use super::{RawAgentData, SourceError, TrustSource};
+
+/// Hard ceiling on a single ActivityPub collection/page response, enforced
+/// while streaming the body so a malicious instance can't make us buffer an
+/// unbounded reply before we ever get to parse it — `.json()` alone has no
+/// size limit. 2 MiB comfortably fits a paginated outbox/followers page;
+/// legitimate instances paginate well under this.
+const MAX_ACTIVITYPUB_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Hard ceiling on the number of outbox items read per page. An outbox page
+/// is fully attacker-controlled (see `FediverseSource::new`), and
+/// `compute_spam_score`'s near-duplicate pass is O(n^2) in the number of
+/// texts it's handed — matches `agent_lookup::PAIRWISE_TEXT_LIMIT`.
+const MAX_OUTBOX_ITEMS: usize = 64;
+
+pub struct FediverseSource {
+    client: reqwest::Client,
+    /// When set, outbound actor/collection fetches are signed per
+    /// ActivityPub's "authorized fetch" convention. WebFinger discovery is
+    /// intentionally left unsigned — it's meant to be publicly resolvable
+    /// and instances don't gate it the way they gate actor fetches.
+    signing_key: Option<Arc<HttpSignatureKey>>,
+}
+
+impl FediverseSource {
+    pub fn new(signing_key: Option<Arc<HttpSignatureKey>>) -> Self {
+        Self {
+            // The actor object a remote instance returns is fully
+            // attacker-controlled — its `followers`/`following`/`outbox`/
+            // `first` page URLs get fetched right here. Route every request
+            // through the SSRF-guarded resolver so none of them can be
+            // pointed at cloud metadata or another internal service.
+            client: crate::ssrf::guarded_client(),
+            signing_key,
+        }
+    }
+}
+
+impl Default for FediverseSource {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl FediverseSource {
+    /// Build a GET request for `url`, attaching a `Signature`/`Date` header
+    /// pair when authorized-fetch signing is configured. WebFinger lookups
+    /// bypass this and call `self.client` directly.
+    fn signed_request(&self, url: &str) -> anyhow::Result<reqwest::RequestBuilder> {
+        let mut builder = self.client.get(url).header("Accept", "application/activity+json");
+        if let Some(key) = &self.signing_key {
+            let parsed = reqwest::Url::parse(url)?;
+            let host = match parsed.port() {
+                Some(port) => format!("{}:{}", parsed.host_str().unwrap_or_default(), port),
+                None => parsed.host_str().unwrap_or_default().to_string(),
+            };
+            let path_and_query = match parsed.query() {
+                Some(q) => format!("{}?{}", parsed.path(), q),
+                None => parsed.path().to_string(),
+            };
+            let date = chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            let signature = crate::crypto::sign_http_get(key, &path_and_query, &host, &date)?;
+            builder = builder
+                .header("Date", date)
+                .header("Signature", signature)
+                .header("Host", host);
+        }
+        Ok(builder)
+    }
+}
+
+#[async_trait]
+impl TrustSource for FediverseSource {
+    async fn fetch(&self, agent: &str) -> Result<RawAgentData, SourceError> {
+        let (user, domain) = agent.split_once('@').ok_or(SourceError::NotFound)?;
+
+        let webfinger_url = format!(
+            "https://{domain}/.well-known/webfinger?resource=acct:{user}@{domain}"
+        );
+        let resp = self.client.get(&webfinger_url).send().await.map_err(|e| {
+            warn!("[clawproof] WebFinger request failed: {}", e);
+            SourceError::Upstream("Failed to reach WebFinger endpoint".to_string())
+        })?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(SourceError::NotFound);
+        }
+        if !resp.status().is_success() {
+            return Err(SourceError::Upstream(format!(
+                "WebFinger returned status {}",
+                resp.status().as_u16()
+            )));
+        }
+        let webfinger: WebfingerResponse = resp.json().await.map_err(|e| {
+            warn!("[clawproof] Failed to parse WebFinger response: {}", e);
+            SourceError::Upstream("Failed to parse WebFinger response".to_string())
+        })?;
+
+        let actor_url = webfinger
+            .links
+            .iter()
+            .find(|l| {
+                l.rel.as_deref() == Some("self")
+                    && l.kind.as_deref() == Some("application/activity+json")
+            })
+            .and_then(|l| l.href.clone())
+            .ok_or(SourceError::NotFound)?;
+
+        let actor_resp = self.signed_request(&actor_url).map_err(|e| {
+            warn!("[clawproof] Failed to sign actor request: {}", e);
+            SourceError::SigningFailed(e.to_string())
+        })?
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("[clawproof] Actor fetch failed: {}", e);
+                SourceError::Upstream("Failed to reach actor endpoint".to_string())
+            })?;
+        if !actor_resp.status().is_success() {
+            return Err(SourceError::Upstream(format!(
+                "Actor endpoint returned status {}",
+                actor_resp.status().as_u16()
+            )));
+        }
+        let person: ApPerson = actor_resp.json().await.map_err(|e| {
+            warn!("[clawproof] Failed to parse actor object: {}", e);
+            SourceError::Upstream("Failed to parse actor object".to_string())
+        })?;
+
+        let days_old = person
+            .published
+            .as_deref()
+            .and_then(|ts| {
+                chrono::DateTime::parse_from_rfc3339(ts).ok().map(|created| {
+                    let now = chrono::Utc::now();
+                    (now - created.with_timezone(&chrono::Utc)).num_seconds() as f64 / 86400.0
+                })
+            })
+            .unwrap_or(0.0);
+
+        let follower_count = match &person.followers {
+            Some(v) => resolve_collection_count(self, v).await.unwrap_or(0),
+            None => 0,
+        };
+        // Mirror Moltbook's `None` handling: a missing `following` field means
+        // "unknown", not zero, so `bucket_follower_ratio` falls back to its
+        // neutral middle bucket instead of assuming a perfect ratio.
+        let following_count = match &person.following {
+            Some(v) => resolve_collection_count(self, v).await,
+            None => None,
+        };
+        let posts = match &person.outbox {
+            Some(v) => resolve_collection_count(self, v).await.unwrap_or(0),
+            None => 0,
+        };
+        let recent_texts = match &person.outbox {
+            Some(v) => fetch_outbox_texts(self, v).await,
+            None => Vec::new(),
+        };
+
+        let x_verified = person.verified.unwrap_or(false) || has_identity_proof(&person.attachment);
+
+        Ok(RawAgentData {
+            karma: 0,
+            follower_count,
+            following_count,
+            posts,
+            comments: 0,
+            days_old,
+            is_claimed: true,
+            x_verified,
+            recent_texts,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// WebFinger / ActivityStreams types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct WebfingerResponse {
+    #[serde(default)]
+    links: Vec<WebfingerLink>,
+}
+
+#[derive(Deserialize)]
+struct WebfingerLink {
+    #[serde(default)]
+    rel: Option<String>,
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    href: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApPerson {
+    #[serde(default)]
+    published: Option<String>,
+    #[serde(default)]
+    followers: Option<Value>,
+    #[serde(default)]
+    following: Option<Value>,
+    #[serde(default)]
+    outbox: Option<Value>,
+    #[serde(default)]
+    attachment: Option<Vec<ApAttachment>>,
+    /// Not part of core ActivityStreams, but some platforms (e.g. Mastodon
+    /// forks) set this directly instead of (or alongside) an identity-proof
+    /// attachment.
+    #[serde(default)]
+    verified: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct ApAttachment {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+}
+
+fn has_identity_proof(attachments: &Option<Vec<ApAttachment>>) -> bool {
+    attachments
+        .as_ref()
+        .map(|atts| {
+            atts.iter()
+                .any(|a| a.kind.as_deref().is_some_and(|k| k.eq_ignore_ascii_case("IdentityProof")))
+        })
+        .unwrap_or(false)
+}
+
+// ---------------------------------------------------------------------------
+// Collection resolution (followers/following/outbox)
+// ---------------------------------------------------------------------------
+
+/// Pull `totalItems` (or fall back to counting `items`/`orderedItems`) out of
+/// an embedded or dereferenced ActivityStreams collection object.
+fn collection_count_from_json(v: &Value) -> Option<i64> {
+    if let Some(n) = v.get("totalItems").and_then(|x| x.as_i64()) {
+        return Some(n);
+    }
+    if let Some(items) = v
+        .get("orderedItems")
+        .or_else(|| v.get("items"))
+        .and_then(|x| x.as_array())
+    {
+        return Some(items.len() as i64);
+    }
+    None
+}
+
+/// A collection field is either an inline object or a URL to dereference.
+/// Collections are frequently paginated — a bare count may live on the
+/// top-level object, or only on its `first` page.
+async fn resolve_collection_count(source: &FediverseSource, v: &Value) -> Option<i64> {
+    let collection: Value = if let Some(url) = v.as_str() {
+        fetch_activitypub_json(source, url).await?
+    } else {
+        v.clone()
+    };
+
+    if let Some(n) = collection_count_from_json(&collection) {
+        return Some(n);
+    }
+
+    match collection.get("first") {
+        Some(Value::String(first_url)) => {
+            let page = fetch_activitypub_json(source, first_url).await?;
+            collection_count_from_json(&page)
+        }
+        Some(first_obj) => collection_count_from_json(first_obj),
+        None => None,
+    }
+}
+
+/// Fetch the outbox's first page and pull `content` off any `Note` objects
+/// wrapped in its activities, for `compute_spam_score` to analyze.
+async fn fetch_outbox_texts(source: &FediverseSource, outbox: &Value) -> Vec<String> {
+    let collection = match outbox.as_str() {
+        Some(url) => match fetch_activitypub_json(source, url).await {
+            Some(c) => c,
+            None => return Vec::new(),
+        },
+        None => outbox.clone(),
+    };
+
+    let page = match collection
+        .get("orderedItems")
+        .or_else(|| collection.get("items"))
+    {
+        Some(Value::Array(_)) => collection,
+        _ => match collection.get("first") {
+            Some(Value::String(first_url)) => {
+                fetch_activitypub_json(source, first_url).await.unwrap_or(collection)
+            }
+            Some(first_obj) => first_obj.clone(),
+            None => collection,
+        },
+    };
+
+    let items = page
+        .get("orderedItems")
+        .or_else(|| page.get("items"))
+        .and_then(|x| x.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    items
+        .iter()
+        .take(MAX_OUTBOX_ITEMS)
+        .filter_map(|activity| {
+            let object = activity.get("object").unwrap_or(activity);
+            let is_note = object.get("type").and_then(|t| t.as_str()) == Some("Note");
+            if !is_note {
+                return None;
+            }
+            object
+                .get("content")
+                .and_then(|c| c.as_str())
+                .map(|s| s.to_string())
+                .filter(|s| !s.is_empty())
+        })
+        .collect()
+}
+
+async fn fetch_activitypub_json(source: &FediverseSource, url: &str) -> Option<Value> {
+    let resp = source.signed_request(url).ok()?.send().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    read_capped_json(resp).await
+}
+
+/// Stream `resp`'s body into a buffer, bailing out as soon as it would
+/// exceed `MAX_ACTIVITYPUB_RESPONSE_BYTES` rather than buffering the whole
+/// thing first and checking after the fact.
+async fn read_capped_json(resp: reqwest::Response) -> Option<Value> {
+    let mut buf = Vec::new();
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.ok()?;
+        if buf.len() + chunk.len() > MAX_ACTIVITYPUB_RESPONSE_BYTES {
+            warn!(
+                "[clawproof] ActivityPub response exceeded {} bytes, discarding",
+                MAX_ACTIVITYPUB_RESPONSE_BYTES
+            );
+            return None;
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    serde_json::from_slice(&buf).ok()
+}
@@ -0,0 +1,171 @@
+//! Moltbook `TrustSource` implementation — the original hardwired
+//! platform, now behind the trait so it's just one registrant (alongside
+//! `fediverse`) instead of the whole data-fetching path.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::warn;
+
+use super::{RawAgentData, SourceError, TrustSource};
+
+pub struct MoltbookSource {
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl MoltbookSource {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl TrustSource for MoltbookSource {
+    async fn fetch(&self, agent: &str) -> Result<RawAgentData, SourceError> {
+        let api_key = self.api_key.as_deref().ok_or(SourceError::NotConfigured)?;
+
+        let url = format!(
+            "https://www.moltbook.com/api/v1/agents/profile?name={}",
+            agent
+        );
+
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("[clawproof] Moltbook API request failed: {}", e);
+                SourceError::Upstream("Failed to reach Moltbook API".to_string())
+            })?;
+
+        if !resp.status().is_success() {
+            if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(SourceError::NotFound);
+            }
+            let status = resp.status().as_u16();
+            return Err(SourceError::Upstream(format!(
+                "Moltbook API returned status {}",
+                status
+            )));
+        }
+
+        let profile: MoltbookProfile = resp.json().await.map_err(|e| {
+            warn!("[clawproof] Failed to parse Moltbook profile: {}", e);
+            SourceError::Upstream("Failed to parse Moltbook API response".to_string())
+        })?;
+
+        let days_old = profile
+            .created_at
+            .as_deref()
+            .and_then(|ts| {
+                chrono::DateTime::parse_from_rfc3339(ts).ok().map(|created| {
+                    let now = chrono::Utc::now();
+                    (now - created.with_timezone(&chrono::Utc)).num_seconds() as f64 / 86400.0
+                })
+            })
+            .unwrap_or(0.0);
+
+        let stats = profile.stats.unwrap_or_default();
+        let owner = profile.owner.unwrap_or_default();
+        let is_claimed = profile.is_claimed.unwrap_or(false);
+
+        let mut recent_texts = Vec::new();
+        if let Some(posts) = &profile.recent_posts {
+            for p in posts {
+                let mut text = String::new();
+                if let Some(t) = &p.title {
+                    text.push_str(t);
+                    text.push(' ');
+                }
+                if let Some(b) = &p.body {
+                    text.push_str(b);
+                }
+                let text = text.trim().to_string();
+                if !text.is_empty() {
+                    recent_texts.push(text);
+                }
+            }
+        }
+        if let Some(comments) = &profile.recent_comments {
+            for c in comments {
+                if let Some(b) = &c.body {
+                    let text = b.trim().to_string();
+                    if !text.is_empty() {
+                        recent_texts.push(text);
+                    }
+                }
+            }
+        }
+
+        Ok(RawAgentData {
+            karma: profile.karma,
+            follower_count: profile.follower_count,
+            following_count: profile.following_count,
+            posts: stats.posts,
+            comments: stats.comments,
+            days_old,
+            is_claimed,
+            x_verified: owner.x_verified,
+            recent_texts,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Moltbook API response types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct MoltbookProfile {
+    #[serde(default)]
+    karma: i64,
+    #[serde(default)]
+    follower_count: i64,
+    #[serde(default)]
+    following_count: Option<i64>,
+    #[serde(default)]
+    is_claimed: Option<bool>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    stats: Option<MoltbookStats>,
+    #[serde(default)]
+    owner: Option<MoltbookOwner>,
+    #[serde(default, rename = "recentPosts")]
+    recent_posts: Option<Vec<MoltbookPost>>,
+    #[serde(default, rename = "recentComments")]
+    recent_comments: Option<Vec<MoltbookComment>>,
+}
+
+#[derive(Deserialize, Default)]
+struct MoltbookStats {
+    #[serde(default)]
+    posts: i64,
+    #[serde(default)]
+    comments: i64,
+}
+
+#[derive(Deserialize, Default)]
+struct MoltbookOwner {
+    #[serde(default)]
+    x_verified: bool,
+}
+
+#[derive(Deserialize)]
+struct MoltbookPost {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MoltbookComment {
+    #[serde(default)]
+    body: Option<String>,
+}